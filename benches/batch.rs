@@ -0,0 +1,57 @@
+//! Compares per-value generation with batch generation, in values per
+//! second.
+
+use std::hint::black_box;
+
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use criterion::criterion_group;
+use criterion::criterion_main;
+
+use rs_asn1der2uuid7::MonotonicGenerator;
+use rs_asn1der2uuid7::new_raw_uuid_v7_asn1_now;
+
+const BATCH_SIZES: [usize; 3] = [16, 1024, 16384];
+
+fn generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+    for count in BATCH_SIZES {
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::new("now_v7_each", count), &count, |b, &n| {
+            b.iter(|| {
+                (0..n)
+                    .map(|_| new_raw_uuid_v7_asn1_now().expect("encodable"))
+                    .collect::<Vec<_>>()
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("monotonic_each", count),
+            &count,
+            |b, &n| {
+                let mut generator = MonotonicGenerator::default();
+                b.iter(|| {
+                    (0..n)
+                        .map(|_| generator.next_raw_uuid_v7_asn1().expect("encodable"))
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("batch_structs", count), &count, |b, &n| {
+            let mut generator = MonotonicGenerator::default();
+            b.iter(|| {
+                generator
+                    .next_raw_uuid_v7_asn1_batch(black_box(n))
+                    .expect("encodable")
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("batch_der", count), &count, |b, &n| {
+            let mut generator = MonotonicGenerator::default();
+            b.iter(|| generator.next_der_batch(black_box(n)).expect("encodable"))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, generate);
+criterion_main!(benches);
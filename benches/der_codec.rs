@@ -0,0 +1,66 @@
+//! Compares the allocating `RawUuidV7Asn1` codec with the fixed-size
+//! encoder and the borrowed decoder.
+
+use std::hint::black_box;
+
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::RawUuidV7Asn1Ref;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::UuidV7Asn1;
+
+const UUID: u128 = 0x0189_8F2B_1C3D_7ABC_9DEF_0123_4567_89AB;
+
+fn encode(c: &mut Criterion) {
+    let raw_uuid = RawUuidV7::from(UnverifiedUuidV7(UUID));
+    let uuid_v7 = UuidV7::try_from(UnverifiedUuidV7(UUID)).expect("valid UUIDv7");
+
+    let mut group = c.benchmark_group("encode");
+    group.bench_function("raw_uuid_v7/owned_vec", |b| {
+        b.iter(|| {
+            let asn1 = RawUuidV7Asn1::try_from(black_box(raw_uuid)).expect("encodable");
+            asn1.to_der_bytes().expect("encodable")
+        })
+    });
+    group.bench_function("raw_uuid_v7/array", |b| {
+        b.iter(|| black_box(raw_uuid).to_der_array().expect("encodable"))
+    });
+    group.bench_function("uuid_v7/owned_vec", |b| {
+        b.iter(|| {
+            let asn1 = UuidV7Asn1::try_from(black_box(uuid_v7)).expect("encodable");
+            asn1.to_der_bytes().expect("encodable")
+        })
+    });
+    group.bench_function("uuid_v7/array", |b| {
+        b.iter(|| black_box(uuid_v7).to_der_array().expect("encodable"))
+    });
+    group.finish();
+}
+
+fn decode(c: &mut Criterion) {
+    let raw_uuid = RawUuidV7::from(UnverifiedUuidV7(UUID));
+    let der_bytes = raw_uuid.to_der_array().expect("encodable");
+
+    let mut group = c.benchmark_group("decode");
+    group.bench_function("raw_uuid_v7/owned", |b| {
+        b.iter(|| {
+            let asn1 = RawUuidV7Asn1::from_der_bytes(black_box(&der_bytes)).expect("decodable");
+            RawUuidV7::try_from(asn1).expect("valid fields")
+        })
+    });
+    group.bench_function("raw_uuid_v7/borrowed", |b| {
+        b.iter(|| {
+            let asn1 = RawUuidV7Asn1Ref::from_der_bytes(black_box(&der_bytes)).expect("decodable");
+            RawUuidV7::try_from(asn1).expect("valid fields")
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, encode, decode);
+criterion_main!(benches);
@@ -1,32 +1,453 @@
+use std::env;
+use std::fmt;
 use std::io;
+use std::io::BufRead;
+use std::io::Read;
 use std::io::Write;
 use std::process::ExitCode;
 
+use uuid::NoContext;
+use uuid::Timestamp;
+use uuid::Uuid;
+
+use rs_asn1der2uuid7::BitLayout;
+use rs_asn1der2uuid7::DerReader;
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::LabelPolicy;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::dump;
+use rs_asn1der2uuid7::dump::Schema;
+use rs_asn1der2uuid7::new_raw_uuid_v7_asn1;
 use rs_asn1der2uuid7::new_raw_uuid_v7_asn1_now;
+use rs_asn1der2uuid7::pem;
+
+const USAGE: &str = "\
+usage: uuid2asn1 [COMMAND] [ARGS]
+
+commands:
+  gen [-n COUNT] [--unix-ts-ms MS] [-o FORMAT]
+      generate COUNT (default: 1) UUIDv7s and write them to stdout;
+      uses the current time unless MS is given
+  encode [-o FORMAT] [UUID...]
+      read textual UUIDv7s from the arguments, or one per line from stdin,
+      and write them to stdout; accepts the hyphenated, urn:uuid:,
+      braced, simple hex, Crockford Base32 and base64url forms
+  decode [-i FORMAT] [--strict-label] [--repair]
+      read values from stdin and print canonical UUIDs; values written
+      with the legacy BIT STRING layout of version 0.1.0 are rejected
+      unless --repair is given, which reports each repaired value on
+      stderr
+  inspect [-i FORMAT] [--strict-label]
+      read values from stdin and print the RawUuidV7 fields
+  dump [--compact]
+      read one DER value from stdin and print its TLV tree with offsets,
+      decoded fields and every byte that breaks DER; reads a RawUuidV7
+      unless --compact selects the compact UuidV7
+  help
+      print this message
+
+Running without a command is the same as `gen`.
+
+formats (-o, --output for gen and encode; -i, --input for decode and inspect):
+  der     concatenated DER values (default)
+  pem     one `-----BEGIN UUIDV7-----` PEM document per value; text
+          around the documents is ignored on input, and any label is
+          accepted unless --strict-label is given
+  base64  the base64 of each DER value on its own line
+
+exit codes:
+  0  success
+  1  I/O error
+  2  usage error
+  3  invalid input, or input that `dump` found not to be DER
+
+Errors from the library are printed with their stable error code,
+e.g. `Error [E002 invalid-version]: ...`.
+";
+
+/// The ways a command can fail, each with its own exit code.
+enum Failure {
+    Io(io::Error),
+    Usage(String),
+    Invalid { context: String, error: Error },
+    NotDer { problems: usize },
+}
+
+impl Failure {
+    fn invalid(context: impl ToString, error: Error) -> Self {
+        Self::Invalid {
+            context: context.to_string(),
+            error,
+        }
+    }
+
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Self::Io(_) => ExitCode::from(1),
+            Self::Usage(_) => ExitCode::from(2),
+            Self::Invalid {
+                error: Error::Io(e),
+                ..
+            } if e.kind() != io::ErrorKind::UnexpectedEof => ExitCode::from(1),
+            Self::Invalid { .. } | Self::NotDer { .. } => ExitCode::from(3),
+        }
+    }
+
+    fn report(&self) {
+        match self {
+            Self::Io(e) => eprintln!("Error: {}", e),
+            Self::Usage(msg) => eprintln!("Error: {}\n\n{}", msg, USAGE),
+            Self::Invalid { context, error } => {
+                eprintln!("Error [{}]: {}: {}", error.code(), context, error)
+            }
+            Self::NotDer { problems } => {
+                eprintln!("Error: input is not valid DER: {} problem(s)", problems)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => Self::Io(e),
+            other => Self::invalid("uuid2asn1", other),
+        }
+    }
+}
+
+/// How values are written to stdout or read from stdin.
+#[derive(Clone, Copy)]
+enum Format {
+    Der,
+    Pem,
+    Base64,
+}
+
+impl Format {
+    fn parse(name: &str, value: Option<String>) -> Result<Self, Failure> {
+        let value = value.ok_or_else(|| Failure::Usage(format!("{} requires a value", name)))?;
+        match value.as_str() {
+            "der" => Ok(Self::Der),
+            "pem" => Ok(Self::Pem),
+            "base64" => Ok(Self::Base64),
+            _ => Err(Failure::Usage(format!(
+                "{}: unknown format: {}",
+                name, value
+            ))),
+        }
+    }
+}
+
+fn asn1_to_writer(
+    asn1_uuid: &RawUuidV7Asn1,
+    format: Format,
+    writer: &mut impl Write,
+) -> Result<(), Failure> {
+    match format {
+        Format::Der => writer.write_all(&asn1_uuid.to_der_bytes()?)?,
+        Format::Pem => writer.write_all(asn1_uuid.to_pem_string()?.as_bytes())?,
+        Format::Base64 => writeln!(writer, "{}", asn1_uuid.to_base64()?)?,
+    }
+    Ok(())
+}
+
+fn parse_number(name: &str, value: Option<String>) -> Result<u64, Failure> {
+    let value = value.ok_or_else(|| Failure::Usage(format!("{} requires a value", name)))?;
+    value
+        .parse()
+        .map_err(|_| Failure::Usage(format!("{}: not a number: {}", name, value)))
+}
+
+/// Largest timestamp a UUIDv7 can hold.
+const UNIX_TS_MS_MAX: u64 = 0xFFFF_FFFF_FFFF;
+
+fn ms2timestamp(unix_ts_ms: u64) -> Timestamp {
+    let seconds = unix_ts_ms / 1000;
+    let nanos = (unix_ts_ms % 1000) as u32 * 1_000_000;
+    Timestamp::from_unix(NoContext, seconds, nanos)
+}
+
+fn generate(args: impl Iterator<Item = String>, out: &mut impl Write) -> Result<(), Failure> {
+    let mut count: u64 = 1;
+    let mut unix_ts_ms: Option<u64> = None;
+    let mut format = Format::Der;
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--count" => count = parse_number(&arg, args.next())?,
+            "--unix-ts-ms" => {
+                let ms = parse_number(&arg, args.next())?;
+                if ms > UNIX_TS_MS_MAX {
+                    return Err(Failure::Usage(format!(
+                        "{}: {} does not fit into 48 bits (max {})",
+                        arg, ms, UNIX_TS_MS_MAX
+                    )));
+                }
+                unix_ts_ms = Some(ms);
+            }
+            "-o" | "--output" => format = Format::parse(&arg, args.next())?,
+            _ => return Err(Failure::Usage(format!("gen: unknown argument: {}", arg))),
+        }
+    }
+
+    for _ in 0..count {
+        let asn1_uuid = match unix_ts_ms {
+            Some(ms) => new_raw_uuid_v7_asn1(ms2timestamp(ms))?,
+            None => new_raw_uuid_v7_asn1_now()?,
+        };
+        asn1_to_writer(&asn1_uuid, format, out)?;
+    }
+    Ok(())
+}
 
-fn now2uuid2asn1() -> Result<Vec<u8>, io::Error> {
-    let asn1_uuid = new_raw_uuid_v7_asn1_now()?;
-    asn1_uuid.to_der_bytes()
+fn text2asn1(text: &str) -> Result<RawUuidV7Asn1, Failure> {
+    let uuid_v7 = UuidV7::parse_str(text).map_err(|e| Failure::invalid(text, e))?;
+    let raw_uuid = RawUuidV7::from(uuid_v7);
+    RawUuidV7Asn1::try_from(raw_uuid).map_err(|e| Failure::invalid(text, e))
 }
 
-fn der2writer(der_bytes: &[u8], writer: &mut impl Write) -> Result<(), io::Error> {
-    writer.write_all(der_bytes)?;
+fn encode(args: impl Iterator<Item = String>, out: &mut impl Write) -> Result<(), Failure> {
+    let mut format = Format::Der;
+    let mut texts = Vec::new();
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => format = Format::parse(&arg, args.next())?,
+            _ => texts.push(arg),
+        }
+    }
+    if !texts.is_empty() {
+        for text in &texts {
+            asn1_to_writer(&text2asn1(text)?, format, out)?;
+        }
+        return Ok(());
+    }
+
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        asn1_to_writer(&text2asn1(text)?, format, out)?;
+    }
     Ok(())
 }
 
-fn der2stdout(der_bytes: &[u8]) -> Result<(), io::Error> {
-    der2writer(der_bytes, &mut io::stdout())
+/// Where a value was read from: a byte offset for DER, a 1-based record
+/// number for the text formats.
+#[derive(Clone, Copy)]
+enum Position {
+    Offset(u64),
+    Record(usize),
+}
+
+impl Position {
+    fn key(self) -> &'static str {
+        match self {
+            Self::Offset(_) => "offset",
+            Self::Record(_) => "record",
+        }
+    }
+
+    fn value(self) -> u64 {
+        match self {
+            Self::Offset(offset) => offset,
+            Self::Record(record) => record as u64,
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.key(), self.value())
+    }
+}
+
+/// Options of `decode` and `inspect`.
+struct InputOptions {
+    format: Format,
+    label_policy: LabelPolicy,
+    /// Accept the legacy BIT STRING layout; `decode` only.
+    repair: bool,
+}
+
+impl InputOptions {
+    fn parse(command: &str, args: impl Iterator<Item = String>) -> Result<Self, Failure> {
+        let mut options = Self {
+            format: Format::Der,
+            label_policy: LabelPolicy::Any,
+            repair: false,
+        };
+
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-i" | "--input" => options.format = Format::parse(&arg, args.next())?,
+                "--strict-label" => options.label_policy = LabelPolicy::Strict,
+                "--repair" if command == "decode" => options.repair = true,
+                _ => {
+                    return Err(Failure::Usage(format!(
+                        "{}: unexpected argument: {}",
+                        command, arg
+                    )));
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Calls `f` with the position and value of each value on stdin.
+fn for_each_asn1(
+    options: &InputOptions,
+    mut f: impl FnMut(Position, RawUuidV7Asn1) -> Result<(), Failure>,
+) -> Result<(), Failure> {
+    let label_policy = options.label_policy;
+    match options.format {
+        Format::Der => {
+            let mut reader = DerReader::new(io::stdin().lock());
+            loop {
+                let position = Position::Offset(reader.position());
+                let next = reader
+                    .read_next()
+                    .map_err(|e| Failure::invalid(position, e))?;
+                match next {
+                    Some(asn1_uuid) => f(position, asn1_uuid)?,
+                    None => return Ok(()),
+                }
+            }
+        }
+        Format::Pem => {
+            let text = io::read_to_string(io::stdin().lock())?;
+            let records = pem::pem_records::<RawUuidV7Asn1>(&text, label_policy);
+            for (index, record) in records.enumerate() {
+                let position = Position::Record(index + 1);
+                f(position, record.map_err(|e| Failure::invalid(position, e))?)?;
+            }
+            Ok(())
+        }
+        Format::Base64 => {
+            let lines = io::stdin().lock().lines();
+            let texts = lines.filter(|line| !line.as_ref().is_ok_and(|l| l.trim().is_empty()));
+            for (index, line) in texts.enumerate() {
+                let position = Position::Record(index + 1);
+                let asn1_uuid = RawUuidV7Asn1::from_base64(&line?)
+                    .map_err(|e| Failure::invalid(position, e))?;
+                f(position, asn1_uuid)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn decode(options: &InputOptions, out: &mut impl Write) -> Result<(), Failure> {
+    for_each_asn1(options, |position, asn1_uuid| {
+        let asn1_uuid = match asn1_uuid.bit_layout() {
+            BitLayout::LegacyLsbFirst if options.repair => {
+                eprintln!(
+                    "Warning: {}: repaired the legacy BIT STRING layout",
+                    position
+                );
+                asn1_uuid
+                    .repair()
+                    .map_err(|e| Failure::invalid(position, e))?
+            }
+            _ => asn1_uuid,
+        };
+        let uuid = Uuid::try_from(asn1_uuid).map_err(|e| Failure::invalid(position, e))?;
+        writeln!(out, "{}", uuid)?;
+        Ok(())
+    })
+}
+
+fn inspect(options: &InputOptions, out: &mut impl Write) -> Result<(), Failure> {
+    for_each_asn1(options, |position, asn1_uuid| {
+        let layout = asn1_uuid.bit_layout();
+        let raw: RawUuidV7 = asn1_uuid
+            .to_raw_uuid_v7_compat()
+            .map_err(|e| Failure::invalid(position, e.into()))?;
+        writeln!(
+            out,
+            "{}={} unix_ts_ms={} version={} rand_a=0x{:03x} variant=0b{:02b} rand_b=0x{:016x} layout={:?}",
+            position.key(),
+            position.value(),
+            raw.unix_ts_ms,
+            raw.version,
+            raw.rand_a,
+            raw.variant,
+            raw.rand_b,
+            layout
+        )?;
+        Ok(())
+    })
+}
+
+fn dump(args: impl Iterator<Item = String>, out: &mut impl Write) -> Result<(), Failure> {
+    let mut schema = Schema::RawUuidV7;
+    for arg in args {
+        match arg.as_str() {
+            "--compact" => schema = Schema::UuidV7,
+            _ => {
+                return Err(Failure::Usage(format!(
+                    "dump: unexpected argument: {}",
+                    arg
+                )));
+            }
+        }
+    }
+
+    let mut der_bytes = Vec::new();
+    io::stdin().lock().read_to_end(&mut der_bytes)?;
+    let dump = dump::dump(&der_bytes, schema);
+    write!(out, "{}", dump)?;
+    out.flush()?;
+
+    match dump.problems().count() {
+        0 => Ok(()),
+        problems => Err(Failure::NotDer { problems }),
+    }
+}
+
+fn run() -> Result<(), Failure> {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+
+    match command.as_deref() {
+        None | Some("gen") => generate(args, &mut out)?,
+        Some("encode") => encode(args, &mut out)?,
+        Some("decode") => decode(&InputOptions::parse("decode", args)?, &mut out)?,
+        Some("inspect") => inspect(&InputOptions::parse("inspect", args)?, &mut out)?,
+        Some("dump") => dump(args, &mut out)?,
+        Some("help") | Some("-h") | Some("--help") => {
+            write!(out, "{}", USAGE)?;
+        }
+        Some(other) => return Err(Failure::Usage(format!("unknown command: {}", other))),
+    }
+
+    out.flush()?;
+    Ok(())
 }
 
 fn main() -> ExitCode {
-    match now2uuid2asn1() {
-        Ok(der_bytes) => {
-            der2stdout(&der_bytes).expect("Failed to write to stdout");
-            ExitCode::SUCCESS
-        }
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            ExitCode::FAILURE
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            failure.report();
+            failure.exit_code()
         }
     }
 }
@@ -0,0 +1,151 @@
+//! Runs the binary on bad input and checks the exit code and the message of
+//! each failure.
+
+use std::io::Write;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.der.txt`.
+const MIXED_1_DER_HEX: &str = "301f0206019123456789020107030304123003020680030902048d159e26af37bc";
+
+/// `mixed-1` as written by version 0.1.0, with right-aligned BIT STRINGs.
+const MIXED_1_LEGACY_HEX: &str = "301f0206019123456789020107030304012303020602030902\
+0123456789abcdef";
+
+fn hex2bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("hex"))
+        .collect()
+}
+
+fn run(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_uuid2asn1"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawnable");
+    let mut child_stdin = child.stdin.take().expect("piped stdin");
+    child_stdin.write_all(stdin).expect("writable stdin");
+    drop(child_stdin);
+    child.wait_with_output().expect("finished")
+}
+
+/// Asserts that the command failed with `code` and a first line of stderr
+/// starting with `message`.
+fn assert_failure(args: &[&str], stdin: &[u8], code: i32, message: &str) {
+    let output = run(args, stdin);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(code), "{args:?}: {stderr}");
+    assert!(stderr.starts_with(message), "{args:?}: {stderr}");
+}
+
+#[test]
+fn encode_failures() {
+    assert_failure(
+        &["encode", "01898f2b-1c3d-4abc-9def-0123456789ab"],
+        b"",
+        3,
+        "Error [E002 invalid-version]: 01898f2b-1c3d-4abc-9def-0123456789ab: ",
+    );
+    assert_failure(
+        &["encode"],
+        b"not-a-uuid\n",
+        3,
+        "Error [E013 invalid-text]: not-a-uuid: ",
+    );
+    assert_failure(
+        &["encode", "-o", "xml"],
+        b"",
+        2,
+        "Error: -o: unknown format: xml",
+    );
+}
+
+#[test]
+fn decode_failures() {
+    let der = hex2bytes(MIXED_1_DER_HEX);
+    assert_failure(
+        &["decode"],
+        &der[..20],
+        3,
+        "Error [E010 truncated]: offset 0: ",
+    );
+
+    let mut two = der.clone();
+    two.extend_from_slice(&der[..1]);
+    assert_failure(&["decode"], &two, 3, "Error [E010 truncated]: offset 33: ");
+
+    assert_failure(
+        &["decode", "-i", "base64"],
+        b"not base64\n",
+        3,
+        "Error [E018",
+    );
+    assert_failure(
+        &["decode", "--compact"],
+        &der,
+        2,
+        "Error: decode: unexpected argument: --compact",
+    );
+}
+
+#[test]
+fn decode_repairs_the_legacy_layout_only_on_request() {
+    let legacy = hex2bytes(MIXED_1_LEGACY_HEX);
+    assert_failure(
+        &["decode"],
+        &legacy,
+        3,
+        "Error [E006 nonzero-unused-bits]: offset 0: ",
+    );
+
+    let output = run(&["decode", "--repair"], &legacy);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "01912345-6789-7123-8123-456789abcdef\n"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Warning: offset 0: repaired the legacy BIT STRING layout\n"
+    );
+
+    // Values already in the DER layout are not reported.
+    let output = run(&["decode", "--repair"], &hex2bytes(MIXED_1_DER_HEX));
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn dump_failures() {
+    let mut der = hex2bytes(MIXED_1_DER_HEX);
+    der[0] = 0x31;
+    let output = run(&["dump"], &der);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(3), "{stderr}");
+    assert_eq!(stderr, "Error: input is not valid DER: 1 problem(s)\n");
+    // The tree is still printed.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("expected SEQUENCE, found SET"), "{stdout}");
+
+    assert_failure(
+        &["dump", "--strict"],
+        &der,
+        2,
+        "Error: dump: unexpected argument: --strict",
+    );
+}
+
+#[test]
+fn unknown_commands_are_usage_errors() {
+    assert_failure(
+        &["frobnicate"],
+        b"",
+        2,
+        "Error: unknown command: frobnicate",
+    );
+}
@@ -0,0 +1,449 @@
+#!/usr/bin/env python3
+"""Generates the DER, PER, OER, XER and JER test vectors in tests/vectors
+for the types of uuid-v7.asn1.
+
+Every vector is encoded by the small X.690 DER, X.691 PER, X.696 OER, X.693
+XER and X.697 JER encoders below. The same values are also encoded from the
+schema with asn1tools, and the script aborts on any mismatch. JER is not
+cross-checked: its BIT STRINGs use the object form with a length, while
+asn1tools writes fixed-size ones as plain hex strings.
+
+asn1tools is required. With --unchecked, the vectors are written without the
+cross-check when it is missing, and the header of every file says so.
+
+Usage: ./gen-vectors.py [--unchecked]
+"""
+
+import json
+import os
+import sys
+
+SCHEMA = "./uuid-v7.asn1"
+OUTDIR = "./tests/vectors"
+
+# (name, unix_ts_ms, rand_a, rand_b)
+SEEDS = [
+    ("zero", 0, 0x000, 0x0000000000000000),
+    ("ts-one", 1, 0x000, 0x0000000000000000),
+    ("ts-7f", 0x7F, 0x001, 0x0000000000000001),
+    ("ts-80", 0x80, 0x800, 0x2000000000000000),
+    ("ts-ff", 0xFF, 0xFFF, 0x3FFFFFFFFFFFFFFF),
+    ("ts-100", 0x100, 0x555, 0x1555555555555555),
+    ("ts-2023", 1700000000000, 0xAAA, 0x2AAAAAAAAAAAAAAA),
+    ("ts-rfc9562", 0x017F22E279B0, 0xCC3, 0x18C4DC0C0C07398F),
+    ("ts-max", 0xFFFFFFFFFFFF, 0xFFF, 0x3FFFFFFFFFFFFFFF),
+    ("ts-max-zero-rand", 0xFFFFFFFFFFFF, 0x000, 0x0000000000000000),
+    ("mixed-1", 0x0191_2345_6789, 0x123, 0x0123456789ABCDEF),
+    ("mixed-2", 0x00FF_FF00_00FF, 0x801, 0x3000000000000001),
+]
+
+VERSION = 7
+VARIANT = 0b10
+
+
+def der_len(n):
+    if n < 0x80:
+        return bytes([n])
+    body = n.to_bytes((n.bit_length() + 7) // 8, "big")
+    return bytes([0x80 | len(body)]) + body
+
+
+def der_tlv(tag, content):
+    return bytes([tag]) + der_len(len(content)) + content
+
+
+def der_integer(value):
+    # Minimal two's complement; non-negative values only.
+    size = value.bit_length() // 8 + 1
+    return der_tlv(0x02, value.to_bytes(size, "big"))
+
+
+def der_bit_string(value, nbits):
+    octets = (nbits + 7) // 8
+    unused = octets * 8 - nbits
+    body = (value << unused).to_bytes(octets, "big")
+    return der_tlv(0x03, bytes([unused]) + body)
+
+
+def der_raw_uuid_v7(ts, rand_a, rand_b):
+    return der_tlv(
+        0x30,
+        der_integer(ts)
+        + der_integer(VERSION)
+        + der_bit_string(rand_a, 12)
+        + der_bit_string(VARIANT, 2)
+        + der_bit_string(rand_b, 62),
+    )
+
+
+def der_uuid_v7(ts, rand_a, rand_b):
+    return der_tlv(
+        0x30,
+        der_integer(ts) + der_bit_string((rand_a << 62) | rand_b, 74),
+    )
+
+
+class BitWriter:
+    def __init__(self, aligned):
+        self.aligned = aligned
+        self.bits = ""
+
+    def write(self, value, nbits):
+        self.bits += format(value, "0%db" % nbits)
+
+    def align(self):
+        if self.aligned:
+            self.pad()
+
+    def pad(self):
+        self.bits += "0" * (-len(self.bits) % 8)
+
+    def octets(self):
+        self.pad()
+        return int(self.bits, 2).to_bytes(len(self.bits) // 8, "big")
+
+
+def per_integer(writer, value):
+    # Unconstrained whole number: one-octet length, minimal two's complement.
+    size = value.bit_length() // 8 + 1
+    writer.align()
+    writer.write(size, 8)
+    writer.write(value, size * 8)
+
+
+def per_bit_string(writer, value, nbits):
+    # Fixed size: no length; aligned in ALIGNED PER only above 16 bits.
+    if nbits > 16:
+        writer.align()
+    writer.write(value, nbits)
+
+
+def per_raw_uuid_v7(ts, rand_a, rand_b, aligned):
+    writer = BitWriter(aligned)
+    per_integer(writer, ts)
+    per_integer(writer, VERSION)
+    per_bit_string(writer, rand_a, 12)
+    per_bit_string(writer, VARIANT, 2)
+    per_bit_string(writer, rand_b, 62)
+    return writer.octets()
+
+
+def per_uuid_v7(ts, rand_a, rand_b, aligned):
+    writer = BitWriter(aligned)
+    per_integer(writer, ts)
+    per_bit_string(writer, (rand_a << 62) | rand_b, 74)
+    return writer.octets()
+
+
+def oer_length(n):
+    if n < 0x80:
+        return bytes([n])
+    body = n.to_bytes((n.bit_length() + 7) // 8, "big")
+    return bytes([0x80 | len(body)]) + body
+
+
+def oer_integer(value):
+    # Unconstrained: length determinant, minimal two's complement.
+    body = value.to_bytes(value.bit_length() // 8 + 1, "big")
+    return oer_length(len(body)) + body
+
+
+def oer_bit_string(value, nbits):
+    # Fixed size: left-aligned octets without a length.
+    return bits(value, nbits)[0]
+
+
+def oer_raw_uuid_v7(ts, rand_a, rand_b):
+    return (
+        oer_integer(ts)
+        + oer_integer(VERSION)
+        + oer_bit_string(rand_a, 12)
+        + oer_bit_string(VARIANT, 2)
+        + oer_bit_string(rand_b, 62)
+    )
+
+
+def oer_uuid_v7(ts, rand_a, rand_b):
+    return oer_integer(ts) + oer_bit_string((rand_a << 62) | rand_b, 74)
+
+
+def xer_bit_string(value, nbits):
+    return format(value, "0%db" % nbits)
+
+
+def xer_raw_uuid_v7(ts, rand_a, rand_b):
+    # Canonical XER: no whitespace at all.
+    return (
+        "<RawUuidV7>"
+        "<unix-ts-ms>%d</unix-ts-ms>"
+        "<version>%d</version>"
+        "<rand-a>%s</rand-a>"
+        "<variant>%s</variant>"
+        "<rand-b>%s</rand-b>"
+        "</RawUuidV7>"
+        % (
+            ts,
+            VERSION,
+            xer_bit_string(rand_a, 12),
+            xer_bit_string(VARIANT, 2),
+            xer_bit_string(rand_b, 62),
+        )
+    )
+
+
+def xer_uuid_v7(ts, rand_a, rand_b):
+    return (
+        "<UuidV7><unix-ts-ms>%d</unix-ts-ms><rand-ab>%s</rand-ab></UuidV7>"
+        % (ts, xer_bit_string((rand_a << 62) | rand_b, 74))
+    )
+
+
+def jer_bit_string(value, nbits):
+    return {"value": bits(value, nbits)[0].hex().upper(), "length": nbits}
+
+
+def jer(value):
+    return json.dumps(value, separators=(",", ":"))
+
+
+def jer_raw_uuid_v7(ts, rand_a, rand_b):
+    return jer(
+        {
+            "unix-ts-ms": ts,
+            "version": VERSION,
+            "rand-a": jer_bit_string(rand_a, 12),
+            "variant": jer_bit_string(VARIANT, 2),
+            "rand-b": jer_bit_string(rand_b, 62),
+        }
+    )
+
+
+def jer_uuid_v7(ts, rand_a, rand_b):
+    return jer(
+        {"unix-ts-ms": ts, "rand-ab": jer_bit_string((rand_a << 62) | rand_b, 74)}
+    )
+
+
+def uuid_of(ts, rand_a, rand_b):
+    value = (ts << 80) | (VERSION << 76) | (rand_a << 64) | (VARIANT << 62) | rand_b
+    h = "%032x" % value
+    return "-".join([h[0:8], h[8:12], h[12:16], h[16:20], h[20:32]])
+
+
+def bits(value, nbits):
+    octets = (nbits + 7) // 8
+    unused = octets * 8 - nbits
+    return ((value << unused).to_bytes(octets, "big"), nbits)
+
+
+def reference_encoders(codec):
+    try:
+        import asn1tools
+    except ImportError:
+        return None
+
+    spec = asn1tools.compile_files(SCHEMA, codec)
+
+    def raw(ts, rand_a, rand_b):
+        return spec.encode(
+            "RawUuidV7",
+            {
+                "unix-ts-ms": ts,
+                "version": VERSION,
+                "rand-a": bits(rand_a, 12),
+                "variant": bits(VARIANT, 2),
+                "rand-b": bits(rand_b, 62),
+            },
+        )
+
+    def compact(ts, rand_a, rand_b):
+        return spec.encode(
+            "UuidV7",
+            {"unix-ts-ms": ts, "rand-ab": bits((rand_a << 62) | rand_b, 74)},
+        )
+
+    return raw, compact
+
+
+def check(reference, name, ts, rand_a, rand_b, raw, compact):
+    if reference is not None:
+        ref_raw, ref_compact = reference
+        assert ref_raw(ts, rand_a, rand_b) == raw, name
+        assert ref_compact(ts, rand_a, rand_b) == compact, name
+
+
+# asn1tools cannot write the JER object form of a BIT STRING; see above.
+JER_PROVENANCE = "# Encoded by gen-vectors.py only; JER is not cross-checked with asn1tools."
+
+
+def provenance(references):
+    """Returns the header line that says how the vectors were checked."""
+    if references["der"] is None:
+        return "# Encoded by gen-vectors.py only; NOT cross-checked with asn1tools."
+    import asn1tools
+
+    return "# Cross-checked against uuid-v7.asn1 with asn1tools %s." % asn1tools.__version__
+
+
+def main():
+    unchecked = sys.argv[1:] == ["--unchecked"]
+    if sys.argv[1:] not in ([], ["--unchecked"]):
+        sys.exit(__doc__)
+
+    references = {
+        codec: reference_encoders(codec) for codec in ("der", "uper", "per", "oer", "xer")
+    }
+    if references["der"] is None:
+        if not unchecked:
+            sys.exit(
+                "asn1tools not found; install it, or pass --unchecked to write "
+                "the vectors without the schema cross-check"
+            )
+        print("asn1tools not found; skipping the schema cross-check", file=sys.stderr)
+    checked_by = provenance(references)
+
+    raw_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (RawUuidV7). Do not edit.",
+        checked_by,
+        "# name unix_ts_ms version rand_a variant rand_b der_hex",
+    ]
+    compact_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (UuidV7). Do not edit.",
+        checked_by,
+        "# name uuid der_hex",
+    ]
+    raw_per_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (RawUuidV7). Do not edit.",
+        checked_by,
+        "# name unix_ts_ms version rand_a variant rand_b uper_hex aper_hex",
+    ]
+    compact_per_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (UuidV7). Do not edit.",
+        checked_by,
+        "# name uuid uper_hex aper_hex",
+    ]
+    raw_oer_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (RawUuidV7). Do not edit.",
+        checked_by,
+        "# name unix_ts_ms version rand_a variant rand_b oer_hex",
+    ]
+    compact_oer_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (UuidV7). Do not edit.",
+        checked_by,
+        "# name uuid oer_hex",
+    ]
+    raw_xer_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (RawUuidV7). Do not edit.",
+        checked_by,
+        "# name unix_ts_ms version rand_a variant rand_b cxer",
+    ]
+    compact_xer_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (UuidV7). Do not edit.",
+        checked_by,
+        "# name uuid cxer",
+    ]
+    raw_jer_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (RawUuidV7). Do not edit.",
+        JER_PROVENANCE,
+        "# name unix_ts_ms version rand_a variant rand_b jer",
+    ]
+    compact_jer_lines = [
+        "# Generated by gen-vectors.py for uuid-v7.asn1 (UuidV7). Do not edit.",
+        JER_PROVENANCE,
+        "# name uuid jer",
+    ]
+
+    for name, ts, rand_a, rand_b in SEEDS:
+        raw = der_raw_uuid_v7(ts, rand_a, rand_b)
+        compact = der_uuid_v7(ts, rand_a, rand_b)
+        check(references["der"], name, ts, rand_a, rand_b, raw, compact)
+
+        raw_uper = per_raw_uuid_v7(ts, rand_a, rand_b, False)
+        compact_uper = per_uuid_v7(ts, rand_a, rand_b, False)
+        check(references["uper"], name, ts, rand_a, rand_b, raw_uper, compact_uper)
+
+        raw_aper = per_raw_uuid_v7(ts, rand_a, rand_b, True)
+        compact_aper = per_uuid_v7(ts, rand_a, rand_b, True)
+        check(references["per"], name, ts, rand_a, rand_b, raw_aper, compact_aper)
+
+        raw_oer = oer_raw_uuid_v7(ts, rand_a, rand_b)
+        compact_oer = oer_uuid_v7(ts, rand_a, rand_b)
+        check(references["oer"], name, ts, rand_a, rand_b, raw_oer, compact_oer)
+
+        raw_xer = xer_raw_uuid_v7(ts, rand_a, rand_b)
+        compact_xer = xer_uuid_v7(ts, rand_a, rand_b)
+        check(
+            references["xer"],
+            name,
+            ts,
+            rand_a,
+            rand_b,
+            raw_xer.encode(),
+            compact_xer.encode(),
+        )
+
+        raw_jer = jer_raw_uuid_v7(ts, rand_a, rand_b)
+        compact_jer = jer_uuid_v7(ts, rand_a, rand_b)
+
+        raw_lines.append(
+            "%s %d %d 0x%03x 0x%x 0x%016x %s"
+            % (name, ts, VERSION, rand_a, VARIANT, rand_b, raw.hex())
+        )
+        compact_lines.append(
+            "%s %s %s" % (name, uuid_of(ts, rand_a, rand_b), compact.hex())
+        )
+        raw_per_lines.append(
+            "%s %d %d 0x%03x 0x%x 0x%016x %s %s"
+            % (name, ts, VERSION, rand_a, VARIANT, rand_b, raw_uper.hex(), raw_aper.hex())
+        )
+        compact_per_lines.append(
+            "%s %s %s %s"
+            % (name, uuid_of(ts, rand_a, rand_b), compact_uper.hex(), compact_aper.hex())
+        )
+        raw_oer_lines.append(
+            "%s %d %d 0x%03x 0x%x 0x%016x %s"
+            % (name, ts, VERSION, rand_a, VARIANT, rand_b, raw_oer.hex())
+        )
+        compact_oer_lines.append(
+            "%s %s %s" % (name, uuid_of(ts, rand_a, rand_b), compact_oer.hex())
+        )
+        raw_xer_lines.append(
+            "%s %d %d 0x%03x 0x%x 0x%016x %s"
+            % (name, ts, VERSION, rand_a, VARIANT, rand_b, raw_xer)
+        )
+        compact_xer_lines.append(
+            "%s %s %s" % (name, uuid_of(ts, rand_a, rand_b), compact_xer)
+        )
+        raw_jer_lines.append(
+            "%s %d %d 0x%03x 0x%x 0x%016x %s"
+            % (name, ts, VERSION, rand_a, VARIANT, rand_b, raw_jer)
+        )
+        compact_jer_lines.append(
+            "%s %s %s" % (name, uuid_of(ts, rand_a, rand_b), compact_jer)
+        )
+
+    os.makedirs(OUTDIR, exist_ok=True)
+    with open(os.path.join(OUTDIR, "raw-uuid-v7.der.txt"), "w") as f:
+        f.write("\n".join(raw_lines) + "\n")
+    with open(os.path.join(OUTDIR, "uuid-v7.der.txt"), "w") as f:
+        f.write("\n".join(compact_lines) + "\n")
+    with open(os.path.join(OUTDIR, "raw-uuid-v7.per.txt"), "w") as f:
+        f.write("\n".join(raw_per_lines) + "\n")
+    with open(os.path.join(OUTDIR, "uuid-v7.per.txt"), "w") as f:
+        f.write("\n".join(compact_per_lines) + "\n")
+    with open(os.path.join(OUTDIR, "raw-uuid-v7.oer.txt"), "w") as f:
+        f.write("\n".join(raw_oer_lines) + "\n")
+    with open(os.path.join(OUTDIR, "uuid-v7.oer.txt"), "w") as f:
+        f.write("\n".join(compact_oer_lines) + "\n")
+    with open(os.path.join(OUTDIR, "raw-uuid-v7.xer.txt"), "w") as f:
+        f.write("\n".join(raw_xer_lines) + "\n")
+    with open(os.path.join(OUTDIR, "uuid-v7.xer.txt"), "w") as f:
+        f.write("\n".join(compact_xer_lines) + "\n")
+    with open(os.path.join(OUTDIR, "raw-uuid-v7.jer.txt"), "w") as f:
+        f.write("\n".join(raw_jer_lines) + "\n")
+    with open(os.path.join(OUTDIR, "uuid-v7.jer.txt"), "w") as f:
+        f.write("\n".join(compact_jer_lines) + "\n")
+
+
+if __name__ == "__main__":
+    main()
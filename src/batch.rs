@@ -0,0 +1,152 @@
+//! Batch generation with one clock read per batch.
+//!
+//! Calling [`MonotonicGenerator::next_u128`] in a loop reads the system clock
+//! once per value. The batch methods here read it once for the whole batch
+//! and let the counter of the [`MonotonicStrategy`](crate::MonotonicStrategy)
+//! supply the sequence bits. When the counter of a millisecond is used up,
+//! the timestamp moves forward by one millisecond as usual, so a large batch
+//! may run slightly ahead of the clock.
+//!
+//! A batch is returned either as values or as a [`DerBatch`]: the DER
+//! encodings back to back in one buffer, with the offset of each value.
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use crate::Error;
+use crate::MonotonicGenerator;
+use crate::RawUuidV7;
+#[cfg(feature = "std")]
+use crate::RawUuidV7Asn1;
+use crate::source::Clock;
+use crate::source::RandomSource;
+#[cfg(feature = "std")]
+use crate::source::SystemClock;
+#[cfg(feature = "std")]
+use crate::source::SystemRandom;
+
+/// DER `RawUuidV7` values stored back to back in one buffer.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DerBatch {
+    der_bytes: Vec<u8>,
+    /// Start of each value in `der_bytes`.
+    offsets: Vec<usize>,
+}
+
+impl DerBatch {
+    /// Encodes UUIDv7 `u128` values as `RawUuidV7`.
+    pub fn encode(values: impl IntoIterator<Item = u128>) -> Result<Self, Error> {
+        let values = values.into_iter();
+        let mut batch = Self {
+            der_bytes: Vec::new(),
+            offsets: Vec::with_capacity(values.size_hint().0),
+        };
+        // UUIDv7s with current timestamps encode in 33 bytes.
+        batch.der_bytes.reserve(batch.offsets.capacity() * 33);
+        for value in values {
+            let der_array = RawUuidV7::from(value).to_der_array()?;
+            batch.offsets.push(batch.der_bytes.len());
+            batch.der_bytes.extend_from_slice(&der_array);
+        }
+        Ok(batch)
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns true if the batch holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Returns all encodings as one buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.der_bytes
+    }
+
+    /// Returns the offset of each value in [`DerBatch::as_bytes`].
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Returns the encoding of the value at `index`.
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let start = *self.offsets.get(index)?;
+        let end = self
+            .offsets
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.der_bytes.len());
+        self.der_bytes.get(start..end)
+    }
+
+    /// Returns an iterator over the encodings of the values.
+    pub fn iter(&self) -> impl Iterator<Item = &[u8]> {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+
+    /// Returns the buffer and the offsets.
+    pub fn into_parts(self) -> (Vec<u8>, Vec<usize>) {
+        (self.der_bytes, self.offsets)
+    }
+}
+
+impl MonotonicGenerator {
+    /// Creates one UUIDv7 `u128` value for each item of `random_bytes`, all
+    /// from the same time since the Unix epoch.
+    ///
+    /// Fails if the counter of the largest timestamp runs out within the
+    /// batch.
+    pub fn next_batch_u128_from(
+        &mut self,
+        since_epoch: Duration,
+        random_bytes: impl IntoIterator<Item = u128>,
+    ) -> Result<Vec<u128>, Error> {
+        random_bytes
+            .into_iter()
+            .map(|random_bytes| self.next_u128_from(since_epoch, random_bytes))
+            .collect()
+    }
+
+    /// Creates `count` UUIDv7 `u128` values from one reading of `clock` and
+    /// `count` readings of `random`.
+    pub fn next_batch_u128_with(
+        &mut self,
+        mut clock: impl Clock,
+        mut random: impl RandomSource,
+        count: usize,
+    ) -> Result<Vec<u128>, Error> {
+        let since_epoch = clock.now();
+        let random_bytes = (0..count).map(|_| random.next_u128());
+        self.next_batch_u128_from(since_epoch, random_bytes)
+    }
+
+    /// Creates `count` UUIDv7 `u128` values from one reading of the system
+    /// clock and a UUIDv4 each.
+    #[cfg(feature = "std")]
+    pub fn next_batch_u128(&mut self, count: usize) -> Result<Vec<u128>, Error> {
+        self.next_batch_u128_with(SystemClock, SystemRandom, count)
+    }
+
+    /// Creates `count` UUIDv7s from one reading of the system clock as
+    /// `RawUuidV7Asn1` values.
+    #[cfg(feature = "std")]
+    pub fn next_raw_uuid_v7_asn1_batch(
+        &mut self,
+        count: usize,
+    ) -> Result<Vec<RawUuidV7Asn1>, Error> {
+        self.next_batch_u128(count)?
+            .into_iter()
+            .map(RawUuidV7Asn1::try_from)
+            .collect()
+    }
+
+    /// Creates `count` UUIDv7s from one reading of the system clock, encoded
+    /// into one DER buffer.
+    #[cfg(feature = "std")]
+    pub fn next_der_batch(&mut self, count: usize) -> Result<DerBatch, Error> {
+        DerBatch::encode(self.next_batch_u128(count)?)
+    }
+}
@@ -0,0 +1,217 @@
+//! Tolerant decoder for BER and CER `RawUuidV7` values from older ASN.1
+//! stacks.
+//!
+//! On top of DER, the decoder accepts:
+//!
+//! - the indefinite length form for the SEQUENCE and constructed BIT STRINGs;
+//! - non-minimal length octets;
+//! - INTEGERs with redundant leading zero bytes;
+//! - constructed BIT STRINGs, whose segments are concatenated;
+//! - BIT STRINGs whose trailing unused bits are not zero; they are ignored.
+//!
+//! Values that do not fit into [`RawUuidV7`], such as negative INTEGERs, are
+//! still errors. [`canonicalize`] re-encodes the value as strict DER.
+
+use alloc::vec::Vec;
+
+use der::ErrorKind;
+use der::Tag;
+
+use crate::Error;
+use crate::FieldError;
+use crate::RawUuidV7;
+use crate::RawUuidV7Asn1;
+use crate::RawUuidV7Field;
+use crate::tlv;
+use crate::tlv::Tlv;
+use crate::tlv::TlvError;
+
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+
+/// Decodes a single BER, CER or DER `RawUuidV7` value.
+///
+/// The input must contain exactly one value; trailing bytes are rejected.
+pub fn decode(ber_bytes: &[u8]) -> Result<RawUuidV7, Error> {
+    let (raw_uuid, rest) = decode_prefix(ber_bytes)?;
+    if !rest.is_empty() {
+        let decoded = ber_bytes.len() - rest.len();
+        return Err(tlv::trailing_data(decoded, ber_bytes.len()).into());
+    }
+    Ok(raw_uuid)
+}
+
+/// Decodes the first BER, CER or DER `RawUuidV7` value of `ber_bytes`.
+///
+/// Returns the value and the bytes following it.
+pub fn decode_prefix(ber_bytes: &[u8]) -> Result<(RawUuidV7, &[u8]), Error> {
+    let (sequence, trailing) = tlv::parse_ber(ber_bytes, 0).map_err(der::Error::from)?;
+    tlv::expect_tag(&sequence, TAG_SEQUENCE)?;
+
+    let base = sequence.content_offset();
+    let input = sequence.content;
+    let at = |rest: &[u8]| base + input.len() - rest.len();
+
+    let (unix_ts_ms, rest) = integer(input, base)?;
+    let (version, rest) = integer(rest, at(rest))?;
+    let version = u8::try_from(version).map_err(|_| FieldError::OutOfRange {
+        field: RawUuidV7Field::Version,
+        value: version,
+    })?;
+    let (rand_a, rest) = bit_string(rest, at(rest), RawUuidV7Field::RandA)?;
+    let (variant, rest) = bit_string(rest, at(rest), RawUuidV7Field::Variant)?;
+    let (rand_b, rest) = bit_string(rest, at(rest), RawUuidV7Field::RandB)?;
+
+    if !rest.is_empty() {
+        return Err(tlv::trailing_data(at(rest), base + input.len()).into());
+    }
+
+    let raw_uuid = RawUuidV7 {
+        unix_ts_ms,
+        version,
+        rand_a: rand_a as u16,
+        variant: variant as u8,
+        rand_b: rand_b as u64,
+    };
+    Ok((raw_uuid, trailing))
+}
+
+/// Decodes a BER, CER or DER `RawUuidV7` value and re-encodes it as DER
+/// through [`RawUuidV7Asn1::to_der_bytes`].
+pub fn canonicalize(ber_bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let raw_uuid = decode(ber_bytes)?;
+    RawUuidV7Asn1::try_from(raw_uuid)?.to_der_bytes()
+}
+
+/// Reads a non-negative INTEGER of at most 64 significant bits.
+fn integer(input: &[u8], base: usize) -> Result<(u64, &[u8]), Error> {
+    let (element, rest) = tlv::parse_ber(input, base).map_err(der::Error::from)?;
+    tlv::expect_tag(&element, TAG_INTEGER)?;
+
+    let Some(first) = element.content.first() else {
+        return Err(error_at(ErrorKind::Length { tag: Tag::Integer }, &element));
+    };
+    if first & 0x80 != 0 {
+        return Err(error_at(ErrorKind::Value { tag: Tag::Integer }, &element));
+    }
+
+    let leading_zeros = element.content.iter().take_while(|b| **b == 0).count();
+    let significant = &element.content[leading_zeros..];
+    if significant.len() > 8 {
+        return Err(error_at(ErrorKind::Value { tag: Tag::Integer }, &element));
+    }
+    let value = significant
+        .iter()
+        .fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+    Ok((value, rest))
+}
+
+/// The octets of a BIT STRING, gathered from all of its segments.
+#[derive(Default)]
+struct Segments {
+    bytes: [u8; 16],
+    len: usize,
+    /// Total number of octets, including any beyond the buffer.
+    total: usize,
+    /// Unused bits of the last primitive segment.
+    unused: u8,
+}
+
+impl Segments {
+    fn bit_len(&self) -> usize {
+        (self.total * 8).saturating_sub(usize::from(self.unused))
+    }
+
+    /// Appends the segments of `element`, descending into constructed ones.
+    fn push(&mut self, element: &Tlv<'_>, depth: usize) -> Result<(), Error> {
+        if self.unused != 0 {
+            // Only the last segment may have unused bits.
+            return Err(segment_error(element));
+        }
+
+        if !element.is_constructed() {
+            let Some((&unused, bytes)) = element.content.split_first() else {
+                return Err(segment_error(element));
+            };
+            if unused > 7 || (bytes.is_empty() && unused != 0) {
+                return Err(segment_error(element));
+            }
+            for byte in bytes {
+                if let Some(slot) = self.bytes.get_mut(self.len) {
+                    *slot = *byte;
+                    self.len += 1;
+                }
+            }
+            self.total += bytes.len();
+            self.unused = unused;
+            return Ok(());
+        }
+
+        if depth >= tlv::MAX_DEPTH {
+            let e = TlvError::TooDeep {
+                offset: element.offset,
+            };
+            return Err(der::Error::from(e).into());
+        }
+        let mut rest = element.content;
+        while !rest.is_empty() {
+            let offset = element.content_offset() + element.content.len() - rest.len();
+            let (segment, next) = tlv::parse_ber(rest, offset).map_err(der::Error::from)?;
+            expect_bit_string(&segment)?;
+            self.push(&segment, depth + 1)?;
+            rest = next;
+        }
+        Ok(())
+    }
+}
+
+fn segment_error(element: &Tlv<'_>) -> Error {
+    error_at(
+        ErrorKind::Value {
+            tag: Tag::BitString,
+        },
+        element,
+    )
+}
+
+/// Returns `kind` as an error at the offset of `element`.
+fn error_at(kind: ErrorKind, element: &Tlv<'_>) -> Error {
+    match der::Length::try_from(element.offset) {
+        Ok(position) => Error::Der(kind.at(position)),
+        Err(_) => Error::Der(kind.into()),
+    }
+}
+
+/// Accepts the primitive and the constructed form of BIT STRING.
+fn expect_bit_string(element: &Tlv<'_>) -> Result<(), der::Error> {
+    if element.tag == TAG_BIT_STRING | tlv::CONSTRUCTED {
+        return Ok(());
+    }
+    tlv::expect_tag(element, TAG_BIT_STRING)
+}
+
+/// Reads a primitive or constructed BIT STRING of exactly `field.bit_len()`
+/// bits.
+fn bit_string(input: &[u8], base: usize, field: RawUuidV7Field) -> Result<(u128, &[u8]), Error> {
+    let (element, rest) = tlv::parse_ber(input, base).map_err(der::Error::from)?;
+    expect_bit_string(&element)?;
+
+    let mut segments = Segments::default();
+    segments.push(&element, 0)?;
+
+    let expected = field.bit_len();
+    let actual = segments.bit_len();
+    if actual != expected || segments.total > segments.bytes.len() {
+        return Err(Error::Field(FieldError::InvalidBitLength {
+            field,
+            expected,
+            actual,
+        }));
+    }
+
+    let raw = segments.bytes[..segments.len]
+        .iter()
+        .fold(0u128, |acc, b| (acc << 8) | u128::from(*b));
+    Ok((raw >> segments.unused, rest))
+}
@@ -0,0 +1,122 @@
+//! Codec for the fixed-size BIT STRING fields of the UUIDv7 ASN.1 types.
+//!
+//! DER stores a BIT STRING left-aligned: the first bit of the value is the
+//! most significant bit of the first content octet, and the trailing "unused"
+//! bits of the last octet must be zero. The 12-, 2- and 62-bit fields are
+//! therefore shifted left by the number of unused bits before encoding.
+
+#[cfg(feature = "alloc")]
+use der::asn1::BitString;
+use der::asn1::BitStringRef;
+
+use crate::FieldError;
+use crate::RawUuidV7Field;
+
+/// Describes how the bits of a field are placed inside its BIT STRING.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitLayout {
+    /// Left-aligned, as required by DER. Unused bits are zero.
+    MsbFirst,
+    /// Right-aligned big-endian integer bytes, as written by version 0.1.0
+    /// of this crate. The significant bits overlap the unused bits.
+    LegacyLsbFirst,
+}
+
+/// Number of octets and unused bits needed to hold `bit_len` bits.
+fn octets_and_unused(bit_len: usize) -> (usize, u8) {
+    let octets = bit_len.div_ceil(8);
+    (octets, (octets * 8 - bit_len) as u8)
+}
+
+/// Reads the raw octets as a big-endian integer.
+fn raw_value(bit_string: &BitStringRef<'_>) -> u128 {
+    bit_string
+        .raw_bytes()
+        .iter()
+        .fold(0u128, |acc, byte| (acc << 8) | u128::from(*byte))
+}
+
+fn check_bit_len(field: RawUuidV7Field, bit_string: &BitStringRef<'_>) -> Result<(), FieldError> {
+    let expected = field.bit_len();
+    let actual = bit_string.bit_len();
+    if expected != actual || bit_string.raw_bytes().len() > 16 {
+        return Err(FieldError::InvalidBitLength {
+            field,
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Returns true if any of the trailing unused bits is set.
+pub(crate) fn has_unused_bits_set(bit_string: &BitStringRef<'_>) -> bool {
+    let mask = (1u8 << bit_string.unused_bits()) - 1;
+    bit_string
+        .raw_bytes()
+        .last()
+        .is_some_and(|last| last & mask != 0)
+}
+
+/// Encodes `value` as a left-aligned BIT STRING whose octets live in `buf`.
+///
+/// Bits above `field.bit_len()` are dropped, so callers check the range
+/// first.
+pub(crate) fn encode_into(
+    field: RawUuidV7Field,
+    value: u128,
+    buf: &mut [u8; 16],
+) -> Result<BitStringRef<'_>, der::Error> {
+    let (octets, unused) = octets_and_unused(field.bit_len());
+    let shifted = value << unused;
+    *buf = shifted.to_be_bytes();
+    BitStringRef::new(unused, &buf[16 - octets..])
+}
+
+/// Encodes `value` as a left-aligned BIT STRING. Callers check the range first.
+#[cfg(feature = "alloc")]
+pub(crate) fn encode(field: RawUuidV7Field, value: u128) -> Result<BitString, der::Error> {
+    let mut buf = [0u8; 16];
+    let bit_string = encode_into(field, value, &mut buf)?;
+    BitString::new(bit_string.unused_bits(), bit_string.raw_bytes())
+}
+
+/// Decodes a left-aligned BIT STRING, rejecting non-zero unused bits.
+pub(crate) fn decode(
+    field: RawUuidV7Field,
+    bit_string: &BitStringRef<'_>,
+) -> Result<u128, FieldError> {
+    check_bit_len(field, bit_string)?;
+    if has_unused_bits_set(bit_string) {
+        return Err(FieldError::NonZeroUnusedBits { field });
+    }
+    Ok(raw_value(bit_string) >> bit_string.unused_bits())
+}
+
+/// Decodes a BIT STRING written with the legacy right-aligned layout.
+pub(crate) fn decode_legacy(
+    field: RawUuidV7Field,
+    bit_string: &BitStringRef<'_>,
+) -> Result<u128, FieldError> {
+    check_bit_len(field, bit_string)?;
+    let value = raw_value(bit_string);
+    if value > field.max_value() {
+        return Err(FieldError::OutOfRange {
+            field,
+            value: value as u64,
+        });
+    }
+    Ok(value)
+}
+
+/// Decodes a BIT STRING using the given layout.
+pub(crate) fn decode_with(
+    layout: BitLayout,
+    field: RawUuidV7Field,
+    bit_string: &BitStringRef<'_>,
+) -> Result<u128, FieldError> {
+    match layout {
+        BitLayout::MsbFirst => decode(field, bit_string),
+        BitLayout::LegacyLsbFirst => decode_legacy(field, bit_string),
+    }
+}
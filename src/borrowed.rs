@@ -0,0 +1,324 @@
+//! Borrowed DER types and fixed-size encoding, both without allocating.
+//!
+//! The owned [`RawUuidV7Asn1`](crate::RawUuidV7Asn1) and
+//! [`UuidV7Asn1`](crate::UuidV7Asn1) keep their BIT STRINGs in a `Vec` and
+//! need the `alloc` feature. The types here borrow the octets from the input
+//! or from stack buffers instead:
+//!
+//! - [`RawUuidV7Asn1Ref`] and [`UuidV7Asn1Ref`] decode by pointing into the
+//!   input bytes;
+//! - [`RawUuidV7::to_der_array`] and [`UuidV7::to_der_array`] encode into a
+//!   [`DerArray`] sized for the longest possible encoding.
+
+use core::ops::Deref;
+
+use der::Decode;
+use der::Encode;
+use der::Reader;
+use der::Sequence;
+use der::SliceReader;
+use der::asn1::BitStringRef;
+
+use crate::BitLayout;
+use crate::Error;
+use crate::FieldError;
+use crate::RawUuidV7;
+use crate::RawUuidV7Field;
+use crate::UnverifiedUuidV7;
+use crate::UuidV7;
+use crate::bitfield;
+
+/// Longest DER encoding of a `RawUuidV7`, reached by a [`RawUuidV7Asn1Ref`]
+/// holding a 64-bit timestamp and a version of 128 or more.
+pub const RAW_UUID_V7_DER_MAX_LEN: usize = 37;
+
+/// Longest DER encoding of a compact `UuidV7` holding a valid UUIDv7.
+pub const UUID_V7_DER_MAX_LEN: usize = 24;
+
+/// A DER encoding stored inline in a fixed-size array.
+///
+/// Dereferences to the encoded bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DerArray<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> DerArray<N> {
+    /// Returns the encoded bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Returns the length of the encoding.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if nothing was encoded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Encodes `value` into a new array.
+    fn encode(value: &impl Encode) -> Result<Self, Error> {
+        let mut bytes = [0u8; N];
+        let len = value.encode_to_slice(&mut bytes)?.len();
+        Ok(Self { bytes, len })
+    }
+}
+
+impl<const N: usize> Deref for DerArray<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for DerArray<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Represents the ASN.1 structure of a Raw UUIDv7, borrowing the BIT STRING
+/// octets from the DER input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Sequence)]
+pub struct RawUuidV7Asn1Ref<'a> {
+    /// The 48-bit Unix timestamp in milliseconds.
+    pub unix_ts_ms: u64,
+
+    /// The 4-bit version field.
+    pub version: u8,
+
+    /// The 12-bit `rand_a` part as an ASN.1 BitString.
+    pub rand_a: BitStringRef<'a>,
+
+    /// The 2-bit variant field as an ASN.1 BitString.
+    pub variant: BitStringRef<'a>,
+
+    /// The 62-bit `rand_b` part as an ASN.1 BitString.
+    pub rand_b: BitStringRef<'a>,
+}
+
+impl<'a> RawUuidV7Asn1Ref<'a> {
+    /// Parses a single DER-encoded `RawUuidV7` value without copying.
+    ///
+    /// The input must contain exactly one value; trailing bytes are rejected.
+    pub fn from_der_bytes(der_bytes: &'a [u8]) -> Result<Self, Error> {
+        Ok(Self::from_der(der_bytes)?)
+    }
+
+    /// Parses the first DER-encoded `RawUuidV7` value of `der_bytes`.
+    ///
+    /// Returns the value and the bytes following it.
+    pub fn from_der_prefix(der_bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let mut reader = SliceReader::new(der_bytes)?;
+        let asn1 = Self::decode(&mut reader)?;
+        let used = usize::try_from(reader.position())?;
+        Ok((asn1, &der_bytes[used..]))
+    }
+}
+
+impl RawUuidV7Asn1Ref<'_> {
+    /// Encodes the value into a stack buffer.
+    pub fn to_der_array(&self) -> Result<DerArray<RAW_UUID_V7_DER_MAX_LEN>, Error> {
+        DerArray::encode(self)
+    }
+
+    /// Detects the bit layout of the BIT STRING fields.
+    ///
+    /// See [`RawUuidV7Asn1::bit_layout`](crate::RawUuidV7Asn1::bit_layout).
+    pub fn bit_layout(&self) -> BitLayout {
+        let legacy = [&self.rand_a, &self.variant, &self.rand_b]
+            .into_iter()
+            .any(bitfield::has_unused_bits_set);
+        if legacy {
+            BitLayout::LegacyLsbFirst
+        } else {
+            BitLayout::MsbFirst
+        }
+    }
+
+    /// Extracts the fields, reading the BIT STRINGs with the given layout.
+    pub fn to_raw_uuid_v7_with(self, layout: BitLayout) -> Result<RawUuidV7, FieldError> {
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, self.unix_ts_ms)?;
+        let rand_a = bitfield::decode_with(layout, RawUuidV7Field::RandA, &self.rand_a)?;
+        let variant = bitfield::decode_with(layout, RawUuidV7Field::Variant, &self.variant)?;
+        let rand_b = bitfield::decode_with(layout, RawUuidV7Field::RandB, &self.rand_b)?;
+
+        Ok(RawUuidV7 {
+            unix_ts_ms,
+            version: self.version,
+            rand_a: rand_a as u16,
+            rand_b: rand_b as u64,
+            variant: variant as u8,
+        })
+    }
+
+    /// Extracts the fields, accepting both the DER layout and the legacy layout.
+    pub fn to_raw_uuid_v7_compat(self) -> Result<RawUuidV7, FieldError> {
+        self.to_raw_uuid_v7_with(self.bit_layout())
+    }
+}
+
+impl TryFrom<RawUuidV7Asn1Ref<'_>> for RawUuidV7 {
+    type Error = FieldError;
+
+    /// Extracts the fields, requiring the DER layout for the BIT STRINGs.
+    fn try_from(asn1: RawUuidV7Asn1Ref<'_>) -> Result<Self, Self::Error> {
+        asn1.to_raw_uuid_v7_with(BitLayout::MsbFirst)
+    }
+}
+
+impl TryFrom<RawUuidV7Asn1Ref<'_>> for UnverifiedUuidV7 {
+    type Error = FieldError;
+
+    fn try_from(asn1: RawUuidV7Asn1Ref<'_>) -> Result<Self, Self::Error> {
+        let raw_uuid: RawUuidV7 = asn1.try_into()?;
+        raw_uuid.try_into()
+    }
+}
+
+impl TryFrom<RawUuidV7Asn1Ref<'_>> for UuidV7 {
+    type Error = Error;
+
+    fn try_from(asn1: RawUuidV7Asn1Ref<'_>) -> Result<Self, Self::Error> {
+        let unverified_uuid: UnverifiedUuidV7 = asn1.try_into()?;
+        Ok(unverified_uuid.try_into()?)
+    }
+}
+
+/// Represents the ASN.1 structure of the compact UuidV7, borrowing the
+/// BIT STRING octets from the DER input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Sequence)]
+pub struct UuidV7Asn1Ref<'a> {
+    /// The 48-bit Unix timestamp in milliseconds.
+    pub unix_ts_ms: u64,
+
+    /// The 74-bit `rand_a` and `rand_b` parts as an ASN.1 BitString.
+    pub rand_ab: BitStringRef<'a>,
+}
+
+impl<'a> UuidV7Asn1Ref<'a> {
+    /// Parses a single DER-encoded `UuidV7` value without copying.
+    ///
+    /// The input must contain exactly one value; trailing bytes are rejected.
+    pub fn from_der_bytes(der_bytes: &'a [u8]) -> Result<Self, Error> {
+        Ok(Self::from_der(der_bytes)?)
+    }
+}
+
+impl UuidV7Asn1Ref<'_> {
+    /// Rebuilds the UUIDv7, putting back the version and variant bits.
+    ///
+    /// `rand_ab` must be exactly 74 bits long.
+    pub fn to_uuid_v7(self) -> Result<UuidV7, FieldError> {
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, self.unix_ts_ms)?;
+        let rand_ab = bitfield::decode(RawUuidV7Field::RandAb, &self.rand_ab)?;
+        let rand_a = rand_ab >> 62;
+        let rand_b = rand_ab & 0x3FFF_FFFF_FFFF_FFFF;
+
+        Ok(UuidV7(
+            ((unix_ts_ms as u128) << 80) | (7u128 << 76) | (rand_a << 64) | (2u128 << 62) | rand_b,
+        ))
+    }
+}
+
+impl TryFrom<UuidV7Asn1Ref<'_>> for UuidV7 {
+    type Error = FieldError;
+
+    fn try_from(asn1: UuidV7Asn1Ref<'_>) -> Result<Self, Self::Error> {
+        asn1.to_uuid_v7()
+    }
+}
+
+/// Holds the BIT STRING octets of a `RawUuidV7` being encoded.
+#[derive(Default)]
+struct RawUuidV7Bits {
+    rand_a: [u8; 16],
+    variant: [u8; 16],
+    rand_b: [u8; 16],
+}
+
+impl RawUuidV7 {
+    /// Builds the borrowed ASN.1 value, with the octets stored in `bits`.
+    fn asn1_ref<'a>(&self, bits: &'a mut RawUuidV7Bits) -> Result<RawUuidV7Asn1Ref<'a>, Error> {
+        let RawUuidV7Bits {
+            rand_a: rand_a_buf,
+            variant: variant_buf,
+            rand_b: rand_b_buf,
+        } = bits;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, self.unix_ts_ms)?;
+        let rand_a = RawUuidV7::check_range(RawUuidV7Field::RandA, self.rand_a.into())?;
+        let variant = RawUuidV7::check_range(RawUuidV7Field::Variant, self.variant.into())?;
+        let rand_b = RawUuidV7::check_range(RawUuidV7Field::RandB, self.rand_b)?;
+
+        Ok(RawUuidV7Asn1Ref {
+            unix_ts_ms,
+            version: self.version,
+            rand_a: bitfield::encode_into(RawUuidV7Field::RandA, rand_a.into(), rand_a_buf)?,
+            variant: bitfield::encode_into(RawUuidV7Field::Variant, variant.into(), variant_buf)?,
+            rand_b: bitfield::encode_into(RawUuidV7Field::RandB, rand_b.into(), rand_b_buf)?,
+        })
+    }
+
+    /// Encodes the value as a DER `RawUuidV7` into `buf` without allocating.
+    ///
+    /// Returns the encoded bytes, which are a prefix of `buf`. A buffer of
+    /// [`RAW_UUID_V7_DER_MAX_LEN`] bytes is always large enough.
+    pub fn encode_der<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let mut bits = RawUuidV7Bits::default();
+        Ok(self.asn1_ref(&mut bits)?.encode_to_slice(buf)?)
+    }
+
+    /// Encodes the value as a DER `RawUuidV7` into a stack buffer.
+    pub fn to_der_array(&self) -> Result<DerArray<RAW_UUID_V7_DER_MAX_LEN>, Error> {
+        let mut bits = RawUuidV7Bits::default();
+        DerArray::encode(&self.asn1_ref(&mut bits)?)
+    }
+
+    /// Decodes a single DER `RawUuidV7` value without allocating.
+    ///
+    /// The BIT STRINGs must use the DER layout; trailing bytes are rejected.
+    pub fn decode_der(der_bytes: &[u8]) -> Result<Self, Error> {
+        let asn1 = RawUuidV7Asn1Ref::from_der_bytes(der_bytes)?;
+        Ok(asn1.try_into()?)
+    }
+}
+
+impl UuidV7 {
+    /// Builds the borrowed ASN.1 value, with the octets stored in `rand_ab_buf`.
+    fn asn1_ref<'a>(&self, rand_ab_buf: &'a mut [u8; 16]) -> Result<UuidV7Asn1Ref<'a>, Error> {
+        let unverified_uuid = UnverifiedUuidV7::from(*self);
+        let rand_ab =
+            (u128::from(unverified_uuid.rand_a()) << 62) | u128::from(unverified_uuid.rand_b());
+
+        Ok(UuidV7Asn1Ref {
+            unix_ts_ms: unverified_uuid.unix_ts_ms(),
+            rand_ab: bitfield::encode_into(RawUuidV7Field::RandAb, rand_ab, rand_ab_buf)?,
+        })
+    }
+
+    /// Encodes the value as a compact DER `UuidV7` into `buf` without allocating.
+    ///
+    /// Returns the encoded bytes, which are a prefix of `buf`. A buffer of
+    /// [`UUID_V7_DER_MAX_LEN`] bytes is always large enough.
+    pub fn encode_der<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let mut rand_ab = [0u8; 16];
+        Ok(self.asn1_ref(&mut rand_ab)?.encode_to_slice(buf)?)
+    }
+
+    /// Encodes the value as a compact DER `UuidV7` into a stack buffer.
+    pub fn to_der_array(&self) -> Result<DerArray<UUID_V7_DER_MAX_LEN>, Error> {
+        let mut rand_ab = [0u8; 16];
+        DerArray::encode(&self.asn1_ref(&mut rand_ab)?)
+    }
+
+    /// Decodes a single compact DER `UuidV7` value without allocating.
+    pub fn decode_der(der_bytes: &[u8]) -> Result<Self, Error> {
+        let asn1 = UuidV7Asn1Ref::from_der_bytes(der_bytes)?;
+        Ok(asn1.to_uuid_v7()?)
+    }
+}
@@ -0,0 +1,193 @@
+//! Conversions between the UUIDv7 types and `u128`, `[u8; 16]`,
+//! `uuid::Uuid`, `uuid::Timestamp` and `SystemTime`.
+//!
+//! Bytes are in network order, as in RFC 9562. Conversions fail only when
+//! information would be lost or a check would be skipped:
+//!
+//! - into [`UuidV7`], with [`UuidV7Error`] if the version or variant bits
+//!   are wrong;
+//! - out of [`RawUuidV7`], with [`FieldError`] if a field is wider than its
+//!   slot in the 128-bit layout.
+//!
+//! Times have millisecond precision, matching `Uuid::get_timestamp`. Use
+//! the `to_system_time` methods to also read a sub-millisecond fraction
+//! from `rand_a`.
+
+#[cfg(feature = "std")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+use uuid::Timestamp;
+use uuid::Uuid;
+
+use crate::Error;
+use crate::FieldError;
+use crate::RawUuidV7;
+use crate::UnverifiedUuidV7;
+use crate::UuidV7;
+use crate::UuidV7Error;
+
+/// Converts a Unix timestamp in milliseconds into a `uuid::Timestamp`.
+fn unix_ts_ms_to_timestamp(unix_ts_ms: u64) -> Timestamp {
+    let seconds = unix_ts_ms / 1000;
+    let subsec_nanos = (unix_ts_ms % 1000) as u32 * 1_000_000;
+    Timestamp::from_unix_time(seconds, subsec_nanos, 0, 0)
+}
+
+impl From<u128> for UnverifiedUuidV7 {
+    fn from(uuid_u128: u128) -> Self {
+        UnverifiedUuidV7(uuid_u128)
+    }
+}
+
+impl From<[u8; 16]> for UnverifiedUuidV7 {
+    fn from(bytes: [u8; 16]) -> Self {
+        UnverifiedUuidV7(u128::from_be_bytes(bytes))
+    }
+}
+
+impl From<Uuid> for UnverifiedUuidV7 {
+    fn from(uuid_value: Uuid) -> Self {
+        UnverifiedUuidV7(uuid_value.as_u128())
+    }
+}
+
+impl From<UnverifiedUuidV7> for [u8; 16] {
+    fn from(unverified_uuid: UnverifiedUuidV7) -> Self {
+        unverified_uuid.0.to_be_bytes()
+    }
+}
+
+impl From<UnverifiedUuidV7> for Timestamp {
+    fn from(unverified_uuid: UnverifiedUuidV7) -> Self {
+        unix_ts_ms_to_timestamp(unverified_uuid.unix_ts_ms())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<UnverifiedUuidV7> for SystemTime {
+    /// Returns the creation time with millisecond precision.
+    fn from(unverified_uuid: UnverifiedUuidV7) -> Self {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(unverified_uuid.unix_ts_ms())
+    }
+}
+
+impl TryFrom<u128> for UuidV7 {
+    type Error = UuidV7Error;
+
+    fn try_from(uuid_u128: u128) -> Result<Self, Self::Error> {
+        UnverifiedUuidV7(uuid_u128).try_into()
+    }
+}
+
+impl TryFrom<[u8; 16]> for UuidV7 {
+    type Error = UuidV7Error;
+
+    fn try_from(bytes: [u8; 16]) -> Result<Self, Self::Error> {
+        UnverifiedUuidV7::from(bytes).try_into()
+    }
+}
+
+impl TryFrom<Uuid> for UuidV7 {
+    type Error = UuidV7Error;
+
+    fn try_from(uuid_value: Uuid) -> Result<Self, Self::Error> {
+        UnverifiedUuidV7::from(uuid_value).try_into()
+    }
+}
+
+impl TryFrom<RawUuidV7> for UuidV7 {
+    type Error = Error;
+
+    fn try_from(raw_uuid: RawUuidV7) -> Result<Self, Self::Error> {
+        let unverified_uuid = UnverifiedUuidV7::try_from(raw_uuid)?;
+        Ok(unverified_uuid.try_into()?)
+    }
+}
+
+impl From<UuidV7> for [u8; 16] {
+    fn from(uuid_v7: UuidV7) -> Self {
+        uuid_v7.as_u128().to_be_bytes()
+    }
+}
+
+impl From<UuidV7> for RawUuidV7 {
+    fn from(uuid_v7: UuidV7) -> Self {
+        UnverifiedUuidV7::from(uuid_v7).into()
+    }
+}
+
+impl From<UuidV7> for Timestamp {
+    fn from(uuid_v7: UuidV7) -> Self {
+        UnverifiedUuidV7::from(uuid_v7).into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<UuidV7> for SystemTime {
+    /// Returns the creation time with millisecond precision.
+    fn from(uuid_v7: UuidV7) -> Self {
+        UnverifiedUuidV7::from(uuid_v7).into()
+    }
+}
+
+impl From<u128> for RawUuidV7 {
+    fn from(uuid_u128: u128) -> Self {
+        UnverifiedUuidV7(uuid_u128).into()
+    }
+}
+
+impl From<[u8; 16]> for RawUuidV7 {
+    fn from(bytes: [u8; 16]) -> Self {
+        UnverifiedUuidV7::from(bytes).into()
+    }
+}
+
+impl From<Uuid> for RawUuidV7 {
+    fn from(uuid_value: Uuid) -> Self {
+        UnverifiedUuidV7::from(uuid_value).into()
+    }
+}
+
+impl TryFrom<RawUuidV7> for u128 {
+    type Error = FieldError;
+
+    fn try_from(raw_uuid: RawUuidV7) -> Result<Self, Self::Error> {
+        Ok(UnverifiedUuidV7::try_from(raw_uuid)?.into())
+    }
+}
+
+impl TryFrom<RawUuidV7> for [u8; 16] {
+    type Error = FieldError;
+
+    fn try_from(raw_uuid: RawUuidV7) -> Result<Self, Self::Error> {
+        Ok(UnverifiedUuidV7::try_from(raw_uuid)?.into())
+    }
+}
+
+impl TryFrom<RawUuidV7> for Uuid {
+    type Error = FieldError;
+
+    fn try_from(raw_uuid: RawUuidV7) -> Result<Self, Self::Error> {
+        Ok(UnverifiedUuidV7::try_from(raw_uuid)?.into())
+    }
+}
+
+impl TryFrom<RawUuidV7> for Timestamp {
+    type Error = FieldError;
+
+    fn try_from(raw_uuid: RawUuidV7) -> Result<Self, Self::Error> {
+        Ok(UnverifiedUuidV7::try_from(raw_uuid)?.into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<RawUuidV7> for SystemTime {
+    type Error = FieldError;
+
+    /// Returns the creation time with millisecond precision.
+    fn try_from(raw_uuid: RawUuidV7) -> Result<Self, Self::Error> {
+        Ok(UnverifiedUuidV7::try_from(raw_uuid)?.into())
+    }
+}
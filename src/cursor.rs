@@ -0,0 +1,93 @@
+//! Reads the text of XER and JER documents.
+
+/// Why a decimal INTEGER could not be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumberError {
+    /// No digit at the cursor.
+    NoDigits,
+    /// A `0` followed by more digits.
+    LeadingZero,
+    /// A `-` followed by digits.
+    Negative,
+    /// More than 64 bits.
+    Overflow,
+}
+
+/// A position in a text, advanced byte by byte over ASCII syntax.
+pub(crate) struct Cursor<'a> {
+    pub(crate) text: &'a str,
+    pub(crate) offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        Cursor { text, offset: 0 }
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.offset).copied()
+    }
+
+    pub(crate) fn is_at_end(&self) -> bool {
+        self.offset >= self.text.len()
+    }
+
+    /// Skips the whitespace shared by XML and JSON: space, tab, CR and LF.
+    pub(crate) fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.offset += 1;
+        }
+    }
+
+    /// Consumes `literal` if the text continues with it.
+    pub(crate) fn eat(&mut self, literal: &str) -> bool {
+        let matches = self.text.as_bytes()[self.offset..].starts_with(literal.as_bytes());
+        if matches {
+            self.offset += literal.len();
+        }
+        matches
+    }
+
+    /// Returns the character at byte `offset`, if any.
+    pub(crate) fn character_at(&self, offset: usize) -> Option<char> {
+        self.text.get(offset..).and_then(|rest| rest.chars().next())
+    }
+
+    /// Counts the characters before byte `offset`.
+    pub(crate) fn position_of(&self, offset: usize) -> usize {
+        self.text
+            .get(..offset)
+            .map_or(offset, |head| head.chars().count())
+    }
+
+    /// Reads a non-negative decimal INTEGER without leading zeros, as in the
+    /// ASN.1 value notation and in JSON.
+    pub(crate) fn number(&mut self) -> Result<u64, NumberError> {
+        if self.peek() == Some(b'-') {
+            let sign = self.offset;
+            self.offset += 1;
+            if self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                return Err(NumberError::Negative);
+            }
+            self.offset = sign;
+            return Err(NumberError::NoDigits);
+        }
+
+        let start = self.offset;
+        let mut value = 0u64;
+        while let Some(byte) = self.peek().filter(u8::is_ascii_digit) {
+            if self.offset > start && self.text.as_bytes()[start] == b'0' {
+                return Err(NumberError::LeadingZero);
+            }
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(u64::from(byte - b'0')))
+                .ok_or(NumberError::Overflow)?;
+            self.offset += 1;
+        }
+        if self.offset == start {
+            return Err(NumberError::NoDigits);
+        }
+        Ok(value)
+    }
+}
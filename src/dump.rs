@@ -0,0 +1,717 @@
+//! dumpasn1-style inspector for DER `RawUuidV7` and `UuidV7` values.
+//!
+//! [`dump`] walks the TLV tree of an encoding and records every element with
+//! its offset, tag, length and, for BIT STRINGs, the number of unused bits.
+//! Elements that match a component of the schema are decoded; the timestamp
+//! is shown as ISO-8601 and the version and variant with their meaning.
+//!
+//! Unlike the decoders, the walk does not stop at the first rule an encoding
+//! breaks. Each [`Problem`] points to the exact byte at fault: the length
+//! octet of a non-minimal length, the redundant first byte of an INTEGER, the
+//! last byte of a BIT STRING with non-zero padding, and so on. The walk only
+//! stops where the structure cannot be read any further.
+//!
+//! ```text
+//!     0   31: SEQUENCE {  -- RawUuidV7
+//!     2    6:   INTEGER 01 91 23 45 67 89  -- unix-ts-ms: 1722873636745 (2024-08-05T16:00:36.745Z)
+//!    10    1:   INTEGER 07  -- version: 7 (UUIDv7, Unix Epoch time-based)
+//!    13    3:   BIT STRING, 4 unused bits 12 30  -- rand-a: 0x123
+//!    18    2:   BIT STRING, 6 unused bits 80  -- variant: 0b10 (RFC 9562)
+//!    22    9:   BIT STRING, 2 unused bits 04 8D 15 9E 26 AF 37 BC  -- rand-b: 0x0123456789abcdef
+//!           : }
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use der::Tag;
+
+use crate::RawUuidV7Field;
+use crate::tlv;
+use crate::tlv::Tlv;
+use crate::tlv::TlvError;
+
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+
+/// Content bytes shown per element before the hex dump is cut short.
+const MAX_HEX_BYTES: usize = 16;
+
+/// The ASN.1 type an encoding is read as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Schema {
+    /// `RawUuidV7 ::= SEQUENCE { unix-ts-ms, version, rand-a, variant, rand-b }`.
+    #[default]
+    RawUuidV7,
+    /// `UuidV7 ::= SEQUENCE { unix-ts-ms, rand-ab }`.
+    UuidV7,
+}
+
+impl Schema {
+    fn components(&self) -> &'static [Component] {
+        match self {
+            Self::RawUuidV7 => &[
+                Component::Field(RawUuidV7Field::UnixTsMs),
+                Component::Field(RawUuidV7Field::Version),
+                Component::Field(RawUuidV7Field::RandA),
+                Component::Field(RawUuidV7Field::Variant),
+                Component::Field(RawUuidV7Field::RandB),
+            ],
+            Self::UuidV7 => &[
+                Component::Field(RawUuidV7Field::UnixTsMs),
+                Component::RandAb,
+            ],
+        }
+    }
+}
+
+/// The part of the schema an element encodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    /// The outer SEQUENCE.
+    Sequence(Schema),
+    /// A field of `RawUuidV7`; `unix-ts-ms` is shared with `UuidV7`.
+    Field(RawUuidV7Field),
+    /// The 74-bit `rand-ab` field of `UuidV7`.
+    RandAb,
+}
+
+impl Component {
+    /// Returns the name used in the ASN.1 module.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sequence(Schema::RawUuidV7) => "RawUuidV7",
+            Self::Sequence(Schema::UuidV7) => "UuidV7",
+            Self::Field(field) => field.name(),
+            Self::RandAb => "rand-ab",
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Sequence(_) => TAG_SEQUENCE,
+            Self::Field(RawUuidV7Field::UnixTsMs | RawUuidV7Field::Version) => TAG_INTEGER,
+            Self::Field(_) | Self::RandAb => TAG_BIT_STRING,
+        }
+    }
+
+    /// Returns the size of a BIT STRING component.
+    fn bit_len(&self) -> usize {
+        match self {
+            Self::Field(field) => field.bit_len(),
+            Self::RandAb => 74,
+            Self::Sequence(_) => 0,
+        }
+    }
+}
+
+/// A decoded component value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value {
+    /// An INTEGER of at most 16 content bytes.
+    Integer(i128),
+    /// The bits of a BIT STRING of the expected size.
+    Bits(u128),
+}
+
+/// One TLV element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Element {
+    /// Offset of the tag byte.
+    pub offset: usize,
+    /// Nesting level; 0 for the outer SEQUENCE.
+    pub depth: usize,
+    /// The identifier octet.
+    pub tag: u8,
+    /// Number of tag and length bytes.
+    pub header_len: usize,
+    /// Number of content bytes, excluding any end-of-contents bytes.
+    pub content_len: usize,
+    /// True for the indefinite length form.
+    pub indefinite_length: bool,
+    /// The unused-bits count of a primitive BIT STRING.
+    pub unused_bits: Option<u8>,
+    /// The schema component, if the element is where one is expected.
+    pub component: Option<Component>,
+    /// The decoded value, if the element could be read as its component.
+    pub value: Option<Value>,
+}
+
+impl Element {
+    /// Offset of the first content byte.
+    pub fn content_offset(&self) -> usize {
+        self.offset + self.header_len
+    }
+}
+
+/// A rule of DER or of the schema that the encoding breaks.
+///
+/// Offsets point to the byte at fault and are relative to the start of the
+/// input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    /// The input or the enclosing element ends `missing` bytes too early.
+    Truncated { offset: usize, missing: usize },
+    /// The tag uses the multi-byte high tag number form.
+    HighTagNumber { offset: usize },
+    /// The length does not fit into `usize`.
+    LengthOverflow { offset: usize },
+    /// The length is the indefinite form.
+    IndefiniteLength { offset: usize },
+    /// Elements nest too deeply to be read.
+    TooDeep { offset: usize },
+    /// The length is not encoded in its shortest form.
+    NonMinimalLength { offset: usize },
+    /// The tag is not the one of the schema component.
+    UnexpectedTag {
+        offset: usize,
+        expected: u8,
+        actual: u8,
+    },
+    /// An INTEGER or BIT STRING has no content bytes.
+    EmptyContent { offset: usize, tag: u8 },
+    /// An INTEGER has a redundant leading `0x00` or `0xFF` byte.
+    NonMinimalInteger { offset: usize },
+    /// An INTEGER is negative.
+    NegativeInteger { offset: usize },
+    /// The unused-bits count of a BIT STRING is more than 7, or not 0 for
+    /// an empty BIT STRING.
+    InvalidUnusedBits { offset: usize, unused: u8 },
+    /// The trailing unused bits of a BIT STRING are not zero.
+    NonZeroUnusedBits { offset: usize },
+    /// A BIT STRING does not have the size of its component.
+    InvalidBitLength {
+        offset: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// The SEQUENCE ends before the component.
+    MissingComponent { offset: usize, component: Component },
+    /// Bytes follow the last component or the outer SEQUENCE.
+    TrailingData { offset: usize },
+}
+
+impl Problem {
+    /// Returns the offset of the byte at fault.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::Truncated { offset, .. }
+            | Self::HighTagNumber { offset }
+            | Self::LengthOverflow { offset }
+            | Self::IndefiniteLength { offset }
+            | Self::TooDeep { offset }
+            | Self::NonMinimalLength { offset }
+            | Self::UnexpectedTag { offset, .. }
+            | Self::EmptyContent { offset, .. }
+            | Self::NonMinimalInteger { offset }
+            | Self::NegativeInteger { offset }
+            | Self::InvalidUnusedBits { offset, .. }
+            | Self::NonZeroUnusedBits { offset }
+            | Self::InvalidBitLength { offset, .. }
+            | Self::MissingComponent { offset, .. }
+            | Self::TrailingData { offset } => *offset,
+        }
+    }
+}
+
+impl From<TlvError> for Problem {
+    fn from(e: TlvError) -> Self {
+        match e {
+            TlvError::Truncated {
+                offset,
+                expected,
+                actual,
+            } => Self::Truncated {
+                offset: offset + actual,
+                missing: expected - actual,
+            },
+            TlvError::IndefiniteLength { offset } => Self::IndefiniteLength { offset },
+            TlvError::LengthOverflow { offset } => Self::LengthOverflow { offset },
+            TlvError::HighTagNumber { offset } => Self::HighTagNumber { offset },
+            TlvError::TooDeep { offset } => Self::TooDeep { offset },
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "offset {}: ", self.offset())?;
+        match self {
+            Self::Truncated { missing, .. } => {
+                write!(f, "element is truncated by {missing} bytes")
+            }
+            Self::HighTagNumber { .. } => f.write_str("high tag numbers are not supported"),
+            Self::LengthOverflow { .. } => f.write_str("length is too large"),
+            Self::IndefiniteLength { .. } => f.write_str("indefinite length is not allowed"),
+            Self::TooDeep { .. } => f.write_str("elements nest too deeply"),
+            Self::NonMinimalLength { .. } => f.write_str("length is not minimally encoded"),
+            Self::UnexpectedTag {
+                expected, actual, ..
+            } => write!(
+                f,
+                "expected {}, found {}",
+                TagName(*expected),
+                TagName(*actual)
+            ),
+            Self::EmptyContent { tag, .. } => write!(f, "{} has no content", TagName(*tag)),
+            Self::NonMinimalInteger { .. } => f.write_str("INTEGER is not minimally encoded"),
+            Self::NegativeInteger { .. } => f.write_str("INTEGER is negative"),
+            Self::InvalidUnusedBits { unused, .. } => {
+                write!(f, "invalid unused-bits count {unused}")
+            }
+            Self::NonZeroUnusedBits { .. } => f.write_str("unused bits are not zero"),
+            Self::InvalidBitLength {
+                expected, actual, ..
+            } => write!(f, "BIT STRING has {actual} bits, expected {expected}"),
+            Self::MissingComponent { component, .. } => {
+                write!(f, "SEQUENCE ends before {}", component.name())
+            }
+            Self::TrailingData { .. } => f.write_str("unexpected trailing data"),
+        }
+    }
+}
+
+impl core::error::Error for Problem {}
+
+/// An entry of a dump, in input order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entry {
+    /// The header of an element.
+    Element(Element),
+    /// The end of the constructed element at `depth`.
+    End { depth: usize },
+    /// A problem of the preceding element, or of the input as a whole.
+    Problem(Problem),
+}
+
+/// The result of [`dump`]. Displays as a dumpasn1-style listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dump<'a> {
+    der_bytes: &'a [u8],
+    entries: Vec<Entry>,
+}
+
+impl Dump<'_> {
+    /// Returns the entries in input order.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Returns the problems in input order.
+    pub fn problems(&self) -> impl Iterator<Item = &Problem> {
+        self.entries.iter().filter_map(|entry| match entry {
+            Entry::Problem(problem) => Some(problem),
+            _ => None,
+        })
+    }
+
+    /// Returns true if the encoding is valid DER of the schema.
+    pub fn is_der(&self) -> bool {
+        self.problems().next().is_none()
+    }
+}
+
+/// Walks the TLV tree of `der_bytes` as `schema`.
+pub fn dump(der_bytes: &[u8], schema: Schema) -> Dump<'_> {
+    let mut walker = Walker {
+        entries: Vec::new(),
+    };
+    walker.sequence(der_bytes, schema);
+    Dump {
+        der_bytes,
+        entries: walker.entries,
+    }
+}
+
+struct Walker {
+    entries: Vec<Entry>,
+}
+
+impl Walker {
+    fn problem(&mut self, problem: Problem) {
+        self.entries.push(Entry::Problem(problem));
+    }
+
+    /// Reads the next element and checks its tag and length.
+    ///
+    /// An element whose header is readable but whose content is truncated is
+    /// returned with the content that is there. Returns `None` if the element
+    /// cannot be read at all.
+    fn element<'a>(
+        &mut self,
+        input: &'a [u8],
+        base: usize,
+        depth: usize,
+        component: Component,
+    ) -> Option<(Tlv<'a>, Element, Vec<Problem>, &'a [u8])> {
+        let (tlv, rest, truncation) = match tlv::parse_ber(input, base) {
+            Ok((tlv, rest)) => (tlv, rest, None),
+            Err(e) => match truncated(input, base, e) {
+                Some(tlv) => (tlv, &input[input.len()..], Some(Problem::from(e))),
+                None => {
+                    self.problem(e.into());
+                    return None;
+                }
+            },
+        };
+
+        let mut problems = Vec::new();
+        if tlv.tag != component.tag() {
+            problems.push(Problem::UnexpectedTag {
+                offset: tlv.offset,
+                expected: component.tag(),
+                actual: tlv.tag,
+            });
+        }
+        if tlv.trailer_len != 0 {
+            problems.push(Problem::IndefiniteLength {
+                offset: tlv.offset + 1,
+            });
+        } else if !tlv.minimal_length {
+            problems.push(Problem::NonMinimalLength {
+                offset: tlv.offset + 1,
+            });
+        }
+        problems.extend(truncation);
+
+        let unused_bits = match tlv.tag {
+            TAG_BIT_STRING => tlv.content.first().copied(),
+            _ => None,
+        };
+        let element = Element {
+            offset: tlv.offset,
+            depth,
+            tag: tlv.tag,
+            header_len: tlv.header_len,
+            content_len: tlv.content.len(),
+            indefinite_length: tlv.trailer_len != 0,
+            unused_bits,
+            component: Some(component),
+            value: None,
+        };
+        Some((tlv, element, problems, rest))
+    }
+
+    fn push(&mut self, element: Element, problems: Vec<Problem>) {
+        self.entries.push(Entry::Element(element));
+        self.entries
+            .extend(problems.into_iter().map(Entry::Problem));
+    }
+
+    fn sequence(&mut self, der_bytes: &[u8], schema: Schema) {
+        let component = Component::Sequence(schema);
+        let Some((sequence, element, problems, trailing)) =
+            self.element(der_bytes, 0, 0, component)
+        else {
+            return;
+        };
+        self.push(element, problems);
+
+        if sequence.tag == TAG_SEQUENCE {
+            self.components(&sequence, schema);
+            self.entries.push(Entry::End { depth: 0 });
+        }
+        if !trailing.is_empty() {
+            self.problem(Problem::TrailingData {
+                offset: sequence.end_offset(),
+            });
+        }
+    }
+
+    fn components(&mut self, sequence: &Tlv<'_>, schema: Schema) {
+        let base = sequence.content_offset();
+        let end = base + sequence.content.len();
+        let mut rest = sequence.content;
+        for component in schema.components() {
+            if rest.is_empty() {
+                self.problem(Problem::MissingComponent {
+                    offset: end,
+                    component: *component,
+                });
+                return;
+            }
+            let Some((tlv, mut element, mut problems, next)) =
+                self.element(rest, end - rest.len(), 1, *component)
+            else {
+                return;
+            };
+            let truncated = problems
+                .iter()
+                .any(|problem| matches!(problem, Problem::Truncated { .. }));
+            if tlv.tag == component.tag() && !truncated {
+                element.value = match tlv.tag {
+                    TAG_INTEGER => integer(&tlv, &mut problems),
+                    TAG_BIT_STRING => bit_string(&tlv, component.bit_len(), &mut problems),
+                    _ => None,
+                };
+            }
+            self.push(element, problems);
+            if truncated {
+                return;
+            }
+            rest = next;
+        }
+        if !rest.is_empty() {
+            self.problem(Problem::TrailingData {
+                offset: end - rest.len(),
+            });
+        }
+    }
+}
+
+/// Returns the element at the start of `input` with the content that is
+/// there, if `error` is a truncation and the header is readable.
+fn truncated(input: &[u8], base: usize, error: TlvError) -> Option<Tlv<'_>> {
+    if !matches!(error, TlvError::Truncated { .. }) {
+        return None;
+    }
+    let header = tlv::parse_header(input, base).ok()?;
+    header.length?;
+    Some(Tlv {
+        offset: base,
+        tag: header.tag,
+        header_len: header.len,
+        content: &input[header.len..],
+        minimal_length: header.minimal_length,
+        trailer_len: 0,
+    })
+}
+
+/// Reads an INTEGER, recording its encoding problems.
+fn integer(tlv: &Tlv<'_>, problems: &mut Vec<Problem>) -> Option<Value> {
+    let content = tlv.content;
+    let Some(first) = content.first() else {
+        problems.push(Problem::EmptyContent {
+            offset: tlv.offset + 1,
+            tag: tlv.tag,
+        });
+        return None;
+    };
+    if let [first, second, ..] = content {
+        let redundant =
+            (*first == 0x00 && second & 0x80 == 0) || (*first == 0xFF && second & 0x80 != 0);
+        if redundant {
+            problems.push(Problem::NonMinimalInteger {
+                offset: tlv.content_offset(),
+            });
+        }
+    }
+    if first & 0x80 != 0 {
+        problems.push(Problem::NegativeInteger {
+            offset: tlv.content_offset(),
+        });
+    }
+    if content.len() > 16 {
+        return None;
+    }
+    let sign = if first & 0x80 != 0 { -1i128 } else { 0 };
+    let value = content
+        .iter()
+        .fold(sign, |acc, b| (acc << 8) | i128::from(*b));
+    Some(Value::Integer(value))
+}
+
+/// Reads a primitive BIT STRING of `bit_len` bits, recording its encoding
+/// problems.
+fn bit_string(tlv: &Tlv<'_>, bit_len: usize, problems: &mut Vec<Problem>) -> Option<Value> {
+    let Some((&unused, bytes)) = tlv.content.split_first() else {
+        problems.push(Problem::EmptyContent {
+            offset: tlv.offset + 1,
+            tag: tlv.tag,
+        });
+        return None;
+    };
+    let unused_offset = tlv.content_offset();
+    if unused > 7 || (bytes.is_empty() && unused != 0) {
+        problems.push(Problem::InvalidUnusedBits {
+            offset: unused_offset,
+            unused,
+        });
+        return None;
+    }
+    if let Some(last) = bytes.last()
+        && last & ((1 << unused) - 1) != 0
+    {
+        problems.push(Problem::NonZeroUnusedBits {
+            offset: unused_offset + bytes.len(),
+        });
+    }
+
+    let actual = bytes.len() * 8 - usize::from(unused);
+    if actual != bit_len {
+        problems.push(Problem::InvalidBitLength {
+            offset: unused_offset,
+            expected: bit_len,
+            actual,
+        });
+        return None;
+    }
+    let raw = bytes
+        .iter()
+        .fold(0u128, |acc, b| (acc << 8) | u128::from(*b));
+    Some(Value::Bits(raw >> unused))
+}
+
+/// Displays an identifier octet as the name of its universal tag.
+struct TagName(u8);
+
+impl fmt::Display for TagName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Ok(tag) = Tag::try_from(self.0) {
+            return write!(f, "{tag}");
+        }
+        // Universal types that DER only allows in the primitive form.
+        if self.0 & 0xC0 == 0
+            && let Ok(tag) = Tag::try_from(self.0 & !tlv::CONSTRUCTED)
+        {
+            return write!(f, "{tag} (constructed)");
+        }
+        write!(f, "[tag 0x{:02X}]", self.0)
+    }
+}
+
+/// Displays a Unix timestamp in milliseconds as ISO-8601 in UTC.
+struct Iso8601(u64);
+
+impl fmt::Display for Iso8601 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = self.0 % 1000;
+        let seconds = self.0 / 1000;
+        let (year, month, day) = civil_from_days(seconds / 86_400);
+        let seconds_of_day = seconds % 86_400;
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{millis:03}Z",
+            seconds_of_day / 3600,
+            seconds_of_day / 60 % 60,
+            seconds_of_day % 60,
+        )
+    }
+}
+
+/// Converts days since 1970-01-01 into a proleptic Gregorian date.
+///
+/// See Howard Hinnant, "chrono-Compatible Low-Level Date Algorithms".
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Displays the meaning of a decoded value.
+struct Meaning(Component, Value);
+
+impl fmt::Display for Meaning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.0, self.1) {
+            (Component::Field(RawUuidV7Field::UnixTsMs), Value::Integer(value)) => {
+                write!(f, "{value}")?;
+                match u64::try_from(value) {
+                    Ok(ms) if u128::from(ms) <= RawUuidV7Field::UnixTsMs.max_value() => {
+                        write!(f, " ({})", Iso8601(ms))
+                    }
+                    _ => f.write_str(" (does not fit in 48 bits)"),
+                }
+            }
+            (Component::Field(RawUuidV7Field::Version), Value::Integer(value)) => match value {
+                7 => write!(f, "{value} (UUIDv7, Unix Epoch time-based)"),
+                _ => write!(f, "{value} (not UUIDv7, expected 7)"),
+            },
+            (Component::Field(RawUuidV7Field::Variant), Value::Bits(value)) => {
+                let meaning = match value {
+                    0b00 | 0b01 => "reserved, NCS backward compatibility",
+                    0b10 => "RFC 9562",
+                    _ => "reserved, Microsoft backward compatibility",
+                };
+                write!(f, "0b{value:02b} ({meaning})")
+            }
+            (component, Value::Bits(value)) => {
+                let width = component.bit_len().div_ceil(4);
+                write!(f, "0x{value:0width$x}")
+            }
+            (_, Value::Integer(value)) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl fmt::Display for Dump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut depth = 0;
+        for entry in &self.entries {
+            match entry {
+                Entry::Element(element) => {
+                    depth = element.depth;
+                    self.fmt_element(f, element)?;
+                }
+                Entry::End { depth } => {
+                    writeln!(f, "{:>10}: {:indent$}}}", "", "", indent = depth * 2)?;
+                }
+                Entry::Problem(problem) => {
+                    write!(f, "{:>10}: {:indent$}^ ", "", "", indent = depth * 2 + 2)?;
+                    match self.der_bytes.get(problem.offset()) {
+                        Some(byte) => writeln!(f, "{problem} (byte 0x{byte:02X})")?,
+                        None => writeln!(f, "{problem} (end of input)")?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Dump<'_> {
+    fn fmt_element(&self, f: &mut fmt::Formatter<'_>, element: &Element) -> fmt::Result {
+        write!(f, "{:>5} ", element.offset)?;
+        if element.indefinite_length {
+            write!(f, "NDEF")?;
+        } else {
+            write!(f, "{:>4}", element.content_len)?;
+        }
+        write!(f, ": {:indent$}", "", indent = element.depth * 2)?;
+
+        let tag = TagName(element.tag);
+        if element.tag & tlv::CONSTRUCTED != 0 {
+            write!(f, "{tag} {{")?;
+        } else {
+            let start = element.content_offset();
+            let end = start + element.content_len;
+            let mut content = self.der_bytes.get(start..end).unwrap_or_default();
+            write!(f, "{tag}")?;
+            if let Some(unused) = element.unused_bits {
+                write!(f, ", {unused} unused bits")?;
+                content = content.get(1..).unwrap_or_default();
+            }
+            for byte in content.iter().take(MAX_HEX_BYTES) {
+                write!(f, " {byte:02X}")?;
+            }
+            if content.len() > MAX_HEX_BYTES {
+                write!(f, " ...")?;
+            }
+        }
+
+        match (element.component, element.value) {
+            (Some(component), Some(value)) => write!(
+                f,
+                "  -- {}: {}",
+                component.name(),
+                Meaning(component, value)
+            )?,
+            (Some(component), None) => write!(f, "  -- {}", component.name())?,
+            (None, _) => {}
+        }
+        writeln!(f)
+    }
+}
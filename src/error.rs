@@ -0,0 +1,401 @@
+//! The crate-wide error type and its stable error codes.
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use der::ErrorKind;
+
+use crate::ClockRegression;
+use crate::FieldError;
+use crate::JerError;
+use crate::OerError;
+use crate::ParseError;
+#[cfg(feature = "pem")]
+use crate::PemError;
+use crate::PerError;
+use crate::RawUuidV7Field;
+use crate::TimeError;
+use crate::UuidV7Error;
+use crate::XerError;
+#[cfg(feature = "alloc")]
+use crate::validate::Violation;
+
+/// Stable, machine-readable identifiers for every kind of [`Error`].
+///
+/// The numeric values and names never change between releases, so they can
+/// be printed by tools and matched on by services.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// The input is not valid DER, or the value cannot be DER-encoded.
+    Der = 1,
+    /// The version bits are not 7.
+    InvalidVersion = 2,
+    /// The variant bits are not `0b10`.
+    InvalidVariant = 3,
+    /// A BIT STRING has the wrong number of bits.
+    InvalidBitLength = 4,
+    /// A field value other than the timestamp does not fit into its width.
+    FieldOutOfRange = 5,
+    /// The unused bits of a BIT STRING are not zero.
+    NonZeroUnusedBits = 6,
+    /// A time or `unix-ts-ms` value does not fit into the 48-bit millisecond
+    /// timestamp.
+    TimestampOverflow = 7,
+    /// A time is before the Unix epoch.
+    BeforeUnixEpoch = 8,
+    /// An I/O operation failed.
+    Io = 9,
+    /// The input ended in the middle of a value.
+    Truncated = 10,
+    /// A length or INTEGER is not encoded in its shortest form.
+    NonMinimalEncoding = 11,
+    /// An INTEGER that must not be negative is negative.
+    NegativeInteger = 12,
+    /// Text is not a UUID in any supported form.
+    InvalidText = 13,
+    /// The input is not valid PER, or the output buffer is too small.
+    Per = 14,
+    /// The input is not valid OER, or the output buffer is too small.
+    Oer = 15,
+    /// The input is not a valid XER document.
+    Xer = 16,
+    /// The input is not a valid JER document.
+    Jer = 17,
+    /// The input is not valid PEM or base64.
+    Pem = 18,
+    /// The clock stepped backwards and the regression policy refused it.
+    ClockRegression = 19,
+}
+
+impl ErrorCode {
+    /// Returns the numeric code.
+    pub fn as_u16(self) -> u16 {
+        self as u16
+    }
+
+    /// Returns the symbolic name of the code.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Der => "der",
+            Self::InvalidVersion => "invalid-version",
+            Self::InvalidVariant => "invalid-variant",
+            Self::InvalidBitLength => "invalid-bit-length",
+            Self::FieldOutOfRange => "field-out-of-range",
+            Self::NonZeroUnusedBits => "nonzero-unused-bits",
+            Self::TimestampOverflow => "timestamp-overflow",
+            Self::BeforeUnixEpoch => "before-unix-epoch",
+            Self::Io => "io",
+            Self::Truncated => "truncated",
+            Self::NonMinimalEncoding => "non-minimal-encoding",
+            Self::NegativeInteger => "negative-integer",
+            Self::InvalidText => "invalid-text",
+            Self::Per => "per",
+            Self::Oer => "oer",
+            Self::Xer => "xer",
+            Self::Jer => "jer",
+            Self::Pem => "pem",
+            Self::ClockRegression => "clock-regression",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{:03} {}", self.as_u16(), self.as_str())
+    }
+}
+
+/// The error type of this crate.
+///
+/// Wraps the step-specific error types, so the original error stays
+/// reachable through [`core::error::Error::source`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// DER encoding or decoding failed.
+    Der(der::Error),
+    /// The value is not a valid UUIDv7.
+    Uuid(UuidV7Error),
+    /// A field has the wrong bit length or is out of range.
+    Field(FieldError),
+    /// A time cannot be stored in a UUIDv7 timestamp.
+    Time(TimeError),
+    /// Text is not a UUID in any supported form.
+    Parse(ParseError),
+    /// PER encoding or decoding failed.
+    Per(PerError),
+    /// OER encoding or decoding failed.
+    Oer(OerError),
+    /// XER decoding failed.
+    Xer(XerError),
+    /// JER decoding failed.
+    Jer(JerError),
+    /// PEM or base64 decoding failed.
+    #[cfg(feature = "pem")]
+    Pem(PemError),
+    /// The clock stepped backwards.
+    ClockRegression(ClockRegression),
+    /// The input breaks a rule checked by the validating decoder.
+    #[cfg(feature = "alloc")]
+    Violation(Violation),
+    /// An I/O operation failed.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+}
+
+impl Error {
+    /// Returns the stable code of this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Der(e) if matches!(e.kind(), ErrorKind::Incomplete { .. }) => {
+                ErrorCode::Truncated
+            }
+            Self::Der(_) => ErrorCode::Der,
+            Self::Uuid(UuidV7Error::InvalidVersion(_)) => ErrorCode::InvalidVersion,
+            Self::Uuid(UuidV7Error::InvalidVariant(_)) => ErrorCode::InvalidVariant,
+            Self::Field(FieldError::InvalidBitLength { .. }) => ErrorCode::InvalidBitLength,
+            Self::Field(FieldError::OutOfRange {
+                field: RawUuidV7Field::UnixTsMs,
+                ..
+            }) => ErrorCode::TimestampOverflow,
+            Self::Field(FieldError::OutOfRange { .. }) => ErrorCode::FieldOutOfRange,
+            Self::Field(FieldError::NonZeroUnusedBits { .. }) => ErrorCode::NonZeroUnusedBits,
+            Self::Time(TimeError::Overflow) => ErrorCode::TimestampOverflow,
+            Self::Time(TimeError::BeforeUnixEpoch) => ErrorCode::BeforeUnixEpoch,
+            Self::Parse(_) => ErrorCode::InvalidText,
+            Self::Per(e) => match e {
+                PerError::Truncated { .. } => ErrorCode::Truncated,
+                PerError::NonMinimalInteger { .. } => ErrorCode::NonMinimalEncoding,
+                PerError::NegativeInteger { .. } => ErrorCode::NegativeInteger,
+                PerError::IntegerOverflow {
+                    field: RawUuidV7Field::UnixTsMs,
+                    ..
+                } => ErrorCode::TimestampOverflow,
+                PerError::IntegerOverflow { .. } => ErrorCode::FieldOutOfRange,
+                PerError::BufferTooSmall { .. }
+                | PerError::InvalidLength { .. }
+                | PerError::NonZeroPadding { .. }
+                | PerError::TrailingData { .. } => ErrorCode::Per,
+            },
+            Self::Oer(e) => match e {
+                OerError::Truncated { .. } => ErrorCode::Truncated,
+                OerError::NonCanonicalLength { .. } | OerError::NonMinimalInteger { .. } => {
+                    ErrorCode::NonMinimalEncoding
+                }
+                OerError::NegativeInteger { .. } => ErrorCode::NegativeInteger,
+                OerError::IntegerOverflow {
+                    field: RawUuidV7Field::UnixTsMs,
+                    ..
+                } => ErrorCode::TimestampOverflow,
+                OerError::IntegerOverflow { .. } => ErrorCode::FieldOutOfRange,
+                OerError::NonZeroUnusedBits { .. } => ErrorCode::NonZeroUnusedBits,
+                OerError::BufferTooSmall { .. }
+                | OerError::InvalidLength { .. }
+                | OerError::TrailingData { .. } => ErrorCode::Oer,
+            },
+            Self::Xer(e) => match e {
+                XerError::UnexpectedEnd => ErrorCode::Truncated,
+                XerError::NonMinimalInteger { .. } => ErrorCode::NonMinimalEncoding,
+                XerError::NegativeInteger { .. } => ErrorCode::NegativeInteger,
+                XerError::IntegerOverflow {
+                    field: RawUuidV7Field::UnixTsMs,
+                    ..
+                } => ErrorCode::TimestampOverflow,
+                XerError::IntegerOverflow { .. } => ErrorCode::FieldOutOfRange,
+                XerError::UnexpectedCharacter { .. }
+                | XerError::UnexpectedElement { .. }
+                | XerError::TrailingData { .. } => ErrorCode::Xer,
+            },
+            Self::Jer(e) => match e {
+                JerError::UnexpectedEnd => ErrorCode::Truncated,
+                JerError::NonMinimalInteger { .. } => ErrorCode::NonMinimalEncoding,
+                JerError::NegativeInteger { .. } => ErrorCode::NegativeInteger,
+                JerError::IntegerOverflow {
+                    field: RawUuidV7Field::UnixTsMs,
+                    ..
+                } => ErrorCode::TimestampOverflow,
+                JerError::IntegerOverflow { .. } => ErrorCode::FieldOutOfRange,
+                JerError::UnexpectedCharacter { .. }
+                | JerError::StringTooLong { .. }
+                | JerError::UnknownMember { .. }
+                | JerError::DuplicateMember { .. }
+                | JerError::MissingMember { .. }
+                | JerError::InvalidHex { .. }
+                | JerError::TrailingData { .. } => ErrorCode::Jer,
+            },
+            #[cfg(feature = "pem")]
+            Self::Pem(_) => ErrorCode::Pem,
+            Self::ClockRegression(_) => ErrorCode::ClockRegression,
+            #[cfg(feature = "alloc")]
+            Self::Violation(v) => match v {
+                Violation::NonMinimalLength { .. } | Violation::NonMinimalInteger { .. } => {
+                    ErrorCode::NonMinimalEncoding
+                }
+                Violation::NegativeInteger { .. } => ErrorCode::NegativeInteger,
+                Violation::TimestampOutOfRange { .. } => ErrorCode::TimestampOverflow,
+                Violation::InvalidVersion { .. } => ErrorCode::InvalidVersion,
+                Violation::InvalidVariant { .. } => ErrorCode::InvalidVariant,
+                Violation::NonZeroUnusedBits { .. } => ErrorCode::NonZeroUnusedBits,
+            },
+            #[cfg(feature = "std")]
+            Self::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof => ErrorCode::Truncated,
+            #[cfg(feature = "std")]
+            Self::Io(_) => ErrorCode::Io,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Der(e) => write!(f, "DER error: {e}"),
+            Self::Uuid(e) => write!(f, "invalid UUIDv7: {e}"),
+            Self::Field(e) => write!(f, "invalid field: {e}"),
+            Self::Time(e) => write!(f, "invalid time: {e}"),
+            Self::Parse(e) => write!(f, "invalid text: {e}"),
+            Self::Per(e) => write!(f, "PER error: {e}"),
+            Self::Oer(e) => write!(f, "OER error: {e}"),
+            Self::Xer(e) => write!(f, "XER error: {e}"),
+            Self::Jer(e) => write!(f, "JER error: {e}"),
+            #[cfg(feature = "pem")]
+            Self::Pem(e) => write!(f, "PEM error: {e}"),
+            Self::ClockRegression(e) => write!(f, "clock error: {e}"),
+            #[cfg(feature = "alloc")]
+            Self::Violation(e) => write!(f, "validation failed: {e}"),
+            #[cfg(feature = "std")]
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            // `der::Error` implements the error trait only with its `std` feature.
+            #[cfg(feature = "std")]
+            Self::Der(e) => Some(e),
+            #[cfg(not(feature = "std"))]
+            Self::Der(_) => None,
+            Self::Uuid(e) => Some(e),
+            Self::Field(e) => Some(e),
+            Self::Time(e) => Some(e),
+            Self::Parse(e) => Some(e),
+            Self::Per(e) => Some(e),
+            Self::Oer(e) => Some(e),
+            Self::Xer(e) => Some(e),
+            Self::Jer(e) => Some(e),
+            #[cfg(feature = "pem")]
+            Self::Pem(e) => Some(e),
+            Self::ClockRegression(e) => Some(e),
+            #[cfg(feature = "alloc")]
+            Self::Violation(e) => Some(e),
+            #[cfg(feature = "std")]
+            Self::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(e: der::Error) -> Self {
+        Self::Der(e)
+    }
+}
+
+impl From<UuidV7Error> for Error {
+    fn from(e: UuidV7Error) -> Self {
+        Self::Uuid(e)
+    }
+}
+
+impl From<FieldError> for Error {
+    fn from(e: FieldError) -> Self {
+        Self::Field(e)
+    }
+}
+
+impl From<TimeError> for Error {
+    fn from(e: TimeError) -> Self {
+        Self::Time(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<PerError> for Error {
+    fn from(e: PerError) -> Self {
+        Self::Per(e)
+    }
+}
+
+impl From<OerError> for Error {
+    fn from(e: OerError) -> Self {
+        Self::Oer(e)
+    }
+}
+
+impl From<XerError> for Error {
+    fn from(e: XerError) -> Self {
+        Self::Xer(e)
+    }
+}
+
+impl From<JerError> for Error {
+    fn from(e: JerError) -> Self {
+        Self::Jer(e)
+    }
+}
+
+#[cfg(feature = "pem")]
+impl From<PemError> for Error {
+    fn from(e: PemError) -> Self {
+        Self::Pem(e)
+    }
+}
+
+impl From<ClockRegression> for Error {
+    fn from(e: ClockRegression) -> Self {
+        Self::ClockRegression(e)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Violation> for Error {
+    fn from(e: Violation) -> Self {
+        Self::Violation(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for io::Error {
+    /// Keeps I/O errors as they are and wraps everything else as `InvalidData`.
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => e,
+            other => io::Error::new(io::ErrorKind::InvalidData, other),
+        }
+    }
+}
+
+impl fmt::Display for UuidV7Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidVersion(v) => write!(f, "version is {v}, expected 7"),
+            Self::InvalidVariant(v) => write!(f, "variant is 0b{v:02b}, expected 0b10"),
+        }
+    }
+}
+
+impl core::error::Error for UuidV7Error {}
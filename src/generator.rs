@@ -0,0 +1,85 @@
+//! UUIDv7 generation from a pluggable [`Clock`] and [`RandomSource`].
+//!
+//! [`UuidV7Generator`] reads the clock and the random source once per value
+//! and combines them through [`UuidV7Seeds::to_u128`]. With a
+//! [`SteppingClock`](crate::source::SteppingClock) and a
+//! [`SeededRandom`](crate::source::SeededRandom), every value of a run is
+//! reproducible. Values are not monotonic within a millisecond; use
+//! [`MonotonicGenerator::next_u128_with`](crate::MonotonicGenerator::next_u128_with)
+//! for that.
+
+#[cfg(feature = "alloc")]
+use crate::Error;
+#[cfg(feature = "alloc")]
+use crate::RawUuidV7Asn1;
+use crate::UuidV7;
+use crate::UuidV7Seeds;
+use crate::source::Clock;
+use crate::source::RandomSource;
+#[cfg(feature = "std")]
+use crate::source::SystemClock;
+#[cfg(feature = "std")]
+use crate::source::SystemRandom;
+
+const UNIX_TS_MS_MAX: u64 = 0xFFFF_FFFF_FFFF;
+
+/// A UUIDv7 generator over a clock and a random source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UuidV7Generator<C, R> {
+    clock: C,
+    random: R,
+}
+
+impl<C: Clock, R: RandomSource> UuidV7Generator<C, R> {
+    pub fn new(clock: C, random: R) -> Self {
+        Self { clock, random }
+    }
+
+    /// Returns the seeds of the next value. Times past the 48-bit timestamp
+    /// are clamped to its maximum.
+    pub fn next_seeds(&mut self) -> UuidV7Seeds {
+        let since_epoch = self.clock.now();
+        UuidV7Seeds {
+            unix_ts_ms: since_epoch.as_millis().min(u128::from(UNIX_TS_MS_MAX)) as u64,
+            random_bytes: self.random.next_u128(),
+        }
+    }
+
+    /// Creates the next UUIDv7 `u128` value.
+    pub fn next_u128(&mut self) -> u128 {
+        self.next_seeds().to_u128()
+    }
+
+    /// Creates the next validated UUIDv7.
+    pub fn next_uuid_v7(&mut self) -> UuidV7 {
+        UuidV7(self.next_u128())
+    }
+
+    /// Creates the next UUIDv7 as a `RawUuidV7Asn1`.
+    #[cfg(feature = "alloc")]
+    pub fn next_raw_uuid_v7_asn1(&mut self) -> Result<RawUuidV7Asn1, Error> {
+        RawUuidV7Asn1::try_from(self.next_u128())
+    }
+
+    /// Returns the clock.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns the random source.
+    pub fn random(&self) -> &R {
+        &self.random
+    }
+
+    /// Returns the clock and the random source.
+    pub fn into_parts(self) -> (C, R) {
+        (self.clock, self.random)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for UuidV7Generator<SystemClock, SystemRandom> {
+    fn default() -> Self {
+        Self::new(SystemClock, SystemRandom)
+    }
+}
@@ -0,0 +1,581 @@
+//! JSON Encoding Rules (X.697) for `RawUuidV7` and the compact `UuidV7`.
+//!
+//! A SEQUENCE is a JSON object with one member per field, named after the
+//! field's identifier. INTEGERs are JSON numbers and BIT STRINGs are objects
+//! of the octets as hex digits, left-aligned like in DER, and the number of
+//! bits:
+//!
+//! ```text
+//! {"unix-ts-ms":1722873636745,"version":7,
+//!  "rand-a":{"value":"1230","length":12},
+//!  "variant":{"value":"80","length":2},
+//!  "rand-b":{"value":"048D159E26AF37BC","length":62}}
+//! ```
+//!
+//! The encoder writes a single line without whitespace and uppercase hex.
+//! The decoder accepts any JSON whitespace, members in any order, hex in
+//! either case, escaped characters in strings, and also the plain hex
+//! string X.697 allows for fixed-size BIT STRINGs. The unused bits of a
+//! BIT STRING must be zero.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::fmt::Write;
+
+use crate::Error;
+use crate::FieldError;
+use crate::RawUuidV7;
+use crate::RawUuidV7Field;
+#[cfg(feature = "alloc")]
+use crate::UnverifiedUuidV7;
+use crate::UuidV7;
+use crate::cursor::Cursor;
+use crate::cursor::NumberError;
+
+#[cfg(feature = "alloc")]
+const HEX_ALPHABET: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Longest string the decoder reads: the 20 hex digits of `rand-ab`, with
+/// room for escapes written as single characters.
+const MAX_STRING_LEN: usize = 32;
+
+/// Errors of the JER decoder.
+///
+/// Positions count characters from the start of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JerError {
+    /// The input ends before the document does.
+    UnexpectedEnd,
+    /// A character is not allowed at its position.
+    UnexpectedCharacter {
+        character: char,
+        position: usize,
+        expected: &'static str,
+    },
+    /// A string is longer than any string of a valid document.
+    StringTooLong { position: usize },
+    /// An object has a member the type does not define.
+    UnknownMember { position: usize },
+    /// An object has the same member twice.
+    DuplicateMember { name: &'static str, position: usize },
+    /// An object lacks a member.
+    MissingMember { name: &'static str },
+    /// An INTEGER has leading zeros.
+    NonMinimalInteger {
+        field: RawUuidV7Field,
+        position: usize,
+    },
+    /// An INTEGER that must not be negative is negative.
+    NegativeInteger {
+        field: RawUuidV7Field,
+        position: usize,
+    },
+    /// An INTEGER does not fit into 64 bits.
+    IntegerOverflow {
+        field: RawUuidV7Field,
+        position: usize,
+    },
+    /// The hex digits of a BIT STRING are not the octets of its length.
+    InvalidHex {
+        field: RawUuidV7Field,
+        position: usize,
+    },
+    /// Characters other than whitespace follow the document.
+    TrailingData { position: usize },
+}
+
+impl fmt::Display for JerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "input ends in the middle of the document"),
+            Self::UnexpectedCharacter {
+                character,
+                position,
+                expected,
+            } => write!(
+                f,
+                "unexpected character {character:?} at position {position}, expected {expected}"
+            ),
+            Self::StringTooLong { position } => {
+                write!(f, "position {position}: string is too long")
+            }
+            Self::UnknownMember { position } => {
+                write!(f, "position {position}: unknown member")
+            }
+            Self::DuplicateMember { name, position } => {
+                write!(f, "position {position}: duplicate member {name:?}")
+            }
+            Self::MissingMember { name } => write!(f, "missing member {name:?}"),
+            Self::NonMinimalInteger { field, position } => write!(
+                f,
+                "position {position}: {}: INTEGER has leading zeros",
+                field.name()
+            ),
+            Self::NegativeInteger { field, position } => write!(
+                f,
+                "position {position}: {}: INTEGER is negative",
+                field.name()
+            ),
+            Self::IntegerOverflow { field, position } => write!(
+                f,
+                "position {position}: {}: INTEGER does not fit in 64 bits",
+                field.name()
+            ),
+            Self::InvalidHex { field, position } => write!(
+                f,
+                "position {position}: {}: hex digits do not match the length",
+                field.name()
+            ),
+            Self::TrailingData { position } => {
+                write!(
+                    f,
+                    "unexpected content after the document at position {position}"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for JerError {}
+
+/// Builds a JSON object member by member.
+#[cfg(feature = "alloc")]
+struct Writer {
+    out: String,
+}
+
+#[cfg(feature = "alloc")]
+impl Writer {
+    fn new() -> Self {
+        Writer {
+            out: String::from("{"),
+        }
+    }
+
+    fn name(&mut self, field: RawUuidV7Field) {
+        if self.out.len() > 1 {
+            self.out.push(',');
+        }
+        self.out.push('"');
+        self.out.push_str(field.name());
+        self.out.push_str("\":");
+    }
+
+    fn integer(&mut self, field: RawUuidV7Field, value: u64) {
+        self.name(field);
+        // Writing to a `String` cannot fail.
+        let _ = write!(self.out, "{value}");
+    }
+
+    fn bit_string(&mut self, field: RawUuidV7Field, value: u128) {
+        self.name(field);
+        let octets = field.bit_len().div_ceil(8);
+        let left_aligned = value << (octets * 8 - field.bit_len());
+        self.out.push_str("{\"value\":\"");
+        for shift in (0..octets * 2).rev() {
+            let digit = (left_aligned >> (4 * shift)) & 0x0F;
+            self.out.push(char::from(HEX_ALPHABET[digit as usize]));
+        }
+        let _ = write!(self.out, "\",\"length\":{}}}", field.bit_len());
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push('}');
+        self.out
+    }
+}
+
+/// A decoded JSON string, with escapes resolved.
+struct ShortString {
+    bytes: [u8; MAX_STRING_LEN],
+    len: usize,
+}
+
+impl ShortString {
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+
+    fn push(&mut self, c: char) -> bool {
+        let mut utf8 = [0u8; 4];
+        let encoded = c.encode_utf8(&mut utf8).as_bytes();
+        let Some(slot) = self.bytes.get_mut(self.len..self.len + encoded.len()) else {
+            return false;
+        };
+        slot.copy_from_slice(encoded);
+        self.len += encoded.len();
+        true
+    }
+}
+
+/// Reads a document token by token.
+struct Parser<'a> {
+    cursor: Cursor<'a>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(jer: &'a str) -> Self {
+        Parser {
+            cursor: Cursor::new(jer),
+        }
+    }
+
+    /// Reports the character at `offset`, or the end of the input.
+    fn unexpected_at(&self, offset: usize, expected: &'static str) -> JerError {
+        match self.cursor.character_at(offset) {
+            Some(character) => JerError::UnexpectedCharacter {
+                character,
+                position: self.cursor.position_of(offset),
+                expected,
+            },
+            None => JerError::UnexpectedEnd,
+        }
+    }
+
+    fn expect(&mut self, token: u8, expected: &'static str) -> Result<(), JerError> {
+        self.cursor.skip_whitespace();
+        if self.cursor.peek() != Some(token) {
+            return Err(self.unexpected_at(self.cursor.offset, expected));
+        }
+        self.cursor.offset += 1;
+        Ok(())
+    }
+
+    /// Reads a string of at most [`MAX_STRING_LEN`] bytes.
+    fn string(&mut self) -> Result<ShortString, JerError> {
+        self.expect(b'"', "a string")?;
+        let start = self.cursor.offset - 1;
+        let mut string = ShortString {
+            bytes: [0; MAX_STRING_LEN],
+            len: 0,
+        };
+        loop {
+            let offset = self.cursor.offset;
+            let c = self
+                .cursor
+                .character_at(offset)
+                .ok_or(JerError::UnexpectedEnd)?;
+            self.cursor.offset += c.len_utf8();
+            let c = match c {
+                '"' => return Ok(string),
+                '\\' => self.escape()?,
+                '\0'..='\x1f' => return Err(self.unexpected_at(offset, "a printable character")),
+                c => c,
+            };
+            if !string.push(c) {
+                return Err(JerError::StringTooLong {
+                    position: self.cursor.position_of(start),
+                });
+            }
+        }
+    }
+
+    /// Reads the rest of an escape sequence after the backslash.
+    fn escape(&mut self) -> Result<char, JerError> {
+        let offset = self.cursor.offset;
+        let escaped = self.cursor.peek().ok_or(JerError::UnexpectedEnd)?;
+        self.cursor.offset += 1;
+        let c = match escaped {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\x08',
+            b'f' => '\x0c',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let digits = self
+                    .cursor
+                    .text
+                    .get(offset + 1..offset + 5)
+                    .ok_or(JerError::UnexpectedEnd)?;
+                // Surrogates only encode characters no valid string contains.
+                let c = u32::from_str_radix(digits, 16)
+                    .ok()
+                    .filter(|_| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.unexpected_at(offset + 1, "four hex digits"))?;
+                self.cursor.offset += 4;
+                c
+            }
+            _ => return Err(self.unexpected_at(offset, "an escape character")),
+        };
+        Ok(c)
+    }
+
+    /// Reads a non-negative INTEGER of at most 64 bits.
+    fn integer(&mut self, field: RawUuidV7Field) -> Result<u64, JerError> {
+        self.cursor.skip_whitespace();
+        let offset = self.cursor.offset;
+        let position = self.cursor.position_of(offset);
+        let value = self.cursor.number().map_err(|e| match e {
+            NumberError::NoDigits => self.unexpected_at(offset, "a number"),
+            NumberError::LeadingZero => JerError::NonMinimalInteger { field, position },
+            NumberError::Negative => JerError::NegativeInteger { field, position },
+            NumberError::Overflow => JerError::IntegerOverflow { field, position },
+        })?;
+        if let Some(b'.' | b'e' | b'E') = self.cursor.peek() {
+            return Err(self.unexpected_at(self.cursor.offset, "an integer"));
+        }
+        Ok(value)
+    }
+
+    /// Reads an object, passing each member name and the offset of the
+    /// name to `member`, which reads the value.
+    fn object(
+        &mut self,
+        mut member: impl FnMut(&mut Self, &str, usize) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        self.expect(b'{', "an object")?;
+        self.cursor.skip_whitespace();
+        if self.cursor.peek() == Some(b'}') {
+            self.cursor.offset += 1;
+            return Ok(());
+        }
+        loop {
+            self.cursor.skip_whitespace();
+            let offset = self.cursor.offset;
+            let name = self.string()?;
+            self.expect(b':', "':'")?;
+            member(self, name.as_str(), offset)?;
+
+            self.cursor.skip_whitespace();
+            match self.cursor.peek() {
+                Some(b',') => self.cursor.offset += 1,
+                Some(b'}') => {
+                    self.cursor.offset += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.unexpected_at(self.cursor.offset, "',' or '}'").into()),
+            }
+        }
+    }
+
+    /// Reads a BIT STRING of exactly `field.bit_len()` bits, as an object
+    /// or a plain hex string.
+    fn bit_string(&mut self, field: RawUuidV7Field) -> Result<u128, Error> {
+        self.cursor.skip_whitespace();
+        let (hex, hex_offset, length) = if self.cursor.peek() == Some(b'{') {
+            let mut value = None;
+            let mut length = None;
+            self.object(|parser, name, offset| match name {
+                "value" => {
+                    parser.cursor.skip_whitespace();
+                    let hex_offset = parser.cursor.offset;
+                    let hex = parser.string()?;
+                    set(&mut value, (hex, hex_offset), "value", parser, offset)
+                }
+                "length" => {
+                    let length_value = parser.integer(field)?;
+                    set(&mut length, length_value, "length", parser, offset)
+                }
+                _ => Err(parser.unknown_member(offset)),
+            })?;
+            let (hex, hex_offset) = value.ok_or(JerError::MissingMember { name: "value" })?;
+            let length = length.ok_or(JerError::MissingMember { name: "length" })?;
+            (
+                hex,
+                hex_offset,
+                usize::try_from(length).unwrap_or(usize::MAX),
+            )
+        } else {
+            let hex_offset = self.cursor.offset;
+            (self.string()?, hex_offset, field.bit_len())
+        };
+
+        let expected = field.bit_len();
+        if length != expected {
+            return Err(Error::Field(FieldError::InvalidBitLength {
+                field,
+                expected,
+                actual: length,
+            }));
+        }
+
+        let invalid_hex = JerError::InvalidHex {
+            field,
+            position: self.cursor.position_of(hex_offset),
+        };
+        let octets = expected.div_ceil(8);
+        let hex = hex.as_str();
+        if hex.len() != octets * 2 {
+            return Err(invalid_hex.into());
+        }
+        let mut raw = 0u128;
+        for c in hex.chars() {
+            let digit = c.to_digit(16).ok_or(invalid_hex)?;
+            raw = (raw << 4) | u128::from(digit);
+        }
+
+        let unused = octets * 8 - expected;
+        if raw & ((1 << unused) - 1) != 0 {
+            return Err(FieldError::NonZeroUnusedBits { field }.into());
+        }
+        Ok(raw >> unused)
+    }
+
+    fn unknown_member(&self, offset: usize) -> Error {
+        JerError::UnknownMember {
+            position: self.cursor.position_of(offset),
+        }
+        .into()
+    }
+
+    fn finish(mut self) -> Result<(), JerError> {
+        self.cursor.skip_whitespace();
+        if !self.cursor.is_at_end() {
+            return Err(JerError::TrailingData {
+                position: self.cursor.position_of(self.cursor.offset),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Stores the value of member `name`, which starts at `offset`, unless the
+/// object already had it.
+fn set<T>(
+    slot: &mut Option<T>,
+    value: T,
+    name: &'static str,
+    parser: &Parser<'_>,
+    offset: usize,
+) -> Result<(), Error> {
+    if slot.is_some() {
+        return Err(JerError::DuplicateMember {
+            name,
+            position: parser.cursor.position_of(offset),
+        }
+        .into());
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// Returns the value of member `field`, or an error if it was missing.
+fn required<T>(slot: Option<T>, field: RawUuidV7Field) -> Result<T, JerError> {
+    slot.ok_or(JerError::MissingMember { name: field.name() })
+}
+
+impl RawUuidV7 {
+    /// Encodes the value as a JER `RawUuidV7` document.
+    #[cfg(feature = "alloc")]
+    pub fn to_jer_string(&self) -> Result<String, Error> {
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, self.unix_ts_ms)?;
+        let rand_a = RawUuidV7::check_range(RawUuidV7Field::RandA, self.rand_a.into())?;
+        let variant = RawUuidV7::check_range(RawUuidV7Field::Variant, self.variant.into())?;
+        let rand_b = RawUuidV7::check_range(RawUuidV7Field::RandB, self.rand_b)?;
+
+        let mut writer = Writer::new();
+        writer.integer(RawUuidV7Field::UnixTsMs, unix_ts_ms);
+        writer.integer(RawUuidV7Field::Version, self.version.into());
+        writer.bit_string(RawUuidV7Field::RandA, rand_a.into());
+        writer.bit_string(RawUuidV7Field::Variant, variant.into());
+        writer.bit_string(RawUuidV7Field::RandB, rand_b.into());
+        Ok(writer.finish())
+    }
+
+    /// Decodes a JER `RawUuidV7` document.
+    pub fn from_jer(jer: &str) -> Result<Self, Error> {
+        let mut unix_ts_ms = None;
+        let mut version = None;
+        let mut rand_a = None;
+        let mut variant = None;
+        let mut rand_b = None;
+
+        let mut parser = Parser::new(jer);
+        parser.object(|parser, name, offset| {
+            let (field, slot) = match name {
+                "unix-ts-ms" => (RawUuidV7Field::UnixTsMs, &mut unix_ts_ms),
+                "version" => (RawUuidV7Field::Version, &mut version),
+                "rand-a" => (RawUuidV7Field::RandA, &mut rand_a),
+                "variant" => (RawUuidV7Field::Variant, &mut variant),
+                "rand-b" => (RawUuidV7Field::RandB, &mut rand_b),
+                _ => return Err(parser.unknown_member(offset)),
+            };
+            let value = match field {
+                RawUuidV7Field::UnixTsMs | RawUuidV7Field::Version => {
+                    u128::from(parser.integer(field)?)
+                }
+                _ => parser.bit_string(field)?,
+            };
+            set(slot, value, field.name(), parser, offset)
+        })?;
+        parser.finish()?;
+
+        let unix_ts_ms = required(unix_ts_ms, RawUuidV7Field::UnixTsMs)? as u64;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, unix_ts_ms)?;
+        let version = required(version, RawUuidV7Field::Version)? as u64;
+        let version = u8::try_from(version).map_err(|_| FieldError::OutOfRange {
+            field: RawUuidV7Field::Version,
+            value: version,
+        })?;
+        Ok(RawUuidV7 {
+            unix_ts_ms,
+            version,
+            rand_a: required(rand_a, RawUuidV7Field::RandA)? as u16,
+            variant: required(variant, RawUuidV7Field::Variant)? as u8,
+            rand_b: required(rand_b, RawUuidV7Field::RandB)? as u64,
+        })
+    }
+}
+
+impl UuidV7 {
+    /// Encodes the value as a JER compact `UuidV7` document.
+    #[cfg(feature = "alloc")]
+    pub fn to_jer_string(&self) -> String {
+        let unverified_uuid = UnverifiedUuidV7::from(*self);
+        let rand_ab =
+            (u128::from(unverified_uuid.rand_a()) << 62) | u128::from(unverified_uuid.rand_b());
+
+        let mut writer = Writer::new();
+        writer.integer(RawUuidV7Field::UnixTsMs, unverified_uuid.unix_ts_ms());
+        writer.bit_string(RawUuidV7Field::RandAb, rand_ab);
+        writer.finish()
+    }
+
+    /// Decodes a JER compact `UuidV7` document.
+    pub fn from_jer(jer: &str) -> Result<Self, Error> {
+        let mut unix_ts_ms = None;
+        let mut rand_ab = None;
+
+        let mut parser = Parser::new(jer);
+        parser.object(|parser, name, offset| match name {
+            "unix-ts-ms" => {
+                let value = parser.integer(RawUuidV7Field::UnixTsMs)?;
+                set(
+                    &mut unix_ts_ms,
+                    value,
+                    RawUuidV7Field::UnixTsMs.name(),
+                    parser,
+                    offset,
+                )
+            }
+            "rand-ab" => {
+                let value = parser.bit_string(RawUuidV7Field::RandAb)?;
+                set(
+                    &mut rand_ab,
+                    value,
+                    RawUuidV7Field::RandAb.name(),
+                    parser,
+                    offset,
+                )
+            }
+            _ => Err(parser.unknown_member(offset)),
+        })?;
+        parser.finish()?;
+
+        let unix_ts_ms = required(unix_ts_ms, RawUuidV7Field::UnixTsMs)?;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, unix_ts_ms)?;
+        let rand_ab = required(rand_ab, RawUuidV7Field::RandAb)?;
+
+        let rand_a = rand_ab >> 62;
+        let rand_b = rand_ab & 0x3FFF_FFFF_FFFF_FFFF;
+        Ok(UuidV7(
+            ((unix_ts_ms as u128) << 80) | (7u128 << 76) | (rand_a << 64) | (2u128 << 62) | rand_b,
+        ))
+    }
+}
@@ -1,15 +1,121 @@
-use std::io;
+#![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
 use uuid::Timestamp;
 
+#[cfg(feature = "alloc")]
+use der::Decode;
+#[cfg(feature = "alloc")]
 use der::Encode;
+#[cfg(feature = "alloc")]
+use der::Reader;
+#[cfg(feature = "alloc")]
 use der::Sequence;
+#[cfg(feature = "alloc")]
+use der::SliceReader;
+#[cfg(feature = "alloc")]
+use der::referenced::OwnedToRef;
+
+#[cfg(feature = "alloc")]
+pub mod batch;
+#[cfg(feature = "alloc")]
+pub mod ber;
+mod bitfield;
+mod borrowed;
+mod convert;
+mod cursor;
+#[cfg(feature = "alloc")]
+pub mod dump;
+pub mod error;
+pub mod generator;
+pub mod jer;
+pub mod monotonic;
+pub mod oer;
+#[cfg(feature = "pem")]
+pub mod pem;
+pub mod per;
+pub mod precision;
+mod range;
+pub mod regression;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod source;
+#[cfg(feature = "std")]
+pub mod stream;
+pub mod text;
+#[cfg(feature = "alloc")]
+mod tlv;
+#[cfg(feature = "alloc")]
+pub mod validate;
+pub mod xer;
+
+#[cfg(feature = "alloc")]
+pub use batch::DerBatch;
+pub use bitfield::BitLayout;
+pub use borrowed::DerArray;
+pub use borrowed::RAW_UUID_V7_DER_MAX_LEN;
+pub use borrowed::RawUuidV7Asn1Ref;
+pub use borrowed::UUID_V7_DER_MAX_LEN;
+pub use borrowed::UuidV7Asn1Ref;
+pub use error::Error;
+pub use error::ErrorCode;
+pub use generator::UuidV7Generator;
+pub use jer::JerError;
+pub use monotonic::MonotonicGenerator;
+pub use monotonic::MonotonicStrategy;
+pub use oer::OerError;
+pub use oer::OerRules;
+pub use oer::RAW_UUID_V7_OER_MAX_LEN;
+pub use oer::UUID_V7_OER_MAX_LEN;
+#[cfg(feature = "pem")]
+pub use pem::LabelPolicy;
+#[cfg(feature = "pem")]
+pub use pem::PemError;
+pub use per::PerError;
+pub use per::PerVariant;
+pub use per::RAW_UUID_V7_PER_MAX_LEN;
+pub use per::UUID_V7_PER_MAX_LEN;
+pub use precision::TimeError;
+pub use regression::ClockRegression;
+pub use regression::GuardedGenerator;
+pub use regression::RegressionMetrics;
+pub use regression::RegressionPolicy;
+pub use source::Clock;
+pub use source::RandomSource;
+#[cfg(feature = "std")]
+pub use stream::DerReader;
+#[cfg(feature = "std")]
+pub use stream::DerWriter;
+#[cfg(feature = "std")]
+pub use stream::SequenceOfReader;
+#[cfg(feature = "std")]
+pub use stream::write_sequence_of;
+pub use text::Formatted;
+pub use text::ParseError;
+pub use text::TextFormat;
+#[cfg(feature = "alloc")]
+pub use validate::ValidationPolicy;
+#[cfg(feature = "alloc")]
+pub use validate::Violation;
+pub use xer::XerError;
+pub use xer::XerStyle;
 
 /// Represents the seeds for generating a UUIDv7.
 ///
 /// This struct holds the necessary components to create a UUIDv7: a precise
 /// Unix timestamp and a source of random data.
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UuidV7Seeds {
     /// 48-bit Unix timestamp in milliseconds.
     pub unix_ts_ms: u64,
@@ -35,8 +141,8 @@ impl UuidV7Seeds {
         let mut uuid = self.random_bytes;
 
         // Clear the top 48 bits and insert the timestamp.
-        uuid &= 0x0000_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF;
-        uuid |= (self.unix_ts_ms as u128) << 80;
+        uuid &= 0x0000_0000_0000_FFFF_FFFF_FFFF_FFFF_FFFF;
+        uuid |= ((self.unix_ts_ms & 0xFFFF_FFFF_FFFF) as u128) << 80;
 
         // Clear the version bits (76-79) and set them to 7 (0b0111).
         uuid &= 0xFFFF_FFFF_FFFF_0FFF_FFFF_FFFF_FFFF_FFFF;
@@ -48,6 +154,36 @@ impl UuidV7Seeds {
 
         uuid
     }
+
+    /// Creates a new UUIDv7 `u128` value like [`UuidV7Seeds::to_u128`], with the
+    /// 12-bit `rand_a` part replaced by `rand_a`.
+    ///
+    /// Used by the RFC 9562 counter and sub-millisecond methods; only the low
+    /// 12 bits of `rand_a` are used.
+    pub fn to_u128_with_rand_a(&self, rand_a: u16) -> u128 {
+        let mut uuid = self.to_u128();
+
+        // Clear the rand_a bits (64-75) and insert the counter.
+        uuid &= 0xFFFF_FFFF_FFFF_F000_FFFF_FFFF_FFFF_FFFF;
+        uuid |= ((rand_a & 0x0FFF) as u128) << 64;
+
+        uuid
+    }
+
+    /// Creates a new UUIDv7 `u128` value like [`UuidV7Seeds::to_u128`], with the
+    /// 62-bit `rand_b` part replaced by `rand_b`.
+    ///
+    /// Used by the RFC 9562 random-increment counter method; only the low
+    /// 62 bits of `rand_b` are used.
+    pub fn to_u128_with_rand_b(&self, rand_b: u64) -> u128 {
+        let mut uuid = self.to_u128();
+
+        // Clear the rand_b bits (0-61) and insert the counter.
+        uuid &= 0xFFFF_FFFF_FFFF_FFFF_C000_0000_0000_0000;
+        uuid |= (rand_b & 0x3FFF_FFFF_FFFF_FFFF) as u128;
+
+        uuid
+    }
 }
 
 impl From<UuidV7Seeds> for u128 {
@@ -93,7 +229,10 @@ impl UnverifiedUuidV7 {
 ///
 /// This struct ensures that the wrapped `u128` value conforms to the UUIDv7
 /// specification regarding its version and variant bits.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Values compare and hash like their 16 bytes in network order, so they
+/// sort by creation time first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct UuidV7(u128);
 
 impl UuidV7 {
@@ -135,6 +274,7 @@ impl TryFrom<UnverifiedUuidV7> for UuidV7 {
 /// This struct provides a structured view of a UUIDv7's constituent parts,
 /// as extracted from a `u128` value.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RawUuidV7 {
     /// The 48-bit Unix timestamp in milliseconds.
     pub unix_ts_ms: u64,
@@ -161,12 +301,156 @@ impl From<UnverifiedUuidV7> for RawUuidV7 {
     }
 }
 
+/// Identifies a field of a `RawUuidV7`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawUuidV7Field {
+    /// The 48-bit `unix-ts-ms` field.
+    UnixTsMs,
+    /// The 4-bit `version` field.
+    Version,
+    /// The 12-bit `rand-a` field.
+    RandA,
+    /// The 2-bit `variant` field.
+    Variant,
+    /// The 62-bit `rand-b` field.
+    RandB,
+    /// The 74-bit `rand-ab` field of the compact `UuidV7` type.
+    RandAb,
+}
+
+impl RawUuidV7Field {
+    /// Returns the width of the field in bits.
+    pub fn bit_len(&self) -> usize {
+        match self {
+            Self::UnixTsMs => 48,
+            Self::Version => 4,
+            Self::RandA => 12,
+            Self::Variant => 2,
+            Self::RandB => 62,
+            Self::RandAb => 74,
+        }
+    }
+
+    /// Returns the largest value the field can hold.
+    pub fn max_value(&self) -> u128 {
+        (1u128 << self.bit_len()) - 1
+    }
+
+    /// Returns the ASN.1 identifier of the field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UnixTsMs => "unix-ts-ms",
+            Self::Version => "version",
+            Self::RandA => "rand-a",
+            Self::Variant => "variant",
+            Self::RandB => "rand-b",
+            Self::RandAb => "rand-ab",
+        }
+    }
+}
+
+/// Error type for field-level failures while rebuilding a UUIDv7.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldError {
+    /// The BIT STRING does not have the number of bits the field requires.
+    InvalidBitLength {
+        field: RawUuidV7Field,
+        expected: usize,
+        actual: usize,
+    },
+    /// The value does not fit into the width of the field.
+    OutOfRange { field: RawUuidV7Field, value: u64 },
+    /// The trailing unused bits of the BIT STRING are not zero.
+    NonZeroUnusedBits { field: RawUuidV7Field },
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBitLength {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: expected {} bits, got {}",
+                field.name(),
+                expected,
+                actual
+            ),
+            Self::OutOfRange { field, value } => write!(
+                f,
+                "{}: value {} does not fit in {} bits",
+                field.name(),
+                value,
+                field.bit_len()
+            ),
+            Self::NonZeroUnusedBits { field } => {
+                write!(f, "{}: unused bits are not zero", field.name())
+            }
+        }
+    }
+}
+
+impl core::error::Error for FieldError {}
+
+impl RawUuidV7 {
+    /// Checks that the value fits into the width of the field.
+    fn check_range(field: RawUuidV7Field, value: u64) -> Result<u64, FieldError> {
+        if u128::from(value) > field.max_value() {
+            return Err(FieldError::OutOfRange { field, value });
+        }
+        Ok(value)
+    }
+}
+
+impl TryFrom<RawUuidV7> for UnverifiedUuidV7 {
+    type Error = FieldError;
+
+    /// Reassembles the `u128` value, rejecting fields wider than the UUIDv7 layout allows.
+    fn try_from(raw_uuid: RawUuidV7) -> Result<Self, Self::Error> {
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, raw_uuid.unix_ts_ms)?;
+        let version = RawUuidV7::check_range(RawUuidV7Field::Version, raw_uuid.version.into())?;
+        let rand_a = RawUuidV7::check_range(RawUuidV7Field::RandA, raw_uuid.rand_a.into())?;
+        let variant = RawUuidV7::check_range(RawUuidV7Field::Variant, raw_uuid.variant.into())?;
+        let rand_b = RawUuidV7::check_range(RawUuidV7Field::RandB, raw_uuid.rand_b)?;
+
+        Ok(UnverifiedUuidV7(
+            ((unix_ts_ms as u128) << 80)
+                | ((version as u128) << 76)
+                | ((rand_a as u128) << 64)
+                | ((variant as u128) << 62)
+                | (rand_b as u128),
+        ))
+    }
+}
+
+impl From<UnverifiedUuidV7> for u128 {
+    fn from(unverified_uuid: UnverifiedUuidV7) -> Self {
+        unverified_uuid.0
+    }
+}
+
+impl From<UuidV7> for u128 {
+    fn from(uuid_v7: UuidV7) -> Self {
+        uuid_v7.as_u128()
+    }
+}
+
+impl From<UuidV7> for UnverifiedUuidV7 {
+    fn from(uuid_v7: UuidV7) -> Self {
+        UnverifiedUuidV7(uuid_v7.as_u128())
+    }
+}
+
+#[cfg(feature = "alloc")]
 use der::asn1::BitString;
 use uuid::Uuid;
 
 /// Represents the ASN.1 structure of a Raw UUIDv7.
 ///
 /// This struct is intended for serialization/deserialization to/from ASN.1 DER.
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq, Eq, Sequence)]
 pub struct RawUuidV7Asn1 {
     /// The 48-bit Unix timestamp in milliseconds.
@@ -185,27 +469,54 @@ pub struct RawUuidV7Asn1 {
     pub rand_b: BitString,
 }
 
+#[cfg(feature = "alloc")]
 impl RawUuidV7Asn1 {
-    pub fn to_der_bytes(&self) -> Result<Vec<u8>, io::Error> {
-        self.to_der().map_err(io::Error::other)
+    pub fn to_der_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.to_der()?)
+    }
+
+    /// Parses a single DER-encoded `RawUuidV7` value.
+    ///
+    /// The input must contain exactly one value; trailing bytes are rejected.
+    pub fn from_der_bytes(der_bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_der(der_bytes)?)
+    }
+
+    /// Parses the first DER-encoded `RawUuidV7` value of `der_bytes`.
+    ///
+    /// Returns the value and the bytes following it, which makes it possible
+    /// to walk a buffer of concatenated values.
+    pub fn from_der_prefix(der_bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let mut reader = SliceReader::new(der_bytes)?;
+        let asn1 = Self::decode(&mut reader)?;
+        let used = usize::try_from(reader.position())?;
+        Ok((asn1, &der_bytes[used..]))
     }
 }
 
+#[cfg(feature = "alloc")]
 impl TryFrom<RawUuidV7> for RawUuidV7Asn1 {
-    type Error = der::Error;
+    type Error = Error;
 
+    /// Builds the ASN.1 value, rejecting a timestamp or BIT STRING fields
+    /// wider than the UUIDv7 layout allows.
     fn try_from(raw_uuid: RawUuidV7) -> Result<Self, Self::Error> {
-        // Convert u16 (12 bits) to BitString (2 bytes, 4 unused bits)
-        let rand_a_bitstring = BitString::new(4, &raw_uuid.rand_a.to_be_bytes()[..])?;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, raw_uuid.unix_ts_ms)?;
+        let rand_a = RawUuidV7::check_range(RawUuidV7Field::RandA, raw_uuid.rand_a.into())?;
+        let variant = RawUuidV7::check_range(RawUuidV7Field::Variant, raw_uuid.variant.into())?;
+        let rand_b = RawUuidV7::check_range(RawUuidV7Field::RandB, raw_uuid.rand_b)?;
+
+        // Left-align u16 (12 bits) into a BitString (2 bytes, 4 unused bits)
+        let rand_a_bitstring = bitfield::encode(RawUuidV7Field::RandA, rand_a.into())?;
 
-        // Convert u8 (2 bits) to BitString (1 byte, 6 unused bits)
-        let variant_bitstring = BitString::new(6, &raw_uuid.variant.to_be_bytes()[..])?;
+        // Left-align u8 (2 bits) into a BitString (1 byte, 6 unused bits)
+        let variant_bitstring = bitfield::encode(RawUuidV7Field::Variant, variant.into())?;
 
-        // Convert u64 (62 bits) to BitString (8 bytes, 2 unused bits)
-        let rand_b_bitstring = BitString::new(2, &raw_uuid.rand_b.to_be_bytes()[..])?;
+        // Left-align u64 (62 bits) into a BitString (8 bytes, 2 unused bits)
+        let rand_b_bitstring = bitfield::encode(RawUuidV7Field::RandB, rand_b.into())?;
 
         Ok(RawUuidV7Asn1 {
-            unix_ts_ms: raw_uuid.unix_ts_ms,
+            unix_ts_ms,
             version: raw_uuid.version,
             rand_a: rand_a_bitstring,
             variant: variant_bitstring,
@@ -214,8 +525,95 @@ impl TryFrom<RawUuidV7> for RawUuidV7Asn1 {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl RawUuidV7Asn1 {
+    /// Returns a view of the value that borrows the BIT STRING octets.
+    pub fn as_borrowed(&self) -> RawUuidV7Asn1Ref<'_> {
+        RawUuidV7Asn1Ref {
+            unix_ts_ms: self.unix_ts_ms,
+            version: self.version,
+            rand_a: self.rand_a.owned_to_ref(),
+            variant: self.variant.owned_to_ref(),
+            rand_b: self.rand_b.owned_to_ref(),
+        }
+    }
+
+    /// Detects the bit layout of the BIT STRING fields.
+    ///
+    /// Blobs written by version 0.1.0 of this crate right-aligned the fields,
+    /// which leaves significant bits in the DER "unused" positions. A UUIDv7
+    /// variant `0b10` always sets such a bit, so those blobs are detected reliably.
+    pub fn bit_layout(&self) -> BitLayout {
+        self.as_borrowed().bit_layout()
+    }
+
+    /// Encodes the value into a stack buffer.
+    pub fn to_der_array(&self) -> Result<DerArray<RAW_UUID_V7_DER_MAX_LEN>, Error> {
+        self.as_borrowed().to_der_array()
+    }
+
+    /// Extracts the fields, reading the BIT STRINGs with the given layout.
+    pub fn to_raw_uuid_v7_with(&self, layout: BitLayout) -> Result<RawUuidV7, FieldError> {
+        self.as_borrowed().to_raw_uuid_v7_with(layout)
+    }
+
+    /// Extracts the fields, accepting both the DER layout and the legacy layout.
+    pub fn to_raw_uuid_v7_compat(&self) -> Result<RawUuidV7, FieldError> {
+        self.to_raw_uuid_v7_with(self.bit_layout())
+    }
+
+    /// Re-encodes a value written with the legacy layout using the DER layout.
+    ///
+    /// Values already in the DER layout are returned unchanged.
+    pub fn repair(self) -> Result<Self, Error> {
+        match self.bit_layout() {
+            BitLayout::MsbFirst => Ok(self),
+            BitLayout::LegacyLsbFirst => {
+                let raw_uuid = self.to_raw_uuid_v7_compat()?;
+                Ok(RawUuidV7Asn1::try_from(raw_uuid)?)
+            }
+        }
+    }
+
+    /// Parses DER bytes written with either layout and repairs them to the DER layout.
+    pub fn from_der_bytes_compat(der_bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_der_bytes(der_bytes)?.repair()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<RawUuidV7Asn1> for RawUuidV7 {
+    type Error = FieldError;
+
+    /// Extracts the fields, requiring the DER layout for the BIT STRINGs.
+    fn try_from(asn1: RawUuidV7Asn1) -> Result<Self, Self::Error> {
+        asn1.to_raw_uuid_v7_with(BitLayout::MsbFirst)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<RawUuidV7Asn1> for UnverifiedUuidV7 {
+    type Error = FieldError;
+
+    fn try_from(asn1: RawUuidV7Asn1) -> Result<Self, Self::Error> {
+        let raw_uuid: RawUuidV7 = asn1.try_into()?;
+        raw_uuid.try_into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<RawUuidV7Asn1> for u128 {
+    type Error = FieldError;
+
+    fn try_from(asn1: RawUuidV7Asn1) -> Result<Self, Self::Error> {
+        let unverified_uuid: UnverifiedUuidV7 = asn1.try_into()?;
+        Ok(unverified_uuid.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl TryFrom<u128> for RawUuidV7Asn1 {
-    type Error = der::Error;
+    type Error = Error;
 
     fn try_from(uuid_u128: u128) -> Result<Self, Self::Error> {
         let unverified_uuid = UnverifiedUuidV7(uuid_u128);
@@ -224,20 +622,136 @@ impl TryFrom<u128> for RawUuidV7Asn1 {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl TryFrom<Uuid> for RawUuidV7Asn1 {
-    type Error = der::Error;
+    type Error = Error;
 
     fn try_from(uuid_value: Uuid) -> Result<Self, Self::Error> {
         RawUuidV7Asn1::try_from(uuid_value.as_u128())
     }
 }
 
-pub fn new_raw_uuid_v7_asn1(now: Timestamp) -> Result<RawUuidV7Asn1, io::Error> {
+impl From<UuidV7> for Uuid {
+    fn from(uuid_v7: UuidV7) -> Self {
+        Uuid::from_u128(uuid_v7.as_u128())
+    }
+}
+
+impl From<UnverifiedUuidV7> for Uuid {
+    fn from(unverified_uuid: UnverifiedUuidV7) -> Self {
+        Uuid::from_u128(unverified_uuid.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<RawUuidV7Asn1> for UuidV7 {
+    type Error = Error;
+
+    fn try_from(asn1: RawUuidV7Asn1) -> Result<Self, Self::Error> {
+        let unverified_uuid: UnverifiedUuidV7 = asn1.try_into()?;
+        Ok(unverified_uuid.try_into()?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<RawUuidV7Asn1> for Uuid {
+    type Error = Error;
+
+    fn try_from(asn1: RawUuidV7Asn1) -> Result<Self, Self::Error> {
+        let uuid_v7: UuidV7 = asn1.try_into()?;
+        Ok(uuid_v7.into())
+    }
+}
+
+/// Represents the ASN.1 structure of the compact UuidV7.
+///
+/// The constant version and variant fields are left out; `rand_ab` holds the
+/// 12-bit `rand_a` part followed by the 62-bit `rand_b` part.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Sequence)]
+pub struct UuidV7Asn1 {
+    /// The 48-bit Unix timestamp in milliseconds.
+    pub unix_ts_ms: u64,
+
+    /// The 74-bit `rand_a` and `rand_b` parts as an ASN.1 BitString.
+    pub rand_ab: BitString,
+}
+
+#[cfg(feature = "alloc")]
+impl UuidV7Asn1 {
+    pub fn to_der_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.to_der()?)
+    }
+
+    /// Parses a single DER-encoded `UuidV7` value.
+    ///
+    /// The input must contain exactly one value; trailing bytes are rejected.
+    pub fn from_der_bytes(der_bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_der(der_bytes)?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<UuidV7> for UuidV7Asn1 {
+    type Error = der::Error;
+
+    fn try_from(uuid_v7: UuidV7) -> Result<Self, Self::Error> {
+        let unverified_uuid = UnverifiedUuidV7::from(uuid_v7);
+        let rand_ab =
+            (u128::from(unverified_uuid.rand_a()) << 62) | u128::from(unverified_uuid.rand_b());
+
+        Ok(UuidV7Asn1 {
+            unix_ts_ms: unverified_uuid.unix_ts_ms(),
+            rand_ab: bitfield::encode(RawUuidV7Field::RandAb, rand_ab)?,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<UuidV7Asn1> for UuidV7 {
+    type Error = FieldError;
+
+    /// Rebuilds the UUIDv7, putting back the version and variant bits.
+    ///
+    /// `rand_ab` must be exactly 74 bits long.
+    fn try_from(asn1: UuidV7Asn1) -> Result<Self, Self::Error> {
+        let asn1 = UuidV7Asn1Ref {
+            unix_ts_ms: asn1.unix_ts_ms,
+            rand_ab: asn1.rand_ab.owned_to_ref(),
+        };
+        asn1.to_uuid_v7()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<UuidV7Asn1> for Uuid {
+    type Error = FieldError;
+
+    fn try_from(asn1: UuidV7Asn1) -> Result<Self, Self::Error> {
+        let uuid_v7: UuidV7 = asn1.try_into()?;
+        Ok(uuid_v7.into())
+    }
+}
+
+/// Decodes DER bytes of a `RawUuidV7` into a validated `UuidV7`.
+pub fn der2uuid_v7(der_bytes: &[u8]) -> Result<UuidV7, Error> {
+    let raw_uuid = RawUuidV7::decode_der(der_bytes)?;
+    let unverified_uuid = UnverifiedUuidV7::try_from(raw_uuid)?;
+    Ok(unverified_uuid.try_into()?)
+}
+
+#[cfg(feature = "std")]
+pub fn new_raw_uuid_v7_asn1(now: Timestamp) -> Result<RawUuidV7Asn1, Error> {
     let v7: Uuid = Uuid::new_v7(now);
-    v7.try_into().map_err(io::Error::other)
+    v7.try_into()
 }
 
-pub fn new_raw_uuid_v7_asn1_now() -> Result<RawUuidV7Asn1, io::Error> {
+/// Creates a `RawUuidV7Asn1` from `Uuid::now_v7`.
+///
+/// Values sort before earlier ones if the wall clock steps backwards; use a
+/// [`GuardedGenerator`] where that matters.
+#[cfg(feature = "std")]
+pub fn new_raw_uuid_v7_asn1_now() -> Result<RawUuidV7Asn1, Error> {
     let v7: Uuid = Uuid::now_v7();
-    v7.try_into().map_err(io::Error::other)
+    v7.try_into()
 }
@@ -0,0 +1,224 @@
+//! Monotonic UUIDv7 generation (RFC 9562, section 6.2).
+//!
+//! [`UuidV7Seeds::to_u128`] fills everything below the timestamp with random
+//! bits, so UUIDs created within the same millisecond sort randomly. The
+//! [`MonotonicGenerator`] keeps the last issued value and uses part of the
+//! random bits as a counter, so every value it returns sorts after the
+//! previous one.
+
+use core::time::Duration;
+
+use crate::Error;
+#[cfg(feature = "std")]
+use crate::RawUuidV7Asn1;
+#[cfg(feature = "std")]
+use crate::UuidV7;
+use crate::UuidV7Seeds;
+use crate::precision;
+use crate::precision::TimeError;
+use crate::source::Clock;
+use crate::source::RandomSource;
+#[cfg(feature = "std")]
+use crate::source::SystemClock;
+#[cfg(feature = "std")]
+use crate::source::SystemRandom;
+
+/// Largest value of the 48-bit `unix_ts_ms` field.
+const UNIX_TS_MS_MAX: u64 = 0xFFFF_FFFF_FFFF;
+
+/// Largest value of the 12-bit `rand_a` field.
+const RAND_A_MAX: u64 = 0x0FFF;
+
+/// Largest value of the 62-bit `rand_b` field.
+const RAND_B_MAX: u64 = 0x3FFF_FFFF_FFFF_FFFF;
+
+/// The counter strategies of RFC 9562, section 6.2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MonotonicStrategy {
+    /// Method 1: a 12-bit counter in `rand_a`.
+    ///
+    /// The counter starts from a random value with its top bit cleared, which
+    /// leaves at least 2048 increments before it rolls over.
+    #[default]
+    FixedCounterRandA,
+
+    /// Method 2: `rand_b` is used as a counter that grows by a random amount
+    /// in `1..=max_increment` for each value within the same millisecond.
+    ///
+    /// The counter starts from a random value with its top bit cleared, and
+    /// `rand_a` keeps the random value drawn at the start of the millisecond.
+    RandomIncrementRandB { max_increment: u64 },
+
+    /// Method 3: `rand_a` holds the sub-millisecond fraction of the timestamp
+    /// in units of 1/4096 ms. Values that would not sort after the previous
+    /// one reuse its fraction plus one.
+    SubMillisecondRandA,
+}
+
+/// A stateful UUIDv7 generator that guarantees strictly increasing output.
+///
+/// When the counter of the current millisecond is exhausted, the timestamp
+/// is moved forward by one millisecond. If the clock goes backwards, the
+/// last timestamp keeps being used until the clock catches up.
+///
+/// The timestamp never passes 2^48-1 ms, late in the year 10889. Once the
+/// counter of that millisecond is exhausted, every further call fails with
+/// [`TimeError::Overflow`] instead of wrapping around to the start of the
+/// epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonotonicGenerator {
+    strategy: MonotonicStrategy,
+    /// The timestamp and counter of the last issued value.
+    last: Option<(u64, u64)>,
+    /// The random bits shared by all values of the current millisecond when
+    /// the counter lives in `rand_b`, so that `rand_a` stays fixed.
+    pinned_random: u128,
+}
+
+impl MonotonicGenerator {
+    pub fn new(strategy: MonotonicStrategy) -> Self {
+        Self {
+            strategy,
+            last: None,
+            pinned_random: 0,
+        }
+    }
+
+    /// Returns the configured strategy.
+    pub fn strategy(&self) -> MonotonicStrategy {
+        self.strategy
+    }
+
+    /// Picks the timestamp and counter for the next value.
+    fn advance(
+        &mut self,
+        since_epoch: Duration,
+        random_bytes: u128,
+    ) -> Result<(u64, u64), TimeError> {
+        let now_ms = since_epoch.as_millis().min(u128::from(UNIX_TS_MS_MAX)) as u64;
+
+        let next = match self.strategy {
+            MonotonicStrategy::FixedCounterRandA => {
+                let fresh = ((random_bytes >> 64) as u64) & (RAND_A_MAX >> 1);
+                match self.last {
+                    Some((last_ts, last_counter)) if now_ms <= last_ts => {
+                        if last_counter < RAND_A_MAX {
+                            (last_ts, last_counter + 1)
+                        } else if last_ts < UNIX_TS_MS_MAX {
+                            (last_ts + 1, fresh)
+                        } else {
+                            return Err(TimeError::Overflow);
+                        }
+                    }
+                    _ => (now_ms, fresh),
+                }
+            }
+
+            MonotonicStrategy::RandomIncrementRandB { max_increment } => {
+                let fresh = (random_bytes as u64) & (RAND_B_MAX >> 1);
+                match self.last {
+                    Some((last_ts, last_counter)) if now_ms <= last_ts => {
+                        let increment = 1 + ((random_bytes >> 64) as u64) % max_increment.max(1);
+                        match last_counter.checked_add(increment) {
+                            Some(counter) if counter <= RAND_B_MAX => (last_ts, counter),
+                            _ if last_ts < UNIX_TS_MS_MAX => {
+                                self.pinned_random = random_bytes;
+                                (last_ts + 1, fresh)
+                            }
+                            _ => return Err(TimeError::Overflow),
+                        }
+                    }
+                    _ => {
+                        self.pinned_random = random_bytes;
+                        (now_ms, fresh)
+                    }
+                }
+            }
+
+            MonotonicStrategy::SubMillisecondRandA => {
+                let fraction = u64::from(precision::nanos_to_fraction(since_epoch.subsec_nanos()));
+                match self.last {
+                    Some((last_ts, last_counter))
+                        if (now_ms, fraction) <= (last_ts, last_counter) =>
+                    {
+                        if last_counter < RAND_A_MAX {
+                            (last_ts, last_counter + 1)
+                        } else if last_ts < UNIX_TS_MS_MAX {
+                            (last_ts + 1, 0)
+                        } else {
+                            return Err(TimeError::Overflow);
+                        }
+                    }
+                    _ => (now_ms, fraction),
+                }
+            }
+        };
+
+        self.last = Some(next);
+        Ok(next)
+    }
+
+    /// Creates the next UUIDv7 `u128` value from the given time since the
+    /// Unix epoch and 128 bits of randomness.
+    ///
+    /// Fails once the counter of the largest timestamp is exhausted.
+    pub fn next_u128_from(
+        &mut self,
+        since_epoch: Duration,
+        random_bytes: u128,
+    ) -> Result<u128, Error> {
+        let (unix_ts_ms, counter) = self.advance(since_epoch, random_bytes)?;
+
+        Ok(match self.strategy {
+            MonotonicStrategy::FixedCounterRandA | MonotonicStrategy::SubMillisecondRandA => {
+                let seeds = UuidV7Seeds {
+                    unix_ts_ms,
+                    random_bytes,
+                };
+                seeds.to_u128_with_rand_a(counter as u16)
+            }
+            MonotonicStrategy::RandomIncrementRandB { .. } => {
+                let seeds = UuidV7Seeds {
+                    unix_ts_ms,
+                    random_bytes: self.pinned_random,
+                };
+                seeds.to_u128_with_rand_b(counter)
+            }
+        })
+    }
+
+    /// Creates the next UUIDv7 `u128` value from one reading of `clock` and
+    /// `random`.
+    pub fn next_u128_with(
+        &mut self,
+        mut clock: impl Clock,
+        mut random: impl RandomSource,
+    ) -> Result<u128, Error> {
+        let since_epoch = clock.now();
+        self.next_u128_from(since_epoch, random.next_u128())
+    }
+
+    /// Creates the next UUIDv7 `u128` value from the system clock and a UUIDv4.
+    #[cfg(feature = "std")]
+    pub fn next_u128(&mut self) -> Result<u128, Error> {
+        self.next_u128_with(SystemClock, SystemRandom)
+    }
+
+    /// Creates the next validated UUIDv7 from the system clock.
+    #[cfg(feature = "std")]
+    pub fn next_uuid_v7(&mut self) -> Result<UuidV7, Error> {
+        Ok(UuidV7(self.next_u128()?))
+    }
+
+    /// Creates the next UUIDv7 from the system clock as a `RawUuidV7Asn1`.
+    #[cfg(feature = "std")]
+    pub fn next_raw_uuid_v7_asn1(&mut self) -> Result<RawUuidV7Asn1, Error> {
+        RawUuidV7Asn1::try_from(self.next_u128()?)
+    }
+}
+
+impl Default for MonotonicGenerator {
+    fn default() -> Self {
+        Self::new(MonotonicStrategy::default())
+    }
+}
@@ -0,0 +1,374 @@
+//! Octet Encoding Rules (X.696) for `RawUuidV7` and the compact `UuidV7`.
+//!
+//! Both SEQUENCEs have no optional fields and no extension marker, so the
+//! encoding is just the fields in order:
+//!
+//! - the unconstrained INTEGERs are a length determinant followed by the
+//!   two's complement octets;
+//! - the fixed-size BIT STRINGs are their octets, left-aligned like in DER,
+//!   without a length.
+//!
+//! The encoder always produces canonical OER. The decoder checks the input
+//! according to [`OerRules`].
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Error;
+use crate::FieldError;
+use crate::RawUuidV7;
+use crate::RawUuidV7Field;
+use crate::UnverifiedUuidV7;
+use crate::UuidV7;
+
+/// Longest OER encoding of a `RawUuidV7`, reached with a timestamp of 2^47
+/// or more and a version of 128 or more.
+pub const RAW_UUID_V7_OER_MAX_LEN: usize = 22;
+
+/// Longest OER encoding of a compact `UuidV7` holding a valid UUIDv7.
+pub const UUID_V7_OER_MAX_LEN: usize = 18;
+
+/// Which encodings the decoder accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OerRules {
+    /// BASIC-OER: accept long-form lengths, redundant leading INTEGER octets
+    /// and non-zero unused bits, which are ignored.
+    Basic,
+    /// CANONICAL-OER: accept only the one encoding the encoder produces.
+    #[default]
+    Canonical,
+}
+
+/// Errors of the OER codec.
+///
+/// Offsets are relative to the start of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OerError {
+    /// The input ends before the value does.
+    Truncated { offset: usize },
+    /// The output buffer is shorter than the encoding.
+    BufferTooSmall { needed: usize, actual: usize },
+    /// A length determinant is zero or does not fit into `usize`.
+    InvalidLength { offset: usize },
+    /// A length determinant is not in its shortest form.
+    NonCanonicalLength { offset: usize },
+    /// An INTEGER has a redundant leading octet.
+    NonMinimalInteger {
+        field: RawUuidV7Field,
+        offset: usize,
+    },
+    /// An INTEGER that must not be negative is negative.
+    NegativeInteger {
+        field: RawUuidV7Field,
+        offset: usize,
+    },
+    /// An INTEGER does not fit into 64 bits.
+    IntegerOverflow {
+        field: RawUuidV7Field,
+        offset: usize,
+    },
+    /// The trailing unused bits of a BIT STRING are not zero.
+    NonZeroUnusedBits {
+        field: RawUuidV7Field,
+        offset: usize,
+    },
+    /// Octets follow the encoded value.
+    TrailingData { decoded: usize, remaining: usize },
+}
+
+impl fmt::Display for OerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated { offset } => {
+                write!(f, "offset {offset}: input ends in the middle of a value")
+            }
+            Self::BufferTooSmall { needed, actual } => write!(
+                f,
+                "output buffer of {actual} octets is too small, {needed} needed"
+            ),
+            Self::InvalidLength { offset } => {
+                write!(f, "offset {offset}: invalid length determinant")
+            }
+            Self::NonCanonicalLength { offset } => {
+                write!(f, "offset {offset}: length is not in its shortest form")
+            }
+            Self::NonMinimalInteger { field, offset } => write!(
+                f,
+                "offset {offset}: {}: INTEGER is not minimally encoded",
+                field.name()
+            ),
+            Self::NegativeInteger { field, offset } => {
+                write!(f, "offset {offset}: {}: INTEGER is negative", field.name())
+            }
+            Self::IntegerOverflow { field, offset } => write!(
+                f,
+                "offset {offset}: {}: INTEGER does not fit in 64 bits",
+                field.name()
+            ),
+            Self::NonZeroUnusedBits { field, offset } => write!(
+                f,
+                "offset {offset}: {}: unused bits are not zero",
+                field.name()
+            ),
+            Self::TrailingData { decoded, remaining } => write!(
+                f,
+                "{remaining} trailing octets after the {decoded} octets of the value"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for OerError {}
+
+/// Number of octets and unused bits of a fixed-size BIT STRING field.
+fn octets_and_unused(field: RawUuidV7Field) -> (usize, usize) {
+    let octets = field.bit_len().div_ceil(8);
+    (octets, octets * 8 - field.bit_len())
+}
+
+/// Appends octets to a stack buffer.
+struct Writer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Writer<N> {
+    fn new() -> Self {
+        Writer {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    fn octets(&mut self, octets: &[u8]) {
+        self.bytes[self.len..self.len + octets.len()].copy_from_slice(octets);
+        self.len += octets.len();
+    }
+
+    /// Writes a short-form length and the minimal two's complement octets.
+    fn integer(&mut self, value: u64) {
+        let octets = (u64::BITS - value.leading_zeros()) as usize / 8 + 1;
+        let bytes = u128::from(value).to_be_bytes();
+        self.octets(&[octets as u8]);
+        self.octets(&bytes[16 - octets..]);
+    }
+
+    fn bit_string(&mut self, value: u128, field: RawUuidV7Field) {
+        let (octets, unused) = octets_and_unused(field);
+        let bytes = (value << unused).to_be_bytes();
+        self.octets(&bytes[16 - octets..]);
+    }
+
+    /// Copies the encoding into `buf`.
+    fn finish(self, buf: &mut [u8]) -> Result<&[u8], OerError> {
+        let actual = buf.len();
+        let out = buf.get_mut(..self.len).ok_or(OerError::BufferTooSmall {
+            needed: self.len,
+            actual,
+        })?;
+        out.copy_from_slice(&self.bytes[..self.len]);
+        Ok(out)
+    }
+}
+
+/// Reads octets, checking them against the rules.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    rules: OerRules,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], rules: OerRules) -> Self {
+        Reader {
+            bytes,
+            offset: 0,
+            rules,
+        }
+    }
+
+    fn octets(&mut self, len: usize) -> Result<&'a [u8], OerError> {
+        let octets = self
+            .bytes
+            .get(self.offset..self.offset.saturating_add(len))
+            .ok_or(OerError::Truncated {
+                offset: self.offset,
+            })?;
+        self.offset += len;
+        Ok(octets)
+    }
+
+    /// Reports a canonical-OER violation unless the rules are BASIC-OER.
+    fn canonical(&self, violation: OerError) -> Result<(), OerError> {
+        match self.rules {
+            OerRules::Basic => Ok(()),
+            OerRules::Canonical => Err(violation),
+        }
+    }
+
+    /// Reads a length determinant in the short or the long form.
+    fn length(&mut self) -> Result<usize, OerError> {
+        let offset = self.offset;
+        let first = self.octets(1)?[0];
+        if first < 0x80 {
+            return Ok(usize::from(first));
+        }
+
+        let octets = self.octets(usize::from(first & 0x7F))?;
+        let significant = &octets[octets.iter().take_while(|b| **b == 0).count()..];
+        if octets.is_empty() || significant.len() > size_of::<usize>() {
+            return Err(OerError::InvalidLength { offset });
+        }
+        let length = significant
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | usize::from(*b));
+        if length < 0x80 || significant.len() != octets.len() {
+            self.canonical(OerError::NonCanonicalLength { offset })?;
+        }
+        Ok(length)
+    }
+
+    /// Reads a non-negative INTEGER of at most 64 bits.
+    fn integer(&mut self, field: RawUuidV7Field) -> Result<u64, OerError> {
+        let offset = self.offset;
+        let length = self.length()?;
+        if length == 0 {
+            return Err(OerError::InvalidLength { offset });
+        }
+        let octets = self.octets(length)?;
+
+        if octets[0] & 0x80 != 0 {
+            return Err(OerError::NegativeInteger { field, offset });
+        }
+        if let [0x00, second, ..] = octets
+            && second & 0x80 == 0
+        {
+            self.canonical(OerError::NonMinimalInteger { field, offset })?;
+        }
+        let significant = &octets[octets.iter().take_while(|b| **b == 0).count()..];
+        if significant.len() > 8 {
+            return Err(OerError::IntegerOverflow { field, offset });
+        }
+        Ok(significant
+            .iter()
+            .fold(0u64, |acc, b| (acc << 8) | u64::from(*b)))
+    }
+
+    fn bit_string(&mut self, field: RawUuidV7Field) -> Result<u128, OerError> {
+        let offset = self.offset;
+        let (octets, unused) = octets_and_unused(field);
+        let raw = self
+            .octets(octets)?
+            .iter()
+            .fold(0u128, |acc, b| (acc << 8) | u128::from(*b));
+        if raw & ((1 << unused) - 1) != 0 {
+            self.canonical(OerError::NonZeroUnusedBits { field, offset })?;
+        }
+        Ok(raw >> unused)
+    }
+
+    fn finish(self) -> Result<(), OerError> {
+        if self.offset < self.bytes.len() {
+            return Err(OerError::TrailingData {
+                decoded: self.offset,
+                remaining: self.bytes.len() - self.offset,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl RawUuidV7 {
+    /// Encodes the value as a canonical OER `RawUuidV7` into `buf` without
+    /// allocating.
+    ///
+    /// Returns the encoded bytes, which are a prefix of `buf`. A buffer of
+    /// [`RAW_UUID_V7_OER_MAX_LEN`] bytes is always large enough.
+    pub fn encode_oer<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, self.unix_ts_ms)?;
+        let rand_a = RawUuidV7::check_range(RawUuidV7Field::RandA, self.rand_a.into())?;
+        let variant = RawUuidV7::check_range(RawUuidV7Field::Variant, self.variant.into())?;
+        let rand_b = RawUuidV7::check_range(RawUuidV7Field::RandB, self.rand_b)?;
+
+        let mut writer = Writer::<RAW_UUID_V7_OER_MAX_LEN>::new();
+        writer.integer(unix_ts_ms);
+        writer.integer(self.version.into());
+        writer.bit_string(rand_a.into(), RawUuidV7Field::RandA);
+        writer.bit_string(variant.into(), RawUuidV7Field::Variant);
+        writer.bit_string(rand_b.into(), RawUuidV7Field::RandB);
+        Ok(writer.finish(buf)?)
+    }
+
+    /// Encodes the value as a canonical OER `RawUuidV7`.
+    #[cfg(feature = "alloc")]
+    pub fn to_oer_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut buf = [0u8; RAW_UUID_V7_OER_MAX_LEN];
+        Ok(self.encode_oer(&mut buf)?.to_vec())
+    }
+
+    /// Decodes a single OER `RawUuidV7` value; trailing octets are rejected.
+    pub fn decode_oer(oer_bytes: &[u8], rules: OerRules) -> Result<Self, Error> {
+        let mut reader = Reader::new(oer_bytes, rules);
+        let unix_ts_ms = reader.integer(RawUuidV7Field::UnixTsMs)?;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, unix_ts_ms)?;
+        let version = reader.integer(RawUuidV7Field::Version)?;
+        let version = u8::try_from(version).map_err(|_| FieldError::OutOfRange {
+            field: RawUuidV7Field::Version,
+            value: version,
+        })?;
+        let rand_a = reader.bit_string(RawUuidV7Field::RandA)?;
+        let variant = reader.bit_string(RawUuidV7Field::Variant)?;
+        let rand_b = reader.bit_string(RawUuidV7Field::RandB)?;
+        reader.finish()?;
+
+        Ok(RawUuidV7 {
+            unix_ts_ms,
+            version,
+            rand_a: rand_a as u16,
+            variant: variant as u8,
+            rand_b: rand_b as u64,
+        })
+    }
+}
+
+impl UuidV7 {
+    /// Encodes the value as a canonical OER compact `UuidV7` into `buf`
+    /// without allocating.
+    ///
+    /// Returns the encoded bytes, which are a prefix of `buf`. A buffer of
+    /// [`UUID_V7_OER_MAX_LEN`] bytes is always large enough.
+    pub fn encode_oer<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let unverified_uuid = UnverifiedUuidV7::from(*self);
+        let rand_ab =
+            (u128::from(unverified_uuid.rand_a()) << 62) | u128::from(unverified_uuid.rand_b());
+
+        let mut writer = Writer::<UUID_V7_OER_MAX_LEN>::new();
+        writer.integer(unverified_uuid.unix_ts_ms());
+        writer.bit_string(rand_ab, RawUuidV7Field::RandAb);
+        Ok(writer.finish(buf)?)
+    }
+
+    /// Encodes the value as a canonical OER compact `UuidV7`.
+    #[cfg(feature = "alloc")]
+    pub fn to_oer_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut buf = [0u8; UUID_V7_OER_MAX_LEN];
+        Ok(self.encode_oer(&mut buf)?.to_vec())
+    }
+
+    /// Decodes a single OER compact `UuidV7` value; trailing octets are
+    /// rejected.
+    pub fn decode_oer(oer_bytes: &[u8], rules: OerRules) -> Result<Self, Error> {
+        let mut reader = Reader::new(oer_bytes, rules);
+        let unix_ts_ms = reader.integer(RawUuidV7Field::UnixTsMs)?;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, unix_ts_ms)?;
+        let rand_ab = reader.bit_string(RawUuidV7Field::RandAb)?;
+        reader.finish()?;
+
+        let rand_a = rand_ab >> 62;
+        let rand_b = rand_ab & 0x3FFF_FFFF_FFFF_FFFF;
+        Ok(UuidV7(
+            ((unix_ts_ms as u128) << 80) | (7u128 << 76) | (rand_a << 64) | (2u128 << 62) | rand_b,
+        ))
+    }
+}
@@ -0,0 +1,223 @@
+//! PEM armoring (RFC 7468) and plain base64 for the DER types, enabled by
+//! the `pem` feature.
+//!
+//! | Type            | PEM label                      |
+//! |-----------------|--------------------------------|
+//! | `RawUuidV7Asn1` | [`RAW_UUID_V7_PEM_LABEL`]      |
+//! | `UuidV7Asn1`    | [`UUID_V7_PEM_LABEL`]          |
+//!
+//! Both types implement [`PemLabel`], so `der::EncodePem` and
+//! `der::DecodePem` work on them too; the latter always checks the label.
+//! The decoders here check it only with [`LabelPolicy::Strict`].
+//!
+//! A multi-record stream is a sequence of PEM documents. Text before, between
+//! and after the documents is ignored, as RFC 7468 allows explanatory text
+//! outside the encapsulation boundaries.
+//!
+//! Base64 is the standard alphabet with padding, without line breaks.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use base64ct::Base64;
+use base64ct::Encoding;
+use der::DecodeOwned;
+use der::Encode;
+use der::pem;
+use der::pem::LineEnding;
+use der::pem::PemLabel;
+
+use crate::Error;
+use crate::RawUuidV7Asn1;
+use crate::UuidV7Asn1;
+
+/// PEM label of a `RawUuidV7` value.
+pub const RAW_UUID_V7_PEM_LABEL: &str = "UUIDV7";
+
+/// PEM label of a compact `UuidV7` value.
+pub const UUID_V7_PEM_LABEL: &str = "COMPACT UUIDV7";
+
+const BEGIN: &str = "-----BEGIN ";
+const END: &str = "-----END ";
+
+/// How the decoders treat the PEM label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LabelPolicy {
+    /// Accept any well-formed label.
+    #[default]
+    Any,
+    /// Accept only the label of the decoded type.
+    Strict,
+}
+
+/// Errors of the PEM and base64 codecs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PemError {
+    /// The PEM text is malformed.
+    Pem(pem::Error),
+    /// The base64 text is malformed.
+    Base64(base64ct::Error),
+    /// The PEM label is not the one of the decoded type.
+    UnexpectedLabel { expected: &'static str },
+}
+
+impl fmt::Display for PemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pem(e) => write!(f, "malformed PEM: {e}"),
+            Self::Base64(e) => write!(f, "malformed base64: {e}"),
+            Self::UnexpectedLabel { expected } => {
+                write!(f, "unexpected PEM label, expected {expected:?}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PemError {}
+
+impl PemLabel for RawUuidV7Asn1 {
+    const PEM_LABEL: &'static str = RAW_UUID_V7_PEM_LABEL;
+}
+
+impl PemLabel for UuidV7Asn1 {
+    const PEM_LABEL: &'static str = UUID_V7_PEM_LABEL;
+}
+
+fn to_pem_string<T: Encode + PemLabel>(value: &T) -> Result<String, Error> {
+    let der_bytes = value.to_der()?;
+    pem::encode_string(T::PEM_LABEL, LineEnding::LF, &der_bytes)
+        .map_err(|e| PemError::Pem(e).into())
+}
+
+fn from_pem_str<T: DecodeOwned + PemLabel>(
+    pem_text: &str,
+    policy: LabelPolicy,
+) -> Result<T, Error> {
+    let (label, der_bytes) = pem::decode_vec(pem_text.as_bytes()).map_err(PemError::Pem)?;
+    if policy == LabelPolicy::Strict && label != T::PEM_LABEL {
+        return Err(PemError::UnexpectedLabel {
+            expected: T::PEM_LABEL,
+        }
+        .into());
+    }
+    Ok(T::from_der(&der_bytes)?)
+}
+
+fn to_base64<T: Encode>(value: &T) -> Result<String, Error> {
+    Ok(Base64::encode_string(&value.to_der()?))
+}
+
+fn from_base64<T: DecodeOwned>(text: &str) -> Result<T, Error> {
+    let der_bytes = Base64::decode_vec(text.trim()).map_err(PemError::Base64)?;
+    Ok(T::from_der(&der_bytes)?)
+}
+
+impl RawUuidV7Asn1 {
+    /// Encodes the value as a PEM document with LF line endings.
+    pub fn to_pem_string(&self) -> Result<String, Error> {
+        to_pem_string(self)
+    }
+
+    /// Decodes a single PEM document.
+    pub fn from_pem_str(pem_text: &str, policy: LabelPolicy) -> Result<Self, Error> {
+        from_pem_str(pem_text, policy)
+    }
+
+    /// Encodes the DER bytes as base64.
+    pub fn to_base64(&self) -> Result<String, Error> {
+        to_base64(self)
+    }
+
+    /// Decodes base64 DER bytes, ignoring surrounding whitespace.
+    pub fn from_base64(text: &str) -> Result<Self, Error> {
+        from_base64(text)
+    }
+}
+
+impl UuidV7Asn1 {
+    /// Encodes the value as a PEM document with LF line endings.
+    pub fn to_pem_string(&self) -> Result<String, Error> {
+        to_pem_string(self)
+    }
+
+    /// Decodes a single PEM document.
+    pub fn from_pem_str(pem_text: &str, policy: LabelPolicy) -> Result<Self, Error> {
+        from_pem_str(pem_text, policy)
+    }
+
+    /// Encodes the DER bytes as base64.
+    pub fn to_base64(&self) -> Result<String, Error> {
+        to_base64(self)
+    }
+
+    /// Decodes base64 DER bytes, ignoring surrounding whitespace.
+    pub fn from_base64(text: &str) -> Result<Self, Error> {
+        from_base64(text)
+    }
+}
+
+/// Encodes `values` as one PEM document after another.
+pub fn to_pem_stream<'a, T>(values: impl IntoIterator<Item = &'a T>) -> Result<String, Error>
+where
+    T: Encode + PemLabel + 'a,
+{
+    let mut out = String::new();
+    for value in values {
+        out.push_str(&to_pem_string(value)?);
+    }
+    Ok(out)
+}
+
+/// Iterates over the PEM documents of a multi-record stream. Created by
+/// [`pem_records`].
+///
+/// Stops after the first error.
+#[derive(Debug, Clone)]
+pub struct PemRecords<'a, T> {
+    rest: &'a str,
+    policy: LabelPolicy,
+    record: PhantomData<fn() -> T>,
+}
+
+/// Returns an iterator over the PEM documents in `text`, decoding each as
+/// `T`.
+pub fn pem_records<T>(text: &str, policy: LabelPolicy) -> PemRecords<'_, T> {
+    PemRecords {
+        rest: text,
+        policy,
+        record: PhantomData,
+    }
+}
+
+impl<T: DecodeOwned + PemLabel> Iterator for PemRecords<'_, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let begin = self.rest.find(BEGIN)?;
+        let document = &self.rest[begin..];
+        let Some(end) = document.find(END) else {
+            self.rest = "";
+            return Some(Err(
+                PemError::Pem(pem::Error::PostEncapsulationBoundary).into()
+            ));
+        };
+        let end = document[end..]
+            .find('\n')
+            .map_or(document.len(), |line_end| end + line_end + 1);
+
+        let (document, rest) = document.split_at(end);
+        let record = from_pem_str(document, self.policy);
+        self.rest = if record.is_ok() { rest } else { "" };
+        Some(record)
+    }
+}
+
+/// Decodes all PEM documents in `text`.
+pub fn from_pem_stream<T: DecodeOwned + PemLabel>(
+    text: &str,
+    policy: LabelPolicy,
+) -> Result<Vec<T>, Error> {
+    pem_records(text, policy).collect()
+}
@@ -0,0 +1,366 @@
+//! Packed Encoding Rules (X.691) for `RawUuidV7` and the compact `UuidV7`.
+//!
+//! Both SEQUENCEs have no optional fields and no extension marker, so the
+//! encoding is just the fields in order:
+//!
+//! - the unconstrained INTEGERs are a one-octet length followed by the
+//!   minimal two's complement octets;
+//! - the fixed-size BIT STRINGs are their bits, without a length.
+//!
+//! The [`PerVariant::Aligned`] variant additionally aligns the INTEGERs and
+//! the BIT STRINGs of more than 16 bits to octet boundaries. With a current
+//! timestamp, a `RawUuidV7` takes 19 octets in either variant and the
+//! compact `UuidV7` 17 octets; the DER encodings take 33 and 23.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Error;
+use crate::FieldError;
+use crate::RawUuidV7;
+use crate::RawUuidV7Field;
+use crate::UnverifiedUuidV7;
+use crate::UuidV7;
+
+/// Longest PER encoding of a `RawUuidV7`, reached with a timestamp of 2^47
+/// or more and a version of 128 or more.
+pub const RAW_UUID_V7_PER_MAX_LEN: usize = 21;
+
+/// Longest PER encoding of a compact `UuidV7` holding a valid UUIDv7.
+pub const UUID_V7_PER_MAX_LEN: usize = 18;
+
+/// The two variants of PER.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PerVariant {
+    /// UNALIGNED PER (UPER): fields are packed without padding.
+    #[default]
+    Unaligned,
+    /// ALIGNED PER: INTEGERs and long BIT STRINGs start on octet boundaries.
+    Aligned,
+}
+
+/// Errors of the PER codec.
+///
+/// Bit offsets are relative to the start of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerError {
+    /// The input ends before the value does.
+    Truncated { bit_offset: usize },
+    /// The output buffer is shorter than the encoding.
+    BufferTooSmall { needed: usize, actual: usize },
+    /// An INTEGER length determinant is zero or not in the one-octet form.
+    InvalidLength { bit_offset: usize },
+    /// An INTEGER has a redundant leading octet.
+    NonMinimalInteger {
+        field: RawUuidV7Field,
+        bit_offset: usize,
+    },
+    /// An INTEGER that must not be negative is negative.
+    NegativeInteger {
+        field: RawUuidV7Field,
+        bit_offset: usize,
+    },
+    /// An INTEGER does not fit into 64 bits.
+    IntegerOverflow {
+        field: RawUuidV7Field,
+        bit_offset: usize,
+    },
+    /// Padding bits before an aligned field or at the end are not zero.
+    NonZeroPadding { bit_offset: usize },
+    /// Octets follow the encoded value.
+    TrailingData { decoded: usize, remaining: usize },
+}
+
+impl fmt::Display for PerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated { bit_offset } => {
+                write!(f, "bit {bit_offset}: input ends in the middle of a value")
+            }
+            Self::BufferTooSmall { needed, actual } => write!(
+                f,
+                "output buffer of {actual} octets is too small, {needed} needed"
+            ),
+            Self::InvalidLength { bit_offset } => {
+                write!(f, "bit {bit_offset}: invalid INTEGER length determinant")
+            }
+            Self::NonMinimalInteger { field, bit_offset } => write!(
+                f,
+                "bit {bit_offset}: {}: INTEGER is not minimally encoded",
+                field.name()
+            ),
+            Self::NegativeInteger { field, bit_offset } => {
+                write!(f, "bit {bit_offset}: {}: INTEGER is negative", field.name())
+            }
+            Self::IntegerOverflow { field, bit_offset } => write!(
+                f,
+                "bit {bit_offset}: {}: INTEGER does not fit in 64 bits",
+                field.name()
+            ),
+            Self::NonZeroPadding { bit_offset } => {
+                write!(f, "bit {bit_offset}: padding bits are not zero")
+            }
+            Self::TrailingData { decoded, remaining } => write!(
+                f,
+                "{remaining} trailing octets after the {decoded} octets of the value"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for PerError {}
+
+/// Writes bits MSB-first into a zeroed buffer.
+struct BitWriter<const N: usize> {
+    bytes: [u8; N],
+    bit_len: usize,
+    variant: PerVariant,
+}
+
+impl<const N: usize> BitWriter<N> {
+    fn new(variant: PerVariant) -> Self {
+        BitWriter {
+            bytes: [0; N],
+            bit_len: 0,
+            variant,
+        }
+    }
+
+    /// Writes the low `bit_len` bits of `value`.
+    fn bits(&mut self, value: u128, bit_len: usize) {
+        for i in (0..bit_len).rev() {
+            if (value >> i) & 1 != 0 {
+                self.bytes[self.bit_len / 8] |= 0x80 >> (self.bit_len % 8);
+            }
+            self.bit_len += 1;
+        }
+    }
+
+    /// Pads to the next octet boundary in the aligned variant.
+    fn align(&mut self) {
+        if self.variant == PerVariant::Aligned {
+            self.bit_len = self.bit_len.next_multiple_of(8);
+        }
+    }
+
+    fn integer(&mut self, value: u64) {
+        let octets = (u64::BITS - value.leading_zeros()) as usize / 8 + 1;
+        self.align();
+        self.bits(octets as u128, 8);
+        self.bits(value.into(), octets * 8);
+    }
+
+    fn bit_string(&mut self, value: u128, field: RawUuidV7Field) {
+        if field.bit_len() > 16 {
+            self.align();
+        }
+        self.bits(value, field.bit_len());
+    }
+
+    /// Copies the encoding, padded to whole octets, into `buf`.
+    fn finish(self, buf: &mut [u8]) -> Result<&[u8], PerError> {
+        let len = self.bit_len.div_ceil(8);
+        let actual = buf.len();
+        let out = buf.get_mut(..len).ok_or(PerError::BufferTooSmall {
+            needed: len,
+            actual,
+        })?;
+        out.copy_from_slice(&self.bytes[..len]);
+        Ok(out)
+    }
+}
+
+/// Reads bits MSB-first.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit_offset: usize,
+    variant: PerVariant,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8], variant: PerVariant) -> Self {
+        BitReader {
+            bytes,
+            bit_offset: 0,
+            variant,
+        }
+    }
+
+    fn bits(&mut self, bit_len: usize) -> Result<u128, PerError> {
+        if self.bit_offset + bit_len > self.bytes.len() * 8 {
+            return Err(PerError::Truncated {
+                bit_offset: self.bit_offset,
+            });
+        }
+        let mut value = 0u128;
+        for _ in 0..bit_len {
+            let bit = (self.bytes[self.bit_offset / 8] >> (7 - self.bit_offset % 8)) & 1;
+            value = (value << 1) | u128::from(bit);
+            self.bit_offset += 1;
+        }
+        Ok(value)
+    }
+
+    /// Skips the zero padding bits up to the next octet boundary.
+    fn pad(&mut self) -> Result<(), PerError> {
+        let bit_offset = self.bit_offset;
+        let padding = bit_offset.next_multiple_of(8) - bit_offset;
+        if self.bits(padding)? != 0 {
+            return Err(PerError::NonZeroPadding { bit_offset });
+        }
+        Ok(())
+    }
+
+    fn align(&mut self) -> Result<(), PerError> {
+        if self.variant == PerVariant::Aligned {
+            self.pad()?;
+        }
+        Ok(())
+    }
+
+    /// Reads a non-negative INTEGER of at most 64 bits.
+    fn integer(&mut self, field: RawUuidV7Field) -> Result<u64, PerError> {
+        self.align()?;
+        let bit_offset = self.bit_offset;
+        let octets = self.bits(8)? as usize;
+        if octets == 0 || octets >= 0x80 {
+            return Err(PerError::InvalidLength { bit_offset });
+        }
+        if octets > 9 {
+            return Err(PerError::IntegerOverflow { field, bit_offset });
+        }
+
+        let value = self.bits(octets * 8)?;
+        let first = value >> ((octets - 1) * 8);
+        let second_msb = octets > 1 && (value >> ((octets - 2) * 8)) & 0x80 != 0;
+        if first & 0x80 != 0 {
+            return Err(PerError::NegativeInteger { field, bit_offset });
+        }
+        if first == 0 && octets > 1 && !second_msb {
+            return Err(PerError::NonMinimalInteger { field, bit_offset });
+        }
+        u64::try_from(value).map_err(|_| PerError::IntegerOverflow { field, bit_offset })
+    }
+
+    fn bit_string(&mut self, field: RawUuidV7Field) -> Result<u128, PerError> {
+        if field.bit_len() > 16 {
+            self.align()?;
+        }
+        self.bits(field.bit_len())
+    }
+
+    /// Checks that only the zero padding of the last octet is left.
+    fn finish(mut self) -> Result<(), PerError> {
+        self.pad()?;
+        let decoded = self.bit_offset.div_ceil(8);
+        if decoded < self.bytes.len() {
+            return Err(PerError::TrailingData {
+                decoded,
+                remaining: self.bytes.len() - decoded,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl RawUuidV7 {
+    /// Encodes the value as a PER `RawUuidV7` into `buf` without allocating.
+    ///
+    /// Returns the encoded bytes, which are a prefix of `buf`. A buffer of
+    /// [`RAW_UUID_V7_PER_MAX_LEN`] bytes is always large enough.
+    pub fn encode_per<'a>(
+        &self,
+        variant: PerVariant,
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, self.unix_ts_ms)?;
+        let rand_a = RawUuidV7::check_range(RawUuidV7Field::RandA, self.rand_a.into())?;
+        let variant_bits = RawUuidV7::check_range(RawUuidV7Field::Variant, self.variant.into())?;
+        let rand_b = RawUuidV7::check_range(RawUuidV7Field::RandB, self.rand_b)?;
+
+        let mut writer = BitWriter::<RAW_UUID_V7_PER_MAX_LEN>::new(variant);
+        writer.integer(unix_ts_ms);
+        writer.integer(self.version.into());
+        writer.bit_string(rand_a.into(), RawUuidV7Field::RandA);
+        writer.bit_string(variant_bits.into(), RawUuidV7Field::Variant);
+        writer.bit_string(rand_b.into(), RawUuidV7Field::RandB);
+        Ok(writer.finish(buf)?)
+    }
+
+    /// Encodes the value as a PER `RawUuidV7`.
+    #[cfg(feature = "alloc")]
+    pub fn to_per_bytes(&self, variant: PerVariant) -> Result<Vec<u8>, Error> {
+        let mut buf = [0u8; RAW_UUID_V7_PER_MAX_LEN];
+        Ok(self.encode_per(variant, &mut buf)?.to_vec())
+    }
+
+    /// Decodes a single PER `RawUuidV7` value; trailing octets are rejected.
+    pub fn decode_per(per_bytes: &[u8], variant: PerVariant) -> Result<Self, Error> {
+        let mut reader = BitReader::new(per_bytes, variant);
+        let unix_ts_ms = reader.integer(RawUuidV7Field::UnixTsMs)?;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, unix_ts_ms)?;
+        let version = reader.integer(RawUuidV7Field::Version)?;
+        let version = u8::try_from(version).map_err(|_| FieldError::OutOfRange {
+            field: RawUuidV7Field::Version,
+            value: version,
+        })?;
+        let rand_a = reader.bit_string(RawUuidV7Field::RandA)?;
+        let variant_bits = reader.bit_string(RawUuidV7Field::Variant)?;
+        let rand_b = reader.bit_string(RawUuidV7Field::RandB)?;
+        reader.finish()?;
+
+        Ok(RawUuidV7 {
+            unix_ts_ms,
+            version,
+            rand_a: rand_a as u16,
+            variant: variant_bits as u8,
+            rand_b: rand_b as u64,
+        })
+    }
+}
+
+impl UuidV7 {
+    /// Encodes the value as a compact PER `UuidV7` into `buf` without
+    /// allocating.
+    ///
+    /// Returns the encoded bytes, which are a prefix of `buf`. A buffer of
+    /// [`UUID_V7_PER_MAX_LEN`] bytes is always large enough.
+    pub fn encode_per<'a>(
+        &self,
+        variant: PerVariant,
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let unverified_uuid = UnverifiedUuidV7::from(*self);
+        let rand_ab =
+            (u128::from(unverified_uuid.rand_a()) << 62) | u128::from(unverified_uuid.rand_b());
+
+        let mut writer = BitWriter::<UUID_V7_PER_MAX_LEN>::new(variant);
+        writer.integer(unverified_uuid.unix_ts_ms());
+        writer.bit_string(rand_ab, RawUuidV7Field::RandAb);
+        Ok(writer.finish(buf)?)
+    }
+
+    /// Encodes the value as a compact PER `UuidV7`.
+    #[cfg(feature = "alloc")]
+    pub fn to_per_bytes(&self, variant: PerVariant) -> Result<Vec<u8>, Error> {
+        let mut buf = [0u8; UUID_V7_PER_MAX_LEN];
+        Ok(self.encode_per(variant, &mut buf)?.to_vec())
+    }
+
+    /// Decodes a single compact PER `UuidV7` value; trailing octets are
+    /// rejected.
+    pub fn decode_per(per_bytes: &[u8], variant: PerVariant) -> Result<Self, Error> {
+        let mut reader = BitReader::new(per_bytes, variant);
+        let unix_ts_ms = reader.integer(RawUuidV7Field::UnixTsMs)?;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, unix_ts_ms)?;
+        let rand_ab = reader.bit_string(RawUuidV7Field::RandAb)?;
+        reader.finish()?;
+
+        let rand_a = rand_ab >> 62;
+        let rand_b = rand_ab & 0x3FFF_FFFF_FFFF_FFFF;
+        Ok(UuidV7(
+            ((unix_ts_ms as u128) << 80) | (7u128 << 76) | (rand_a << 64) | (2u128 << 62) | rand_b,
+        ))
+    }
+}
@@ -0,0 +1,150 @@
+//! Sub-millisecond timestamp precision (RFC 9562, section 6.2, method 3).
+//!
+//! The 12-bit `rand_a` field can carry the fraction of the current
+//! millisecond in units of 1/4096 ms, which is about 244 ns. The conversions
+//! here are chosen so that `fraction -> nanos -> fraction` is lossless.
+
+use core::fmt;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+use uuid::Timestamp;
+
+use crate::RawUuidV7;
+use crate::UnverifiedUuidV7;
+use crate::UuidV7Seeds;
+
+/// Number of `rand_a` bits used for the sub-millisecond fraction.
+pub const SUB_MS_FRACTION_BITS: u32 = 12;
+
+const NANOS_PER_MS: u64 = 1_000_000;
+
+/// Largest Unix timestamp in milliseconds that fits into 48 bits.
+const UNIX_TS_MS_MAX: u128 = 0xFFFF_FFFF_FFFF;
+
+/// Converts the nanoseconds within a millisecond into a 12-bit fraction.
+///
+/// Values of one millisecond or more are reduced modulo one millisecond.
+pub fn nanos_to_fraction(sub_ms_nanos: u32) -> u16 {
+    let nanos = u64::from(sub_ms_nanos) % NANOS_PER_MS;
+    ((nanos << SUB_MS_FRACTION_BITS) / NANOS_PER_MS) as u16
+}
+
+/// Converts a 12-bit fraction back into nanoseconds within the millisecond.
+///
+/// Rounds up, so that [`nanos_to_fraction`] returns the same fraction.
+pub fn fraction_to_nanos(fraction: u16) -> u32 {
+    let fraction = u64::from(fraction & 0x0FFF);
+    (fraction * NANOS_PER_MS).div_ceil(1 << SUB_MS_FRACTION_BITS) as u32
+}
+
+/// Error type for times that cannot be stored in a UUIDv7 timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeError {
+    /// The time is before the Unix epoch.
+    BeforeUnixEpoch,
+    /// The time in milliseconds does not fit into 48 bits.
+    Overflow,
+}
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BeforeUnixEpoch => write!(f, "time is before the Unix epoch"),
+            Self::Overflow => write!(f, "time does not fit into a 48-bit millisecond timestamp"),
+        }
+    }
+}
+
+impl core::error::Error for TimeError {}
+
+impl UuidV7Seeds {
+    /// Creates seeds whose `rand_a` part carries the sub-millisecond fraction
+    /// of `since_epoch`.
+    ///
+    /// The rest of `random_bytes` is kept as is.
+    pub fn from_duration_since_epoch(
+        since_epoch: Duration,
+        random_bytes: u128,
+    ) -> Result<Self, TimeError> {
+        let unix_ts_ms = since_epoch.as_millis();
+        if unix_ts_ms > UNIX_TS_MS_MAX {
+            return Err(TimeError::Overflow);
+        }
+
+        let fraction = nanos_to_fraction(since_epoch.subsec_nanos());
+        let random_bytes = (random_bytes & 0xFFFF_FFFF_FFFF_F000_FFFF_FFFF_FFFF_FFFF)
+            | (u128::from(fraction) << 64);
+
+        Ok(UuidV7Seeds {
+            unix_ts_ms: unix_ts_ms as u64,
+            random_bytes,
+        })
+    }
+
+    /// Creates seeds with sub-millisecond precision from a `SystemTime`.
+    #[cfg(feature = "std")]
+    pub fn from_system_time(time: SystemTime, random_bytes: u128) -> Result<Self, TimeError> {
+        let since_epoch = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_err(|_| TimeError::BeforeUnixEpoch)?;
+        Self::from_duration_since_epoch(since_epoch, random_bytes)
+    }
+
+    /// Creates seeds with sub-millisecond precision from a `uuid::Timestamp`.
+    pub fn from_timestamp(timestamp: &Timestamp, random_bytes: u128) -> Result<Self, TimeError> {
+        let (seconds, nanos) = timestamp.to_unix();
+        let since_epoch = Duration::new(seconds, nanos);
+        Self::from_duration_since_epoch(since_epoch, random_bytes)
+    }
+}
+
+/// Combines the millisecond timestamp and the `rand_a` fraction.
+fn to_duration(unix_ts_ms: u64, rand_a: u16) -> Duration {
+    Duration::from_millis(unix_ts_ms) + Duration::from_nanos(fraction_to_nanos(rand_a).into())
+}
+
+impl UnverifiedUuidV7 {
+    /// Returns the nanoseconds within the millisecond stored in `rand_a`.
+    ///
+    /// Only meaningful for UUIDs created with sub-millisecond precision.
+    pub fn sub_ms_nanos(&self) -> u32 {
+        fraction_to_nanos(self.rand_a())
+    }
+
+    /// Returns the full-precision time since the Unix epoch, reading `rand_a`
+    /// as a sub-millisecond fraction.
+    pub fn to_duration_since_epoch(&self) -> Duration {
+        to_duration(self.unix_ts_ms(), self.rand_a())
+    }
+
+    /// Returns the full-precision creation time, reading `rand_a` as a
+    /// sub-millisecond fraction.
+    #[cfg(feature = "std")]
+    pub fn to_system_time(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + self.to_duration_since_epoch()
+    }
+}
+
+impl RawUuidV7 {
+    /// Returns the nanoseconds within the millisecond stored in `rand_a`.
+    ///
+    /// Only meaningful for UUIDs created with sub-millisecond precision.
+    pub fn sub_ms_nanos(&self) -> u32 {
+        fraction_to_nanos(self.rand_a)
+    }
+
+    /// Returns the full-precision time since the Unix epoch, reading `rand_a`
+    /// as a sub-millisecond fraction.
+    pub fn to_duration_since_epoch(&self) -> Duration {
+        to_duration(self.unix_ts_ms, self.rand_a)
+    }
+
+    /// Returns the full-precision creation time, reading `rand_a` as a
+    /// sub-millisecond fraction.
+    #[cfg(feature = "std")]
+    pub fn to_system_time(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + self.to_duration_since_epoch()
+    }
+}
@@ -0,0 +1,83 @@
+//! Time-range bounds for key-range scans over UUIDv7s.
+//!
+//! A UUIDv7 starts with its 48-bit timestamp, so all IDs created within a
+//! span of milliseconds form one contiguous range, both as `u128` values and
+//! in byte order. The same holds for the DER encoding of `RawUuidV7` as long
+//! as the version is 7: the timestamp is the only variable-length field, and
+//! a longer timestamp also makes the SEQUENCE longer, so the encodings sort
+//! by the SEQUENCE length octet first and then by content.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+#[cfg(feature = "alloc")]
+use crate::Error;
+use crate::FieldError;
+use crate::RawUuidV7;
+#[cfg(feature = "alloc")]
+use crate::RawUuidV7Asn1;
+use crate::RawUuidV7Field;
+use crate::UuidV7;
+
+/// Version and variant bits of a UUIDv7 with all random bits cleared.
+const VERSION_AND_VARIANT: u128 = (0x7 << 76) | (0x2 << 62);
+
+/// All `rand_a` and `rand_b` bits.
+const RAND_BITS: u128 = (0x0FFF << 64) | 0x3FFF_FFFF_FFFF_FFFF;
+
+impl UuidV7 {
+    /// Returns the smallest UUIDv7 with the given timestamp, with all random
+    /// bits cleared.
+    pub fn min_for_unix_ts_ms(unix_ts_ms: u64) -> Result<Self, FieldError> {
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, unix_ts_ms)?;
+        Ok(UuidV7((u128::from(unix_ts_ms) << 80) | VERSION_AND_VARIANT))
+    }
+
+    /// Returns the largest UUIDv7 with the given timestamp, with all random
+    /// bits set.
+    pub fn max_for_unix_ts_ms(unix_ts_ms: u64) -> Result<Self, FieldError> {
+        let min = Self::min_for_unix_ts_ms(unix_ts_ms)?;
+        Ok(UuidV7(min.0 | RAND_BITS))
+    }
+
+    /// Returns the range of all UUIDv7s created from `start_ms` to `end_ms`,
+    /// both inclusive.
+    ///
+    /// The range is empty if `end_ms` is before `start_ms`.
+    pub fn range_for_unix_ts_ms(
+        start_ms: u64,
+        end_ms: u64,
+    ) -> Result<RangeInclusive<Self>, FieldError> {
+        Ok(Self::min_for_unix_ts_ms(start_ms)?..=Self::max_for_unix_ts_ms(end_ms)?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl RawUuidV7Asn1 {
+    /// Returns the `RawUuidV7` value of [`UuidV7::min_for_unix_ts_ms`].
+    pub fn min_for_unix_ts_ms(unix_ts_ms: u64) -> Result<Self, Error> {
+        let uuid_v7 = UuidV7::min_for_unix_ts_ms(unix_ts_ms)?;
+        RawUuidV7::from(uuid_v7).try_into()
+    }
+
+    /// Returns the `RawUuidV7` value of [`UuidV7::max_for_unix_ts_ms`].
+    pub fn max_for_unix_ts_ms(unix_ts_ms: u64) -> Result<Self, Error> {
+        let uuid_v7 = UuidV7::max_for_unix_ts_ms(unix_ts_ms)?;
+        RawUuidV7::from(uuid_v7).try_into()
+    }
+
+    /// Returns the DER encodings of the smallest and largest `RawUuidV7`
+    /// created from `start_ms` to `end_ms`, both inclusive.
+    ///
+    /// Compared byte by byte, the DER encoding of every UUIDv7 in the time
+    /// range lies within these bounds.
+    pub fn der_range_for_unix_ts_ms(
+        start_ms: u64,
+        end_ms: u64,
+    ) -> Result<RangeInclusive<Vec<u8>>, Error> {
+        let start = Self::min_for_unix_ts_ms(start_ms)?.to_der_bytes()?;
+        let end = Self::max_for_unix_ts_ms(end_ms)?.to_der_bytes()?;
+        Ok(start..=end)
+    }
+}
@@ -0,0 +1,199 @@
+//! Protection against a wall clock that steps backwards.
+//!
+//! When NTP moves the clock back, a generator that trusts each reading issues
+//! values that sort before ones it already issued. [`GuardedGenerator`]
+//! remembers the latest reading, notices backward steps and handles them by
+//! its [`RegressionPolicy`]:
+//!
+//! - [`RegressionPolicy::Reuse`] keeps the latest timestamp and lets the
+//!   [`MonotonicGenerator`] count up from there;
+//! - [`RegressionPolicy::Block`] waits through [`Clock::sleep`] until the
+//!   clock is back at the latest reading, if that takes at most `max_wait`;
+//! - [`RegressionPolicy::Reject`] returns a [`ClockRegression`] error.
+//!
+//! [`RegressionMetrics`] counts how often each policy was applied.
+
+use core::fmt;
+use core::time::Duration;
+
+use crate::Error;
+use crate::MonotonicGenerator;
+use crate::MonotonicStrategy;
+#[cfg(feature = "alloc")]
+use crate::RawUuidV7Asn1;
+use crate::UuidV7;
+use crate::source::Clock;
+use crate::source::RandomSource;
+#[cfg(feature = "std")]
+use crate::source::SystemClock;
+#[cfg(feature = "std")]
+use crate::source::SystemRandom;
+
+/// What [`GuardedGenerator`] does when the clock reads earlier than before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RegressionPolicy {
+    /// Keep using the latest timestamp and increment the counter.
+    #[default]
+    Reuse,
+    /// Wait until the clock catches up. Steps larger than `max_wait`, and
+    /// clocks that do not catch up within it, are rejected.
+    Block { max_wait: Duration },
+    /// Return an error.
+    Reject,
+}
+
+/// How often the clock stepped backwards and how each step was handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RegressionMetrics {
+    /// Number of backward steps seen.
+    pub regressions: u64,
+    /// Steps handled by reusing the latest timestamp.
+    pub reused: u64,
+    /// Steps handled by waiting for the clock.
+    pub blocked: u64,
+    /// Steps returned as errors.
+    pub rejected: u64,
+    /// Total time spent waiting for the clock.
+    pub waited: Duration,
+    /// The largest backward step.
+    pub largest_step: Duration,
+}
+
+/// The clock read earlier than a previous reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockRegression {
+    /// The latest reading before the step.
+    pub last: Duration,
+    /// The reading that went backwards.
+    pub now: Duration,
+}
+
+impl ClockRegression {
+    /// Returns how far the clock went backwards.
+    pub fn step(&self) -> Duration {
+        self.last.saturating_sub(self.now)
+    }
+}
+
+impl fmt::Display for ClockRegression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "clock moved back by {:?}", self.step())
+    }
+}
+
+impl core::error::Error for ClockRegression {}
+
+/// A [`MonotonicGenerator`] over a clock and a random source that notices
+/// backward clock steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardedGenerator<C, R> {
+    generator: MonotonicGenerator,
+    clock: C,
+    random: R,
+    policy: RegressionPolicy,
+    /// The latest clock reading.
+    last: Option<Duration>,
+    metrics: RegressionMetrics,
+}
+
+impl<C: Clock, R: RandomSource> GuardedGenerator<C, R> {
+    pub fn new(strategy: MonotonicStrategy, policy: RegressionPolicy, clock: C, random: R) -> Self {
+        Self {
+            generator: MonotonicGenerator::new(strategy),
+            clock,
+            random,
+            policy,
+            last: None,
+            metrics: RegressionMetrics::default(),
+        }
+    }
+
+    /// Returns the configured policy.
+    pub fn policy(&self) -> RegressionPolicy {
+        self.policy
+    }
+
+    /// Returns the metrics collected so far.
+    pub fn metrics(&self) -> RegressionMetrics {
+        self.metrics
+    }
+
+    /// Resets the metrics to zero.
+    pub fn reset_metrics(&mut self) {
+        self.metrics = RegressionMetrics::default();
+    }
+
+    /// Reads the clock and applies the policy to a backward step.
+    fn read_clock(&mut self) -> Result<Duration, ClockRegression> {
+        let now = self.clock.now();
+        let Some(last) = self.last.filter(|last| now < *last) else {
+            self.last = Some(now);
+            return Ok(now);
+        };
+
+        let regression = ClockRegression { last, now };
+        self.metrics.regressions += 1;
+        self.metrics.largest_step = self.metrics.largest_step.max(regression.step());
+
+        match self.policy {
+            RegressionPolicy::Reuse => {
+                self.metrics.reused += 1;
+                Ok(last)
+            }
+            RegressionPolicy::Block { max_wait } => {
+                let mut now = now;
+                let mut waited = Duration::ZERO;
+                while now < last {
+                    let behind = last - now;
+                    if waited + behind > max_wait {
+                        self.metrics.waited += waited;
+                        self.metrics.rejected += 1;
+                        return Err(ClockRegression { last, now });
+                    }
+                    self.clock.sleep(behind);
+                    waited += behind;
+                    now = self.clock.now();
+                }
+                self.metrics.waited += waited;
+                self.metrics.blocked += 1;
+                self.last = Some(now);
+                Ok(now)
+            }
+            RegressionPolicy::Reject => {
+                self.metrics.rejected += 1;
+                Err(regression)
+            }
+        }
+    }
+
+    /// Creates the next UUIDv7 `u128` value.
+    pub fn next_u128(&mut self) -> Result<u128, Error> {
+        let since_epoch = self.read_clock()?;
+        let random_bytes = self.random.next_u128();
+        self.generator.next_u128_from(since_epoch, random_bytes)
+    }
+
+    /// Creates the next validated UUIDv7.
+    pub fn next_uuid_v7(&mut self) -> Result<UuidV7, Error> {
+        Ok(UuidV7(self.next_u128()?))
+    }
+
+    /// Creates the next UUIDv7 as a `RawUuidV7Asn1`.
+    #[cfg(feature = "alloc")]
+    pub fn next_raw_uuid_v7_asn1(&mut self) -> Result<RawUuidV7Asn1, Error> {
+        RawUuidV7Asn1::try_from(self.next_u128()?)
+    }
+
+    /// Returns the clock and the random source.
+    pub fn into_parts(self) -> (C, R) {
+        (self.clock, self.random)
+    }
+}
+
+#[cfg(feature = "std")]
+impl GuardedGenerator<SystemClock, SystemRandom> {
+    /// Creates a generator over the system clock and UUIDv4 randomness.
+    pub fn system(strategy: MonotonicStrategy, policy: RegressionPolicy) -> Self {
+        Self::new(strategy, policy, SystemClock, SystemRandom)
+    }
+}
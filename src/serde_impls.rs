@@ -0,0 +1,187 @@
+//! `serde` support, enabled by the `serde` feature.
+//!
+//! - `UuidV7` and `UnverifiedUuidV7` serialize as the canonical hyphenated
+//!   string in human-readable formats and as 16 big-endian bytes otherwise.
+//!   Any of the [`TextFormat`](crate::TextFormat)s is accepted when parsing.
+//! - `RawUuidV7` and `UuidV7Seeds` serialize as structs of their fields.
+//! - `RawUuidV7Asn1` and `UuidV7Asn1` serialize as their DER bytes, encoded
+//!   as base64 in human-readable formats.
+//!
+//! Deserializing a `UuidV7` validates the version and variant bits through
+//! `TryFrom<UnverifiedUuidV7> for UuidV7`.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use base64ct::Base64;
+use base64ct::Encoding;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serde::de;
+
+use crate::RAW_UUID_V7_DER_MAX_LEN;
+use crate::RawUuidV7Asn1;
+use crate::UnverifiedUuidV7;
+use crate::UuidV7;
+use crate::UuidV7Asn1;
+
+fn serialize_u128<S: Serializer>(value: u128, serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.collect_str(&UnverifiedUuidV7(value))
+    } else {
+        serializer.serialize_bytes(&value.to_be_bytes())
+    }
+}
+
+/// Accepts a UUID string, 16 bytes, or a sequence of 16 bytes.
+struct U128Visitor;
+
+impl<'de> de::Visitor<'de> for U128Visitor {
+    type Value = u128;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a UUID string or 16 bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        UnverifiedUuidV7::parse_str(v)
+            .map(u128::from)
+            .map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let bytes: [u8; 16] = v
+            .try_into()
+            .map_err(|_| E::invalid_length(v.len(), &self))?;
+        Ok(u128::from_be_bytes(bytes))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0u8; 16];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(17, &self));
+        }
+        Ok(u128::from_be_bytes(bytes))
+    }
+}
+
+fn deserialize_u128<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(U128Visitor)
+    } else {
+        deserializer.deserialize_bytes(U128Visitor)
+    }
+}
+
+impl Serialize for UnverifiedUuidV7 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_u128(self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UnverifiedUuidV7 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_u128(deserializer).map(UnverifiedUuidV7)
+    }
+}
+
+impl Serialize for UuidV7 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_u128(self.as_u128(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UuidV7 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let unverified_uuid = UnverifiedUuidV7::deserialize(deserializer)?;
+        UuidV7::try_from(unverified_uuid).map_err(de::Error::custom)
+    }
+}
+
+fn serialize_der<S: Serializer>(der_bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&Base64::encode_string(der_bytes))
+    } else {
+        serializer.serialize_bytes(der_bytes)
+    }
+}
+
+/// Accepts base64 text or raw bytes and returns the DER bytes.
+struct DerVisitor;
+
+impl<'de> de::Visitor<'de> for DerVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "base64-encoded DER or DER bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Base64::decode_vec(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // The size hint comes from the input, so it only sizes the buffer up
+        // to the longest valid encoding.
+        let capacity = seq.size_hint().unwrap_or(0).min(RAW_UUID_V7_DER_MAX_LEN);
+        let mut bytes = Vec::with_capacity(capacity);
+        while let Some(byte) = seq.next_element()? {
+            if bytes.len() == RAW_UUID_V7_DER_MAX_LEN {
+                return Err(de::Error::invalid_length(bytes.len() + 1, &self));
+            }
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+fn deserialize_der<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(DerVisitor)
+    } else {
+        deserializer.deserialize_byte_buf(DerVisitor)
+    }
+}
+
+impl Serialize for RawUuidV7Asn1 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let der_bytes = self.to_der_bytes().map_err(serde::ser::Error::custom)?;
+        serialize_der(&der_bytes, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawUuidV7Asn1 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let der_bytes = deserialize_der(deserializer)?;
+        RawUuidV7Asn1::from_der_bytes(&der_bytes).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for UuidV7Asn1 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let der_bytes = self.to_der_bytes().map_err(serde::ser::Error::custom)?;
+        serialize_der(&der_bytes, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UuidV7Asn1 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let der_bytes = deserialize_der(deserializer)?;
+        UuidV7Asn1::from_der_bytes(&der_bytes).map_err(de::Error::custom)
+    }
+}
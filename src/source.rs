@@ -0,0 +1,168 @@
+//! Clocks and random sources for UUIDv7 generation.
+//!
+//! The generators take their time and randomness from a [`Clock`] and a
+//! [`RandomSource`], so tests and simulations can swap the system ones for
+//! reproducible ones:
+//!
+//! | Clock             | Random source      | Behaviour                          |
+//! |-------------------|--------------------|------------------------------------|
+//! | [`SystemClock`]   | [`SystemRandom`]   | wall clock, UUIDv4 randomness      |
+//! | [`FixedClock`]    | [`FixedRandom`]    | the same value every time          |
+//! | [`SteppingClock`] | [`SeededRandom`]   | advances by a step / seeded PRNG   |
+//!
+//! [`SeededRandom`] is SplitMix64. It is fast and reproducible across
+//! platforms and releases, but it is not cryptographically secure; use it
+//! for tests only.
+
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+#[cfg(feature = "std")]
+use uuid::Uuid;
+
+/// A source of the current time.
+pub trait Clock {
+    /// Returns the time since the Unix epoch.
+    fn now(&mut self) -> Duration;
+
+    /// Waits for `duration` of this clock's time.
+    ///
+    /// Sleeps the current thread with the `std` feature and returns at once
+    /// without it. Simulated clocks advance their time instead.
+    fn sleep(&mut self, duration: Duration) {
+        #[cfg(feature = "std")]
+        std::thread::sleep(duration);
+        #[cfg(not(feature = "std"))]
+        let _ = duration;
+    }
+}
+
+/// A source of random bits.
+pub trait RandomSource {
+    /// Returns 128 random bits.
+    fn next_u128(&mut self) -> u128;
+}
+
+impl<C: Clock + ?Sized> Clock for &mut C {
+    fn now(&mut self) -> Duration {
+        (**self).now()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+impl<R: RandomSource + ?Sized> RandomSource for &mut R {
+    fn next_u128(&mut self) -> u128 {
+        (**self).next_u128()
+    }
+}
+
+/// The system wall clock. Times before the Unix epoch read as zero.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&mut self) -> Duration {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+}
+
+/// A clock that always returns the same time. Sleeping returns at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FixedClock(pub Duration);
+
+impl Clock for FixedClock {
+    fn now(&mut self) -> Duration {
+        self.0
+    }
+
+    fn sleep(&mut self, _duration: Duration) {}
+}
+
+/// A clock that starts at a given time and advances by a fixed step on
+/// every reading.
+///
+/// Sleeping advances the time instead of waiting. The time saturates at
+/// `Duration::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SteppingClock {
+    next: Duration,
+    step: Duration,
+}
+
+impl SteppingClock {
+    /// Creates a clock whose first reading is `start`.
+    pub fn new(start: Duration, step: Duration) -> Self {
+        Self { next: start, step }
+    }
+}
+
+impl Clock for SteppingClock {
+    fn now(&mut self) -> Duration {
+        let now = self.next;
+        self.next = now.saturating_add(self.step);
+        now
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        self.next = self.next.saturating_add(duration);
+    }
+}
+
+/// Randomness from the operating system, drawn through `Uuid::new_v4`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemRandom;
+
+#[cfg(feature = "std")]
+impl RandomSource for SystemRandom {
+    fn next_u128(&mut self) -> u128 {
+        Uuid::new_v4().as_u128()
+    }
+}
+
+/// A random source that always returns the same bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FixedRandom(pub u128);
+
+impl RandomSource for FixedRandom {
+    fn next_u128(&mut self) -> u128 {
+        self.0
+    }
+}
+
+/// A seeded SplitMix64 generator. Not cryptographically secure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeededRandom {
+    state: u64,
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next 64 bits of the SplitMix64 sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn next_u128(&mut self) -> u128 {
+        let high = self.next_u64();
+        let low = self.next_u64();
+        (u128::from(high) << 64) | u128::from(low)
+    }
+}
@@ -0,0 +1,351 @@
+//! Streaming codec for sequences of `RawUuidV7Asn1` values.
+//!
+//! Two framings are supported:
+//!
+//! - concatenated TLVs: one DER `RawUuidV7` SEQUENCE after another, with no
+//!   outer container ([`DerWriter`], [`DerReader`]);
+//! - a single `SEQUENCE OF RawUuidV7` container ([`write_sequence_of`],
+//!   [`SequenceOfReader`]).
+//!
+//! Readers hold at most one record in memory at a time. Input that ends in
+//! the middle of a record is reported as an [`Error::Der`] of kind
+//! [`ErrorKind::Incomplete`], like truncated input to the DER decoder;
+//! malformed framing as any other [`Error::Der`].
+
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+use der::Encode;
+use der::ErrorKind;
+use der::Length;
+use der::Tag;
+
+use crate::Error;
+use crate::ErrorCode;
+use crate::RawUuidV7Asn1;
+
+/// The DER tag of a constructed SEQUENCE.
+const SEQUENCE_TAG: u8 = 0x30;
+
+/// Largest accepted length of a single record.
+///
+/// A `RawUuidV7` is at most 37 bytes long; the limit keeps a corrupted
+/// length field from causing a large allocation.
+pub const MAX_RECORD_LEN: usize = 64;
+
+/// Encodes a DER length into `buf` and returns the number of bytes used.
+fn encode_length(len: usize, buf: &mut [u8; 9]) -> usize {
+    if len < 0x80 {
+        buf[0] = len as u8;
+        return 1;
+    }
+    let be = (len as u64).to_be_bytes();
+    let skip = be.iter().take_while(|b| **b == 0).count();
+    let octets = be.len() - skip;
+    buf[0] = 0x80 | octets as u8;
+    buf[1..=octets].copy_from_slice(&be[skip..]);
+    octets + 1
+}
+
+/// Builds the error for input that ends after `actual_len` of the
+/// `expected_len` bytes of a record or header.
+fn truncated(expected_len: usize, actual_len: usize) -> Error {
+    let kind = match (Length::try_from(expected_len), Length::try_from(actual_len)) {
+        (Ok(expected_len), Ok(actual_len)) => ErrorKind::Incomplete {
+            expected_len,
+            actual_len,
+        }
+        .into(),
+        (Err(e), _) | (_, Err(e)) => e,
+    };
+    Error::Der(kind)
+}
+
+/// Builds the error for a record longer than [`MAX_RECORD_LEN`].
+fn overlength(len: usize) -> Error {
+    let kind = match Length::try_from(len) {
+        Ok(len) => ErrorKind::Length { tag: Tag::Sequence }.at(len),
+        Err(e) => e,
+    };
+    Error::Der(kind)
+}
+
+/// Reads one byte, returning `None` on a clean end of input.
+fn read_byte_or_eof(reader: &mut impl Read) -> Result<Option<u8>, Error> {
+    let mut byte = [0u8; 1];
+    loop {
+        match reader.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(byte[0])),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(Error::Io(e)),
+        }
+    }
+}
+
+/// Fills `buf`, which starts `offset` bytes into a record or header.
+fn read_exact_at(reader: &mut impl Read, buf: &mut [u8], offset: usize) -> Result<(), Error> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => return Err(truncated(offset + buf.len(), offset + filled)),
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(Error::Io(e)),
+        }
+    }
+    Ok(())
+}
+
+/// A SEQUENCE header read from a stream.
+struct Header {
+    /// The encoded tag and length bytes.
+    bytes: [u8; 6],
+    /// Number of valid bytes in `bytes`.
+    header_len: usize,
+    /// The content length.
+    content_len: usize,
+}
+
+/// Reads a SEQUENCE header, returning `None` on a clean end of input.
+fn read_header(reader: &mut impl Read) -> Result<Option<Header>, Error> {
+    let Some(tag) = read_byte_or_eof(reader)? else {
+        return Ok(None);
+    };
+    if tag != SEQUENCE_TAG {
+        let actual = Tag::try_from(tag)?;
+        return Err(Error::Der(
+            ErrorKind::TagUnexpected {
+                expected: Some(Tag::Sequence),
+                actual,
+            }
+            .into(),
+        ));
+    }
+
+    let mut bytes = [0u8; 6];
+    bytes[0] = tag;
+    read_exact_at(reader, &mut bytes[1..2], 1)?;
+    let first = bytes[1];
+
+    if first < 0x80 {
+        return Ok(Some(Header {
+            bytes,
+            header_len: 2,
+            content_len: first.into(),
+        }));
+    }
+
+    let octets = usize::from(first & 0x7F);
+    if octets == 0 {
+        return Err(Error::Der(ErrorKind::IndefiniteLength.into()));
+    }
+    if octets > 4 {
+        return Err(Error::Der(ErrorKind::Overflow.into()));
+    }
+    read_exact_at(reader, &mut bytes[2..2 + octets], 2)?;
+    let content_len = bytes[2..2 + octets]
+        .iter()
+        .fold(0usize, |acc, b| (acc << 8) | usize::from(*b));
+
+    Ok(Some(Header {
+        bytes,
+        header_len: 2 + octets,
+        content_len,
+    }))
+}
+
+/// Writes `RawUuidV7Asn1` values as concatenated DER TLVs.
+pub struct DerWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> DerWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Encodes one value without allocating and writes it.
+    pub fn write(&mut self, value: &RawUuidV7Asn1) -> Result<(), Error> {
+        let mut buf = [0u8; MAX_RECORD_LEN];
+        let der_bytes = value.encode_to_slice(&mut buf)?;
+        Ok(self.inner.write_all(der_bytes)?)
+    }
+
+    /// Writes every value of the iterator.
+    pub fn write_all<'a>(
+        &mut self,
+        values: impl IntoIterator<Item = &'a RawUuidV7Asn1>,
+    ) -> Result<(), Error> {
+        values.into_iter().try_for_each(|value| self.write(value))
+    }
+
+    pub fn flush(&mut self) -> Result<(), Error> {
+        Ok(self.inner.flush()?)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Reads concatenated DER `RawUuidV7Asn1` TLVs from a byte stream.
+///
+/// Yields `None` when the input ends on a record boundary. Any error ends
+/// the iteration.
+pub struct DerReader<R> {
+    inner: R,
+    position: u64,
+    failed: bool,
+}
+
+impl<R: Read> DerReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            position: 0,
+            failed: false,
+        }
+    }
+
+    /// Returns the number of bytes consumed so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Reads the next value, returning `None` at the end of the input.
+    pub fn read_next(&mut self) -> Result<Option<RawUuidV7Asn1>, Error> {
+        let Some(header) = read_header(&mut self.inner)? else {
+            return Ok(None);
+        };
+        let value = read_record(&mut self.inner, &header)?;
+        self.position += (header.header_len + header.content_len) as u64;
+        Ok(Some(value))
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Reads the content of a record whose header has been read already.
+fn read_record(reader: &mut impl Read, header: &Header) -> Result<RawUuidV7Asn1, Error> {
+    let total = header.header_len + header.content_len;
+    if total > MAX_RECORD_LEN {
+        return Err(overlength(total));
+    }
+
+    let mut buf = [0u8; MAX_RECORD_LEN];
+    buf[..header.header_len].copy_from_slice(&header.bytes[..header.header_len]);
+    read_exact_at(
+        reader,
+        &mut buf[header.header_len..total],
+        header.header_len,
+    )?;
+
+    RawUuidV7Asn1::from_der_bytes(&buf[..total])
+}
+
+impl<R: Read> Iterator for DerReader<R> {
+    type Item = Result<RawUuidV7Asn1, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let item = self.read_next().transpose();
+        self.failed = matches!(item, Some(Err(_)));
+        item
+    }
+}
+
+/// Writes `values` as a single DER `SEQUENCE OF RawUuidV7` container.
+///
+/// The outer length is computed up front, so nothing is buffered.
+pub fn write_sequence_of(writer: &mut impl Write, values: &[RawUuidV7Asn1]) -> Result<(), Error> {
+    let mut content_len = 0usize;
+    for value in values {
+        content_len += usize::try_from(value.encoded_len()?)?;
+    }
+
+    let mut length = [0u8; 9];
+    let length_len = encode_length(content_len, &mut length);
+    writer.write_all(&[SEQUENCE_TAG])?;
+    writer.write_all(&length[..length_len])?;
+
+    let mut der_writer = DerWriter::new(writer);
+    der_writer.write_all(values)
+}
+
+/// Reads the elements of a DER `SEQUENCE OF RawUuidV7` container.
+///
+/// The outer header is read lazily by the first call to `next`. The reader
+/// stops after the container; bytes following it are left unread.
+pub struct SequenceOfReader<R> {
+    inner: R,
+    /// Content bytes of the container not read yet; `None` before the header.
+    remaining: Option<usize>,
+    failed: bool,
+}
+
+impl<R: Read> SequenceOfReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            remaining: None,
+            failed: false,
+        }
+    }
+
+    /// Reads the next element, returning `None` at the end of the container.
+    pub fn read_next(&mut self) -> Result<Option<RawUuidV7Asn1>, Error> {
+        let remaining = match self.remaining {
+            Some(remaining) => remaining,
+            None => {
+                let header = read_header(&mut self.inner)?.ok_or_else(|| truncated(2, 0))?;
+                header.content_len
+            }
+        };
+        self.remaining = Some(remaining);
+        if remaining == 0 {
+            return Ok(None);
+        }
+
+        // The element header must fit into the container too; reading it is
+        // bounded so that no byte after the container is consumed.
+        let mut limited = (&mut self.inner).take(remaining as u64);
+        let header = match read_header(&mut limited) {
+            Ok(Some(header)) => header,
+            Ok(None) => return Err(truncated(remaining, 0)),
+            Err(e) if e.code() == ErrorCode::Truncated && limited.limit() == 0 => {
+                return Err(Error::Der(ErrorKind::Length { tag: Tag::Sequence }.into()));
+            }
+            Err(e) => return Err(e),
+        };
+        let record_len = header.header_len + header.content_len;
+        if record_len > remaining {
+            return Err(overlength(record_len));
+        }
+        let value = read_record(&mut self.inner, &header)?;
+        self.remaining = Some(remaining - record_len);
+        Ok(Some(value))
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Iterator for SequenceOfReader<R> {
+    type Item = Result<RawUuidV7Asn1, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let item = self.read_next().transpose();
+        self.failed = matches!(item, Some(Err(_)));
+        item
+    }
+}
@@ -0,0 +1,368 @@
+//! Text forms of `UuidV7` and `UnverifiedUuidV7`.
+//!
+//! | Format                          | Example                                         |
+//! |---------------------------------|-------------------------------------------------|
+//! | [`TextFormat::Hyphenated`]      | `01898f2b-1c3d-7abc-9def-0123456789ab`          |
+//! | [`TextFormat::Urn`]             | `urn:uuid:01898f2b-1c3d-7abc-9def-0123456789ab` |
+//! | [`TextFormat::Braced`]          | `{01898f2b-1c3d-7abc-9def-0123456789ab}`        |
+//! | [`TextFormat::Simple`]          | `01898f2b1c3d7abc9def0123456789ab`              |
+//! | [`TextFormat::CrockfordBase32`] | `01H67JP71XFAY9VVR14D2PF2DB`                    |
+//! | [`TextFormat::Base64Url`]       | `AYmPKxw9eryd7wEjRWeJqw`                        |
+//!
+//! Formatting writes lowercase hex and uppercase Base32. Parsing ignores the
+//! case of hex digits, Base32 digits and the `urn:uuid:` prefix, and accepts
+//! the Crockford aliases `I`, `L` and `O`.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::Error;
+use crate::UnverifiedUuidV7;
+use crate::UuidV7;
+
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+const URN_PREFIX: &str = "urn:uuid:";
+
+/// Offsets of the hyphens in the hyphenated form.
+const HYPHENS: [usize; 4] = [8, 13, 18, 23];
+
+/// Longest text form, the URN.
+const MAX_LEN: usize = 45;
+
+/// The supported text forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextFormat {
+    /// 32 hex digits in groups of 8-4-4-4-12, as in RFC 9562.
+    #[default]
+    Hyphenated,
+    /// The hyphenated form with a `urn:uuid:` prefix.
+    Urn,
+    /// The hyphenated form in curly braces.
+    Braced,
+    /// 32 hex digits without separators.
+    Simple,
+    /// 26 digits of Crockford's Base32, as used by ULIDs.
+    CrockfordBase32,
+    /// 22 digits of unpadded base64url (RFC 4648, section 5).
+    Base64Url,
+}
+
+impl TextFormat {
+    /// Returns the length of the text form in characters.
+    pub fn char_len(&self) -> usize {
+        match self {
+            Self::Hyphenated => 36,
+            Self::Urn => URN_PREFIX.len() + 36,
+            Self::Braced => 38,
+            Self::Simple => 32,
+            Self::CrockfordBase32 => 26,
+            Self::Base64Url => 22,
+        }
+    }
+}
+
+/// Error type for text that is not a UUID in any supported form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The length does not match the expected form.
+    ///
+    /// `len` counts characters.
+    InvalidLength { len: usize },
+    /// A character is not allowed at its position.
+    ///
+    /// `position` counts characters from the start of the input.
+    InvalidCharacter {
+        character: char,
+        position: usize,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength { len } => write!(f, "invalid length of {len} characters"),
+            Self::InvalidCharacter {
+                character,
+                position,
+                expected,
+            } => write!(
+                f,
+                "invalid character {character:?} at position {position}, expected {expected}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Builds the error for the byte at `offset`, which starts a character.
+fn invalid_character(text: &str, offset: usize, expected: &'static str) -> ParseError {
+    ParseError::InvalidCharacter {
+        character: text[offset..].chars().next().unwrap_or_default(),
+        position: text[..offset].chars().count(),
+        expected,
+    }
+}
+
+/// Parses the hex digits of `text` from byte `offset` on, expecting a
+/// hyphen at the positions in `skip`, counted from `offset`.
+///
+/// The first non-ASCII byte always starts a character, so errors point to
+/// a whole character.
+fn parse_hex(text: &str, offset: usize, skip: &[usize]) -> Result<u128, ParseError> {
+    let mut value = 0u128;
+    for (i, byte) in text.bytes().enumerate().skip(offset) {
+        if skip.contains(&(i - offset)) {
+            if byte != b'-' {
+                return Err(invalid_character(text, i, "'-'"));
+            }
+            continue;
+        }
+        let digit = char::from(byte)
+            .to_digit(16)
+            .ok_or_else(|| invalid_character(text, i, "a hexadecimal digit"))?;
+        value = (value << 4) | u128::from(digit);
+    }
+    Ok(value)
+}
+
+fn parse_crockford(text: &str) -> Result<u128, ParseError> {
+    let mut value = 0u128;
+    for (i, byte) in text.bytes().enumerate() {
+        let digit = match byte.to_ascii_uppercase() {
+            b'I' | b'L' => 1,
+            b'O' => 0,
+            upper => CROCKFORD_ALPHABET
+                .iter()
+                .position(|c| *c == upper)
+                .ok_or_else(|| invalid_character(text, i, "a Crockford Base32 digit"))?,
+        };
+        // 26 digits hold 130 bits; the first one may only use the low 3.
+        if i == 0 && digit > 7 {
+            return Err(invalid_character(text, i, "a Base32 digit from '0' to '7'"));
+        }
+        value = (value << 5) | digit as u128;
+    }
+    Ok(value)
+}
+
+fn parse_base64url(text: &str) -> Result<u128, ParseError> {
+    let mut value = 0u128;
+    let last = text.len() - 1;
+    for (i, byte) in text.bytes().enumerate() {
+        let digit = BASE64URL_ALPHABET
+            .iter()
+            .position(|c| *c == byte)
+            .ok_or_else(|| invalid_character(text, i, "a base64url digit"))?;
+        // 22 digits hold 132 bits; the last one may only use the high 2.
+        if i == last {
+            if digit & 0x0F != 0 {
+                return Err(invalid_character(
+                    text,
+                    i,
+                    "a final base64url digit with zero padding bits",
+                ));
+            }
+            return Ok((value << 2) | (digit >> 4) as u128);
+        }
+        value = (value << 6) | digit as u128;
+    }
+    Ok(value)
+}
+
+/// Parses the hyphenated form found at byte `offset` of `text`.
+fn parse_hyphenated(text: &str, offset: usize) -> Result<u128, ParseError> {
+    parse_hex(text, offset, &HYPHENS)
+}
+
+/// Parses `text` in the given form.
+fn parse_as(text: &str, format: TextFormat) -> Result<u128, ParseError> {
+    let len = text.chars().count();
+    if len != format.char_len() {
+        return Err(ParseError::InvalidLength { len });
+    }
+
+    match format {
+        TextFormat::Hyphenated => parse_hyphenated(text, 0),
+        TextFormat::Urn => {
+            let prefix_len = URN_PREFIX.len();
+            for (i, (byte, expected)) in text.bytes().zip(URN_PREFIX.bytes()).enumerate() {
+                if !byte.eq_ignore_ascii_case(&expected) {
+                    return Err(invalid_character(text, i, "the prefix \"urn:uuid:\""));
+                }
+            }
+            parse_hyphenated(text, prefix_len)
+        }
+        TextFormat::Braced => {
+            if !text.starts_with('{') {
+                return Err(invalid_character(text, 0, "'{'"));
+            }
+            if !text.ends_with('}') {
+                let last = text.char_indices().last().map_or(0, |(i, _)| i);
+                return Err(invalid_character(text, last, "'}'"));
+            }
+            parse_hyphenated(&text[..text.len() - 1], 1)
+        }
+        TextFormat::Simple => parse_hex(text, 0, &[]),
+        TextFormat::CrockfordBase32 => parse_crockford(text),
+        TextFormat::Base64Url => parse_base64url(text),
+    }
+}
+
+/// Detects the form of `text` from its length, which differs for every form.
+fn detect(text: &str) -> Result<TextFormat, ParseError> {
+    let len = text.chars().count();
+    [
+        TextFormat::Hyphenated,
+        TextFormat::Urn,
+        TextFormat::Braced,
+        TextFormat::Simple,
+        TextFormat::CrockfordBase32,
+        TextFormat::Base64Url,
+    ]
+    .into_iter()
+    .find(|format| format.char_len() == len)
+    .ok_or(ParseError::InvalidLength { len })
+}
+
+/// Writes `value` in the given form into `buf` and returns the text.
+fn encode(value: u128, format: TextFormat, buf: &mut [u8; MAX_LEN]) -> &str {
+    let len = format.char_len();
+    let out = &mut buf[..len];
+
+    match format {
+        TextFormat::Hyphenated | TextFormat::Urn | TextFormat::Braced => {
+            let hex = match format {
+                TextFormat::Urn => {
+                    out[..URN_PREFIX.len()].copy_from_slice(URN_PREFIX.as_bytes());
+                    &mut out[URN_PREFIX.len()..]
+                }
+                TextFormat::Braced => {
+                    out[0] = b'{';
+                    out[len - 1] = b'}';
+                    &mut out[1..len - 1]
+                }
+                _ => &mut out[..],
+            };
+            let mut digit = 0;
+            for (i, c) in hex.iter_mut().enumerate() {
+                if HYPHENS.contains(&i) {
+                    *c = b'-';
+                } else {
+                    *c = HEX_ALPHABET[((value >> (124 - 4 * digit)) & 0x0F) as usize];
+                    digit += 1;
+                }
+            }
+        }
+        TextFormat::Simple => {
+            for (i, c) in out.iter_mut().enumerate() {
+                *c = HEX_ALPHABET[((value >> (124 - 4 * i)) & 0x0F) as usize];
+            }
+        }
+        TextFormat::CrockfordBase32 => {
+            for (i, c) in out.iter_mut().enumerate() {
+                *c = CROCKFORD_ALPHABET[((value >> (125 - 5 * i)) & 0x1F) as usize];
+            }
+        }
+        TextFormat::Base64Url => {
+            for (i, c) in out.iter_mut().enumerate() {
+                let digit = if i == len - 1 {
+                    (value & 0x03) << 4
+                } else {
+                    (value >> (122 - 6 * i)) & 0x3F
+                };
+                *c = BASE64URL_ALPHABET[digit as usize];
+            }
+        }
+    }
+
+    core::str::from_utf8(out).unwrap_or_default()
+}
+
+/// Displays a UUID in one of the text forms. Created by
+/// [`UnverifiedUuidV7::format`] and [`UuidV7::format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formatted {
+    value: u128,
+    format: TextFormat,
+}
+
+impl fmt::Display for Formatted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; MAX_LEN];
+        f.pad(encode(self.value, self.format, &mut buf))
+    }
+}
+
+impl UnverifiedUuidV7 {
+    /// Returns a value that displays the UUID in the given form.
+    pub fn format(&self, format: TextFormat) -> Formatted {
+        Formatted {
+            value: self.0,
+            format,
+        }
+    }
+
+    /// Parses any of the supported text forms.
+    pub fn parse_str(text: &str) -> Result<Self, ParseError> {
+        Self::parse_with(text, detect(text)?)
+    }
+
+    /// Parses the given text form only.
+    pub fn parse_with(text: &str, format: TextFormat) -> Result<Self, ParseError> {
+        parse_as(text, format).map(UnverifiedUuidV7)
+    }
+}
+
+impl UuidV7 {
+    /// Returns a value that displays the UUID in the given form.
+    pub fn format(&self, format: TextFormat) -> Formatted {
+        UnverifiedUuidV7::from(*self).format(format)
+    }
+
+    /// Parses any of the supported text forms and checks the version and
+    /// variant bits.
+    pub fn parse_str(text: &str) -> Result<Self, Error> {
+        Ok(UnverifiedUuidV7::parse_str(text)?.try_into()?)
+    }
+
+    /// Parses the given text form only and checks the version and variant bits.
+    pub fn parse_with(text: &str, format: TextFormat) -> Result<Self, Error> {
+        Ok(UnverifiedUuidV7::parse_with(text, format)?.try_into()?)
+    }
+}
+
+impl fmt::Display for UnverifiedUuidV7 {
+    /// Writes the hyphenated form.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format(TextFormat::Hyphenated).fmt(f)
+    }
+}
+
+impl fmt::Display for UuidV7 {
+    /// Writes the hyphenated form.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format(TextFormat::Hyphenated).fmt(f)
+    }
+}
+
+impl FromStr for UnverifiedUuidV7 {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse_str(text)
+    }
+}
+
+impl FromStr for UuidV7 {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse_str(text)
+    }
+}
@@ -0,0 +1,269 @@
+//! A minimal, permissive TLV parser over byte slices.
+//!
+//! Unlike the `der` crate, which rejects anything that is not strict DER,
+//! this parser accepts non-minimal lengths and reports them, so callers can
+//! tell exactly which rule an encoding breaks and where. [`parse_ber`] also
+//! accepts the indefinite length form of BER and CER.
+
+use der::ErrorKind;
+use der::Length;
+use der::Tag;
+
+/// The constructed bit of the identifier octet.
+pub(crate) const CONSTRUCTED: u8 = 0x20;
+
+/// How deeply indefinite-length and constructed elements may nest.
+pub(crate) const MAX_DEPTH: usize = 8;
+
+/// One tag-length-value element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Tlv<'a> {
+    /// Offset of the tag byte from the start of the whole input.
+    pub offset: usize,
+    /// The identifier octet.
+    pub tag: u8,
+    /// Number of tag and length bytes.
+    pub header_len: usize,
+    /// The content bytes.
+    pub content: &'a [u8],
+    /// False if the length is not in its shortest form or is indefinite.
+    pub minimal_length: bool,
+    /// Number of end-of-contents bytes after the content; 2 for the
+    /// indefinite length form, 0 otherwise.
+    pub trailer_len: usize,
+}
+
+impl Tlv<'_> {
+    /// Offset of the first content byte from the start of the whole input.
+    pub fn content_offset(&self) -> usize {
+        self.offset + self.header_len
+    }
+
+    /// Offset just past the element.
+    pub fn end_offset(&self) -> usize {
+        self.content_offset() + self.content.len() + self.trailer_len
+    }
+
+    /// Returns true if the element is constructed.
+    pub fn is_constructed(&self) -> bool {
+        self.tag & CONSTRUCTED != 0
+    }
+}
+
+/// Structural errors that make an element unreadable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TlvError {
+    /// The input ends before the element does.
+    Truncated {
+        offset: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// The length is the indefinite form, which DER does not allow.
+    IndefiniteLength { offset: usize },
+    /// The length does not fit into `usize`.
+    LengthOverflow { offset: usize },
+    /// The tag uses the multi-byte high tag number form.
+    HighTagNumber { offset: usize },
+    /// Elements nest more than [`MAX_DEPTH`] levels deep.
+    TooDeep { offset: usize },
+}
+
+impl TlvError {
+    /// Returns the offset the error refers to.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::Truncated { offset, .. }
+            | Self::IndefiniteLength { offset }
+            | Self::LengthOverflow { offset }
+            | Self::HighTagNumber { offset }
+            | Self::TooDeep { offset } => *offset,
+        }
+    }
+}
+
+impl From<TlvError> for der::Error {
+    fn from(e: TlvError) -> Self {
+        let kind = match e {
+            TlvError::Truncated {
+                expected, actual, ..
+            } => match (Length::try_from(expected), Length::try_from(actual)) {
+                (Ok(expected_len), Ok(actual_len)) => ErrorKind::Incomplete {
+                    expected_len,
+                    actual_len,
+                },
+                _ => ErrorKind::Overflow,
+            },
+            TlvError::IndefiniteLength { .. } => ErrorKind::IndefiniteLength,
+            TlvError::LengthOverflow { .. } => ErrorKind::Overflow,
+            TlvError::HighTagNumber { .. } => ErrorKind::TagNumberInvalid,
+            TlvError::TooDeep { .. } => ErrorKind::Overlength,
+        };
+        match Length::try_from(e.offset()) {
+            Ok(position) => kind.at(position),
+            Err(_) => kind.into(),
+        }
+    }
+}
+
+/// Parses the element at the start of `input`.
+///
+/// `base` is the offset of `input` within the whole input and is only used
+/// for reporting. Returns the element and the bytes following it.
+pub(crate) fn parse(input: &[u8], base: usize) -> Result<(Tlv<'_>, &[u8]), TlvError> {
+    let header = parse_header(input, base)?;
+    let Some(length) = header.length else {
+        return Err(TlvError::IndefiniteLength { offset: base + 1 });
+    };
+    definite(input, base, header, length)
+}
+
+/// Parses the element at the start of `input` like [`parse`], but also
+/// accepts the indefinite length form for constructed elements.
+///
+/// The content of an indefinite-length element excludes the end-of-contents
+/// bytes, which are counted in [`Tlv::trailer_len`].
+pub(crate) fn parse_ber(input: &[u8], base: usize) -> Result<(Tlv<'_>, &[u8]), TlvError> {
+    parse_ber_nested(input, base, 0)
+}
+
+fn parse_ber_nested(input: &[u8], base: usize, depth: usize) -> Result<(Tlv<'_>, &[u8]), TlvError> {
+    let header = parse_header(input, base)?;
+    if let Some(length) = header.length {
+        return definite(input, base, header, length);
+    }
+    if header.tag & CONSTRUCTED == 0 {
+        return Err(TlvError::IndefiniteLength { offset: base + 1 });
+    }
+    if depth >= MAX_DEPTH {
+        return Err(TlvError::TooDeep { offset: base });
+    }
+
+    let body = &input[header.len..];
+    let mut rest = body;
+    while !rest.starts_with(&[0x00, 0x00]) {
+        let offset = base + input.len() - rest.len();
+        (_, rest) = parse_ber_nested(rest, offset, depth + 1)?;
+    }
+
+    let tlv = Tlv {
+        offset: base,
+        tag: header.tag,
+        header_len: header.len,
+        content: &body[..body.len() - rest.len()],
+        minimal_length: false,
+        trailer_len: 2,
+    };
+    Ok((tlv, &rest[2..]))
+}
+
+/// The identifier and length octets of an element.
+pub(crate) struct Header {
+    pub tag: u8,
+    /// Number of tag and length bytes.
+    pub len: usize,
+    /// The content length, or `None` for the indefinite form.
+    pub length: Option<usize>,
+    pub minimal_length: bool,
+}
+
+fn truncated(input: &[u8], base: usize, expected: usize) -> TlvError {
+    TlvError::Truncated {
+        offset: base,
+        expected,
+        actual: input.len(),
+    }
+}
+
+/// Parses the identifier and length octets at the start of `input`, even if
+/// the content is truncated.
+pub(crate) fn parse_header(input: &[u8], base: usize) -> Result<Header, TlvError> {
+    let tag = *input.first().ok_or(truncated(input, base, 1))?;
+    if tag & 0x1F == 0x1F {
+        return Err(TlvError::HighTagNumber { offset: base });
+    }
+    let first = *input.get(1).ok_or(truncated(input, base, 2))?;
+
+    if first < 0x80 {
+        return Ok(Header {
+            tag,
+            len: 2,
+            length: Some(usize::from(first)),
+            minimal_length: true,
+        });
+    }
+    if first == 0x80 {
+        return Ok(Header {
+            tag,
+            len: 2,
+            length: None,
+            minimal_length: false,
+        });
+    }
+
+    let octets = usize::from(first & 0x7F);
+    let bytes = input
+        .get(2..2 + octets)
+        .ok_or(truncated(input, base, 2 + octets))?;
+    if octets > size_of::<usize>() {
+        return Err(TlvError::LengthOverflow { offset: base + 1 });
+    }
+    let length = bytes
+        .iter()
+        .fold(0usize, |acc, b| (acc << 8) | usize::from(*b));
+    Ok(Header {
+        tag,
+        len: 2 + octets,
+        length: Some(length),
+        minimal_length: length >= 0x80 && bytes[0] != 0,
+    })
+}
+
+fn definite(
+    input: &[u8],
+    base: usize,
+    header: Header,
+    length: usize,
+) -> Result<(Tlv<'_>, &[u8]), TlvError> {
+    let end = header
+        .len
+        .checked_add(length)
+        .ok_or(TlvError::LengthOverflow { offset: base + 1 })?;
+    let content = input
+        .get(header.len..end)
+        .ok_or(truncated(input, base, end))?;
+
+    let tlv = Tlv {
+        offset: base,
+        tag: header.tag,
+        header_len: header.len,
+        content,
+        minimal_length: header.minimal_length,
+        trailer_len: 0,
+    };
+    Ok((tlv, &input[end..]))
+}
+
+/// Returns an error unless the element has the expected tag.
+pub(crate) fn expect_tag(element: &Tlv<'_>, expected: u8) -> Result<(), der::Error> {
+    if element.tag == expected {
+        return Ok(());
+    }
+    let kind = ErrorKind::TagUnexpected {
+        expected: Tag::try_from(expected).ok(),
+        actual: Tag::try_from(element.tag)?,
+    };
+    Err(kind.into())
+}
+
+/// Returns the error for `remaining_end - decoded` bytes left after a value.
+pub(crate) fn trailing_data(decoded: usize, remaining_end: usize) -> der::Error {
+    let kind = match (
+        Length::try_from(decoded),
+        Length::try_from(remaining_end - decoded),
+    ) {
+        (Ok(decoded), Ok(remaining)) => ErrorKind::TrailingData { decoded, remaining },
+        _ => ErrorKind::Overflow,
+    };
+    kind.into()
+}
@@ -0,0 +1,324 @@
+//! Validating decoder for DER `RawUuidV7` values from untrusted peers.
+//!
+//! [`RawUuidV7Asn1`](crate::RawUuidV7Asn1) accepts any `u64` timestamp and
+//! any `u8` version. This decoder reads the DER bytes itself and checks every
+//! field against the UUIDv7 layout:
+//!
+//! - [`ValidationPolicy::Strict`] fails on the first violation;
+//! - [`ValidationPolicy::Lenient`] returns the best-effort value together
+//!   with every violation found.
+//!
+//! Input that cannot be read at all, such as wrong tags, truncated elements
+//! or BIT STRINGs of the wrong size, is an error under both policies.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use der::ErrorKind;
+use der::Tag;
+
+use crate::Error;
+use crate::FieldError;
+use crate::RawUuidV7Field;
+use crate::UnverifiedUuidV7;
+use crate::UuidV7;
+use crate::tlv;
+use crate::tlv::Tlv;
+
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+
+/// How the decoder reacts to a violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationPolicy {
+    /// Reject the input on the first violation.
+    #[default]
+    Strict,
+    /// Collect every violation and return the best-effort value.
+    Lenient,
+}
+
+/// A rule of DER or of the UUIDv7 layout that the input breaks.
+///
+/// Offsets are relative to the start of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// A length is not encoded in its shortest form.
+    NonMinimalLength { offset: usize },
+    /// An INTEGER has redundant leading `0x00` or `0xFF` bytes.
+    NonMinimalInteger {
+        field: RawUuidV7Field,
+        offset: usize,
+    },
+    /// An INTEGER is negative.
+    NegativeInteger {
+        field: RawUuidV7Field,
+        offset: usize,
+    },
+    /// The timestamp is 2^48 or more.
+    TimestampOutOfRange { value: i128, offset: usize },
+    /// The version is not 7.
+    InvalidVersion { value: i128, offset: usize },
+    /// The variant bits are not `0b10`.
+    InvalidVariant { value: u8, offset: usize },
+    /// The trailing unused bits of a BIT STRING are not zero.
+    NonZeroUnusedBits {
+        field: RawUuidV7Field,
+        offset: usize,
+    },
+}
+
+impl Violation {
+    /// Returns the offset of the offending element.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::NonMinimalLength { offset }
+            | Self::NonMinimalInteger { offset, .. }
+            | Self::NegativeInteger { offset, .. }
+            | Self::TimestampOutOfRange { offset, .. }
+            | Self::InvalidVersion { offset, .. }
+            | Self::InvalidVariant { offset, .. }
+            | Self::NonZeroUnusedBits { offset, .. } => *offset,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonMinimalLength { offset } => {
+                write!(f, "offset {offset}: length is not minimally encoded")
+            }
+            Self::NonMinimalInteger { field, offset } => write!(
+                f,
+                "offset {offset}: {}: INTEGER is not minimally encoded",
+                field.name()
+            ),
+            Self::NegativeInteger { field, offset } => {
+                write!(f, "offset {offset}: {}: INTEGER is negative", field.name())
+            }
+            Self::TimestampOutOfRange { value, offset } => write!(
+                f,
+                "offset {offset}: unix-ts-ms: {value} does not fit in 48 bits"
+            ),
+            Self::InvalidVersion { value, offset } => {
+                write!(f, "offset {offset}: version is {value}, expected 7")
+            }
+            Self::InvalidVariant { value, offset } => write!(
+                f,
+                "offset {offset}: variant is 0b{value:02b}, expected 0b10"
+            ),
+            Self::NonZeroUnusedBits { field, offset } => write!(
+                f,
+                "offset {offset}: {}: unused bits are not zero",
+                field.name()
+            ),
+        }
+    }
+}
+
+impl core::error::Error for Violation {}
+
+/// The result of a validating decode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validated {
+    /// The decoded value. Fields that broke a rule are truncated to their width.
+    pub uuid: UnverifiedUuidV7,
+    /// Every violation found, in input order. Empty for valid input.
+    pub violations: Vec<Violation>,
+}
+
+impl Validated {
+    /// Returns true if no violation was found.
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Collects violations according to the policy.
+struct Checker {
+    policy: ValidationPolicy,
+    violations: Vec<Violation>,
+}
+
+impl Checker {
+    fn report(&mut self, violation: Violation) -> Result<(), Error> {
+        match self.policy {
+            ValidationPolicy::Strict => Err(Error::Violation(violation)),
+            ValidationPolicy::Lenient => {
+                self.violations.push(violation);
+                Ok(())
+            }
+        }
+    }
+
+    /// Reads the next element, checking its tag and length encoding.
+    fn element<'a>(
+        &mut self,
+        input: &'a [u8],
+        base: usize,
+        tag: u8,
+    ) -> Result<(Tlv<'a>, &'a [u8]), Error> {
+        let (element, rest) = tlv::parse(input, base).map_err(der::Error::from)?;
+        tlv::expect_tag(&element, tag)?;
+        if !element.minimal_length {
+            self.report(Violation::NonMinimalLength {
+                offset: element.offset,
+            })?;
+        }
+        Ok((element, rest))
+    }
+
+    /// Reads an INTEGER as a signed value.
+    fn integer<'a>(
+        &mut self,
+        input: &'a [u8],
+        base: usize,
+        field: RawUuidV7Field,
+    ) -> Result<(i128, usize, &'a [u8]), Error> {
+        let (element, rest) = self.element(input, base, TAG_INTEGER)?;
+        let content = element.content;
+        if content.is_empty() || content.len() > 16 {
+            return Err(Error::Der(ErrorKind::Length { tag: Tag::Integer }.into()));
+        }
+
+        if let [first, second, ..] = content {
+            let redundant =
+                (*first == 0x00 && second & 0x80 == 0) || (*first == 0xFF && second & 0x80 != 0);
+            if redundant {
+                self.report(Violation::NonMinimalInteger {
+                    field,
+                    offset: element.offset,
+                })?;
+            }
+        }
+
+        let sign = if content[0] & 0x80 != 0 { -1i128 } else { 0 };
+        let value = content
+            .iter()
+            .fold(sign, |acc, b| (acc << 8) | i128::from(*b));
+        if value < 0 {
+            self.report(Violation::NegativeInteger {
+                field,
+                offset: element.offset,
+            })?;
+        }
+        Ok((value, element.offset, rest))
+    }
+
+    /// Reads a BIT STRING of exactly `field.bit_len()` bits.
+    fn bit_string<'a>(
+        &mut self,
+        input: &'a [u8],
+        base: usize,
+        field: RawUuidV7Field,
+    ) -> Result<(u128, usize, &'a [u8]), Error> {
+        let (element, rest) = self.element(input, base, TAG_BIT_STRING)?;
+        let Some((&unused, bytes)) = element.content.split_first() else {
+            return Err(Error::Der(
+                ErrorKind::Length {
+                    tag: Tag::BitString,
+                }
+                .into(),
+            ));
+        };
+
+        let expected = field.bit_len();
+        let actual = (bytes.len() * 8).saturating_sub(usize::from(unused));
+        if unused > 7 || bytes.len() > 16 || actual != expected {
+            return Err(Error::Field(FieldError::InvalidBitLength {
+                field,
+                expected,
+                actual,
+            }));
+        }
+
+        let raw = bytes
+            .iter()
+            .fold(0u128, |acc, b| (acc << 8) | u128::from(*b));
+        if raw & ((1 << unused) - 1) != 0 {
+            self.report(Violation::NonZeroUnusedBits {
+                field,
+                offset: element.offset,
+            })?;
+        }
+        Ok((raw >> unused, element.offset, rest))
+    }
+}
+
+/// Decodes a DER `RawUuidV7` value, checking it against `policy`.
+pub fn decode(der_bytes: &[u8], policy: ValidationPolicy) -> Result<Validated, Error> {
+    let mut checker = Checker {
+        policy,
+        violations: Vec::new(),
+    };
+
+    let (sequence, trailing) = checker.element(der_bytes, 0, TAG_SEQUENCE)?;
+    if !trailing.is_empty() {
+        return Err(tlv::trailing_data(sequence.end_offset(), der_bytes.len()).into());
+    }
+
+    let base = sequence.content_offset();
+    let input = sequence.content;
+
+    let at = |rest: &[u8]| base + input.len() - rest.len();
+
+    let (unix_ts_ms, ts_offset, rest) = checker.integer(input, base, RawUuidV7Field::UnixTsMs)?;
+    if unix_ts_ms > RawUuidV7Field::UnixTsMs.max_value() as i128 {
+        checker.report(Violation::TimestampOutOfRange {
+            value: unix_ts_ms,
+            offset: ts_offset,
+        })?;
+    }
+
+    let (version, version_offset, rest) =
+        checker.integer(rest, at(rest), RawUuidV7Field::Version)?;
+    if version != 7 {
+        checker.report(Violation::InvalidVersion {
+            value: version,
+            offset: version_offset,
+        })?;
+    }
+
+    let (rand_a, _, rest) = checker.bit_string(rest, at(rest), RawUuidV7Field::RandA)?;
+    let (variant, variant_offset, rest) =
+        checker.bit_string(rest, at(rest), RawUuidV7Field::Variant)?;
+    if variant != 0b10 {
+        checker.report(Violation::InvalidVariant {
+            value: variant as u8,
+            offset: variant_offset,
+        })?;
+    }
+    let (rand_b, _, rest) = checker.bit_string(rest, at(rest), RawUuidV7Field::RandB)?;
+
+    if !rest.is_empty() {
+        return Err(tlv::trailing_data(at(rest), sequence.end_offset()).into());
+    }
+
+    let uuid = UnverifiedUuidV7(
+        (((unix_ts_ms as u128) & RawUuidV7Field::UnixTsMs.max_value()) << 80)
+            | (((version as u128) & RawUuidV7Field::Version.max_value()) << 76)
+            | (rand_a << 64)
+            | (variant << 62)
+            | rand_b,
+    );
+
+    Ok(Validated {
+        uuid,
+        violations: checker.violations,
+    })
+}
+
+/// Decodes a DER `RawUuidV7` value, rejecting anything that is not a
+/// strictly valid UUIDv7.
+pub fn decode_strict(der_bytes: &[u8]) -> Result<UuidV7, Error> {
+    let validated = decode(der_bytes, ValidationPolicy::Strict)?;
+    Ok(UuidV7::try_from(validated.uuid)?)
+}
+
+/// Decodes a DER `RawUuidV7` value, returning the value and every violation.
+pub fn decode_lenient(der_bytes: &[u8]) -> Result<(UnverifiedUuidV7, Vec<Violation>), Error> {
+    let validated = decode(der_bytes, ValidationPolicy::Lenient)?;
+    Ok((validated.uuid, validated.violations))
+}
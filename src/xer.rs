@@ -0,0 +1,386 @@
+//! XML Encoding Rules (X.693) for `RawUuidV7` and the compact `UuidV7`.
+//!
+//! A SEQUENCE is an element named after its type, holding one element per
+//! field, named after the field's identifier. INTEGERs are decimal numbers
+//! and BIT STRINGs are strings of `0` and `1` characters:
+//!
+//! ```text
+//! <RawUuidV7>
+//!   <unix-ts-ms>1722873636745</unix-ts-ms>
+//!   <version>7</version>
+//!   <rand-a>000100100011</rand-a>
+//!   <variant>10</variant>
+//!   <rand-b>00000100100011010001010110011110001001101010111100110111101111</rand-b>
+//! </RawUuidV7>
+//! ```
+//!
+//! The encoder writes canonical XER, without any whitespace, or the indented
+//! form above, see [`XerStyle`]. The decoder accepts basic XER: an optional
+//! XML declaration, and whitespace between elements, around INTEGERs and
+//! inside BIT STRINGs. Comments, entity references and empty elements are
+//! not accepted.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::fmt::Write;
+
+use crate::Error;
+use crate::FieldError;
+use crate::RawUuidV7;
+use crate::RawUuidV7Field;
+#[cfg(feature = "alloc")]
+use crate::UnverifiedUuidV7;
+use crate::UuidV7;
+use crate::cursor::Cursor;
+use crate::cursor::NumberError;
+
+const RAW_UUID_V7: &str = "RawUuidV7";
+const UUID_V7: &str = "UuidV7";
+
+/// Layout of the XER documents written by the encoder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum XerStyle {
+    /// Canonical XER (CXER): one line without any whitespace.
+    #[default]
+    Canonical,
+    /// Basic XER with every field on its own line, indented by two spaces.
+    Indented,
+}
+
+/// Errors of the XER decoder.
+///
+/// Positions count characters from the start of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XerError {
+    /// The input ends before the document does.
+    UnexpectedEnd,
+    /// A character is not allowed at its position.
+    UnexpectedCharacter {
+        character: char,
+        position: usize,
+        expected: &'static str,
+    },
+    /// A tag does not name the expected element.
+    UnexpectedElement {
+        expected: &'static str,
+        position: usize,
+    },
+    /// An INTEGER has leading zeros.
+    NonMinimalInteger {
+        field: RawUuidV7Field,
+        position: usize,
+    },
+    /// An INTEGER that must not be negative is negative.
+    NegativeInteger {
+        field: RawUuidV7Field,
+        position: usize,
+    },
+    /// An INTEGER does not fit into 64 bits.
+    IntegerOverflow {
+        field: RawUuidV7Field,
+        position: usize,
+    },
+    /// Characters other than whitespace follow the document.
+    TrailingData { position: usize },
+}
+
+impl fmt::Display for XerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "input ends in the middle of the document"),
+            Self::UnexpectedCharacter {
+                character,
+                position,
+                expected,
+            } => write!(
+                f,
+                "unexpected character {character:?} at position {position}, expected {expected}"
+            ),
+            Self::UnexpectedElement { expected, position } => {
+                write!(f, "position {position}: expected a <{expected}> tag")
+            }
+            Self::NonMinimalInteger { field, position } => write!(
+                f,
+                "position {position}: {}: INTEGER has leading zeros",
+                field.name()
+            ),
+            Self::NegativeInteger { field, position } => write!(
+                f,
+                "position {position}: {}: INTEGER is negative",
+                field.name()
+            ),
+            Self::IntegerOverflow { field, position } => write!(
+                f,
+                "position {position}: {}: INTEGER does not fit in 64 bits",
+                field.name()
+            ),
+            Self::TrailingData { position } => {
+                write!(
+                    f,
+                    "unexpected content after the document at position {position}"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for XerError {}
+
+/// Builds a document field by field.
+#[cfg(feature = "alloc")]
+struct Writer {
+    out: String,
+    style: XerStyle,
+}
+
+#[cfg(feature = "alloc")]
+impl Writer {
+    fn new(type_name: &str, style: XerStyle) -> Self {
+        let mut out = String::new();
+        out.push('<');
+        out.push_str(type_name);
+        out.push('>');
+        Writer { out, style }
+    }
+
+    fn start(&mut self, field: RawUuidV7Field) {
+        if self.style == XerStyle::Indented {
+            self.out.push_str("\n  ");
+        }
+        self.out.push('<');
+        self.out.push_str(field.name());
+        self.out.push('>');
+    }
+
+    fn end(&mut self, field: RawUuidV7Field) {
+        self.out.push_str("</");
+        self.out.push_str(field.name());
+        self.out.push('>');
+    }
+
+    fn integer(&mut self, field: RawUuidV7Field, value: u64) {
+        self.start(field);
+        // Writing to a `String` cannot fail.
+        let _ = write!(self.out, "{value}");
+        self.end(field);
+    }
+
+    fn bit_string(&mut self, field: RawUuidV7Field, value: u128) {
+        self.start(field);
+        for shift in (0..field.bit_len()).rev() {
+            self.out
+                .push(if (value >> shift) & 1 == 1 { '1' } else { '0' });
+        }
+        self.end(field);
+    }
+
+    fn finish(mut self, type_name: &str) -> String {
+        if self.style == XerStyle::Indented {
+            self.out.push('\n');
+        }
+        self.out.push_str("</");
+        self.out.push_str(type_name);
+        self.out.push('>');
+        self.out
+    }
+}
+
+/// Reads a document element by element.
+struct Parser<'a> {
+    cursor: Cursor<'a>,
+}
+
+impl<'a> Parser<'a> {
+    /// Starts at the root element, skipping a byte order mark and an XML
+    /// declaration.
+    fn new(xer: &'a str) -> Result<Self, XerError> {
+        let mut cursor = Cursor::new(xer);
+        cursor.eat("\u{feff}");
+        cursor.skip_whitespace();
+        if cursor.eat("<?xml") {
+            let end = xer[cursor.offset..]
+                .find("?>")
+                .ok_or(XerError::UnexpectedEnd)?;
+            cursor.offset += end + 2;
+        }
+        Ok(Parser { cursor })
+    }
+
+    /// Reports the character at `offset`, or the end of the input.
+    fn unexpected_at(&self, offset: usize, expected: &'static str) -> XerError {
+        match self.cursor.character_at(offset) {
+            Some(character) => XerError::UnexpectedCharacter {
+                character,
+                position: self.cursor.position_of(offset),
+                expected,
+            },
+            None => XerError::UnexpectedEnd,
+        }
+    }
+
+    /// Reads the start or end tag of element `name`.
+    fn tag(&mut self, name: &'static str, end: bool) -> Result<(), XerError> {
+        self.cursor.skip_whitespace();
+        let offset = self.cursor.offset;
+        let (open, expected) = if end {
+            ("</", "an end tag")
+        } else {
+            ("<", "a start tag")
+        };
+        if !self.cursor.eat(open) {
+            return Err(self.unexpected_at(offset, expected));
+        }
+        if !self.cursor.eat(name) {
+            return Err(self.unexpected_element(offset, name));
+        }
+        self.cursor.skip_whitespace();
+        if !self.cursor.eat(">") {
+            return Err(self.unexpected_element(offset, name));
+        }
+        Ok(())
+    }
+
+    fn unexpected_element(&self, offset: usize, name: &'static str) -> XerError {
+        if self.cursor.is_at_end() {
+            return XerError::UnexpectedEnd;
+        }
+        XerError::UnexpectedElement {
+            expected: name,
+            position: self.cursor.position_of(offset),
+        }
+    }
+
+    /// Reads a non-negative INTEGER element of at most 64 bits.
+    fn integer(&mut self, field: RawUuidV7Field) -> Result<u64, XerError> {
+        self.tag(field.name(), false)?;
+        self.cursor.skip_whitespace();
+        let offset = self.cursor.offset;
+        let position = self.cursor.position_of(offset);
+        let value = self.cursor.number().map_err(|e| match e {
+            NumberError::NoDigits => self.unexpected_at(offset, "a decimal digit"),
+            NumberError::LeadingZero => XerError::NonMinimalInteger { field, position },
+            NumberError::Negative => XerError::NegativeInteger { field, position },
+            NumberError::Overflow => XerError::IntegerOverflow { field, position },
+        })?;
+        self.tag(field.name(), true)?;
+        Ok(value)
+    }
+
+    /// Reads a BIT STRING element of exactly `field.bit_len()` bits.
+    fn bit_string(&mut self, field: RawUuidV7Field) -> Result<u128, Error> {
+        self.tag(field.name(), false)?;
+        let mut value = 0u128;
+        let mut bits = 0;
+        loop {
+            match self.cursor.peek() {
+                Some(bit @ (b'0' | b'1')) => {
+                    value = (value << 1) | u128::from(bit - b'0');
+                    bits += 1;
+                }
+                Some(b' ' | b'\t' | b'\n' | b'\r') => {}
+                _ => break,
+            }
+            self.cursor.offset += 1;
+        }
+        self.tag(field.name(), true)?;
+
+        let expected = field.bit_len();
+        if bits != expected {
+            return Err(Error::Field(FieldError::InvalidBitLength {
+                field,
+                expected,
+                actual: bits,
+            }));
+        }
+        Ok(value)
+    }
+
+    fn finish(mut self) -> Result<(), XerError> {
+        self.cursor.skip_whitespace();
+        if !self.cursor.is_at_end() {
+            return Err(XerError::TrailingData {
+                position: self.cursor.position_of(self.cursor.offset),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl RawUuidV7 {
+    /// Encodes the value as an XER `RawUuidV7` document.
+    #[cfg(feature = "alloc")]
+    pub fn to_xer_string(&self, style: XerStyle) -> Result<String, Error> {
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, self.unix_ts_ms)?;
+        let rand_a = RawUuidV7::check_range(RawUuidV7Field::RandA, self.rand_a.into())?;
+        let variant = RawUuidV7::check_range(RawUuidV7Field::Variant, self.variant.into())?;
+        let rand_b = RawUuidV7::check_range(RawUuidV7Field::RandB, self.rand_b)?;
+
+        let mut writer = Writer::new(RAW_UUID_V7, style);
+        writer.integer(RawUuidV7Field::UnixTsMs, unix_ts_ms);
+        writer.integer(RawUuidV7Field::Version, self.version.into());
+        writer.bit_string(RawUuidV7Field::RandA, rand_a.into());
+        writer.bit_string(RawUuidV7Field::Variant, variant.into());
+        writer.bit_string(RawUuidV7Field::RandB, rand_b.into());
+        Ok(writer.finish(RAW_UUID_V7))
+    }
+
+    /// Decodes a basic or canonical XER `RawUuidV7` document.
+    pub fn from_xer(xer: &str) -> Result<Self, Error> {
+        let mut parser = Parser::new(xer)?;
+        parser.tag(RAW_UUID_V7, false)?;
+        let unix_ts_ms = parser.integer(RawUuidV7Field::UnixTsMs)?;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, unix_ts_ms)?;
+        let version = parser.integer(RawUuidV7Field::Version)?;
+        let version = u8::try_from(version).map_err(|_| FieldError::OutOfRange {
+            field: RawUuidV7Field::Version,
+            value: version,
+        })?;
+        let rand_a = parser.bit_string(RawUuidV7Field::RandA)?;
+        let variant = parser.bit_string(RawUuidV7Field::Variant)?;
+        let rand_b = parser.bit_string(RawUuidV7Field::RandB)?;
+        parser.tag(RAW_UUID_V7, true)?;
+        parser.finish()?;
+
+        Ok(RawUuidV7 {
+            unix_ts_ms,
+            version,
+            rand_a: rand_a as u16,
+            variant: variant as u8,
+            rand_b: rand_b as u64,
+        })
+    }
+}
+
+impl UuidV7 {
+    /// Encodes the value as an XER compact `UuidV7` document.
+    #[cfg(feature = "alloc")]
+    pub fn to_xer_string(&self, style: XerStyle) -> String {
+        let unverified_uuid = UnverifiedUuidV7::from(*self);
+        let rand_ab =
+            (u128::from(unverified_uuid.rand_a()) << 62) | u128::from(unverified_uuid.rand_b());
+
+        let mut writer = Writer::new(UUID_V7, style);
+        writer.integer(RawUuidV7Field::UnixTsMs, unverified_uuid.unix_ts_ms());
+        writer.bit_string(RawUuidV7Field::RandAb, rand_ab);
+        writer.finish(UUID_V7)
+    }
+
+    /// Decodes a basic or canonical XER compact `UuidV7` document.
+    pub fn from_xer(xer: &str) -> Result<Self, Error> {
+        let mut parser = Parser::new(xer)?;
+        parser.tag(UUID_V7, false)?;
+        let unix_ts_ms = parser.integer(RawUuidV7Field::UnixTsMs)?;
+        let unix_ts_ms = RawUuidV7::check_range(RawUuidV7Field::UnixTsMs, unix_ts_ms)?;
+        let rand_ab = parser.bit_string(RawUuidV7Field::RandAb)?;
+        parser.tag(UUID_V7, true)?;
+        parser.finish()?;
+
+        let rand_a = rand_ab >> 62;
+        let rand_b = rand_ab & 0x3FFF_FFFF_FFFF_FFFF;
+        Ok(UuidV7(
+            ((unix_ts_ms as u128) << 80) | (7u128 << 76) | (rand_a << 64) | (2u128 << 62) | rand_b,
+        ))
+    }
+}
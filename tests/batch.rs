@@ -0,0 +1,157 @@
+//! Checks batch generation and the contiguous DER buffer.
+
+#![cfg(feature = "alloc")]
+
+use core::time::Duration;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::DerBatch;
+use rs_asn1der2uuid7::MonotonicGenerator;
+use rs_asn1der2uuid7::MonotonicStrategy;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::UuidV7;
+
+/// 2024-08-05T16:00:36.745Z.
+const SINCE_EPOCH: Duration = Duration::from_millis(1722873636745);
+
+fn strategies() -> [MonotonicStrategy; 3] {
+    [
+        MonotonicStrategy::FixedCounterRandA,
+        MonotonicStrategy::RandomIncrementRandB {
+            max_increment: 1 << 20,
+        },
+        MonotonicStrategy::SubMillisecondRandA,
+    ]
+}
+
+fn pseudo_random(count: usize) -> impl Iterator<Item = u128> {
+    (0..count as u128).map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C835))
+}
+
+#[test]
+fn batch_is_strictly_increasing_and_valid() {
+    for strategy in strategies() {
+        let mut generator = MonotonicGenerator::new(strategy);
+        let values = generator
+            .next_batch_u128_from(SINCE_EPOCH, pseudo_random(10_000))
+            .expect("in range");
+        assert_eq!(values.len(), 10_000);
+        assert!(values.windows(2).all(|w| w[0] < w[1]), "{strategy:?}");
+        assert!(
+            values.iter().all(|v| UuidV7::try_from(*v).is_ok()),
+            "{strategy:?}"
+        );
+        // A single clock reading: the first value uses it.
+        assert_eq!(
+            UnverifiedUuidV7(values[0]).unix_ts_ms(),
+            SINCE_EPOCH.as_millis() as u64
+        );
+    }
+}
+
+#[test]
+fn batch_continues_after_single_values() {
+    let mut generator = MonotonicGenerator::default();
+    let first = generator.next_u128_from(SINCE_EPOCH, 1).expect("in range");
+    let batch = generator
+        .next_batch_u128_from(SINCE_EPOCH, pseudo_random(100))
+        .expect("in range");
+    let last = generator.next_u128_from(SINCE_EPOCH, 2).expect("in range");
+    assert!(first < batch[0]);
+    assert!(batch[batch.len() - 1] < last);
+}
+
+#[test]
+fn counter_overflow_moves_the_timestamp() {
+    let mut generator = MonotonicGenerator::new(MonotonicStrategy::FixedCounterRandA);
+    let values = generator
+        .next_batch_u128_from(SINCE_EPOCH, pseudo_random(5_000))
+        .expect("in range");
+    let first_ms = UnverifiedUuidV7(values[0]).unix_ts_ms();
+    let last_ms = UnverifiedUuidV7(values[values.len() - 1]).unix_ts_ms();
+    assert!(last_ms > first_ms);
+    assert!(last_ms - first_ms <= 3);
+}
+
+#[test]
+fn der_batch_matches_single_encodings() {
+    let mut generator = MonotonicGenerator::default();
+    let values = generator
+        .next_batch_u128_from(SINCE_EPOCH, pseudo_random(64))
+        .expect("in range");
+    let batch = DerBatch::encode(values.iter().copied()).expect("encodable");
+
+    assert_eq!(batch.len(), values.len());
+    assert_eq!(batch.offsets().len(), values.len());
+    assert_eq!(batch.iter().count(), values.len());
+    for (index, (value, der_bytes)) in values.iter().zip(batch.iter()).enumerate() {
+        let expected = RawUuidV7::from(*value).to_der_array().expect("encodable");
+        assert_eq!(der_bytes, expected.as_bytes());
+        assert_eq!(batch.get(index), Some(der_bytes));
+        assert_eq!(
+            &batch.as_bytes()[batch.offsets()[index]..][..der_bytes.len()],
+            der_bytes
+        );
+    }
+    assert_eq!(batch.get(values.len()), None);
+
+    let concatenated = batch.iter().flatten().copied().collect::<Vec<u8>>();
+    let (der_bytes, offsets) = batch.into_parts();
+    assert_eq!(der_bytes, concatenated);
+    assert_eq!(offsets[0], 0);
+}
+
+#[test]
+fn empty_batch() {
+    let mut generator = MonotonicGenerator::default();
+    let values = generator
+        .next_batch_u128_from(SINCE_EPOCH, [])
+        .expect("in range");
+    assert!(values.is_empty());
+    let batch = DerBatch::encode([]).expect("encodable");
+    assert!(batch.is_empty());
+    assert!(batch.as_bytes().is_empty());
+    assert_eq!(batch.get(0), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn system_clock_batches() {
+    let mut generator = MonotonicGenerator::default();
+    let structs = generator
+        .next_raw_uuid_v7_asn1_batch(100)
+        .expect("encodable");
+    let batch = generator.next_der_batch(100).expect("encodable");
+    assert_eq!(structs.len(), 100);
+    assert_eq!(batch.len(), 100);
+
+    let decoded = batch
+        .iter()
+        .map(|der_bytes| RawUuidV7Asn1::from_der_bytes(der_bytes).expect("decodable"))
+        .collect::<Vec<_>>();
+    let values = structs
+        .into_iter()
+        .chain(decoded)
+        .map(|asn1| UuidV7::try_from(UnverifiedUuidV7::try_from(asn1).expect("fields")))
+        .collect::<Result<Vec<_>, _>>()
+        .expect("valid UUIDv7s");
+    assert!(values.windows(2).all(|w| w[0] < w[1]));
+}
+
+proptest! {
+    #[test]
+    fn der_batch_round_trips(values in proptest::collection::vec(any::<u128>(), 0..32)) {
+        let batch = DerBatch::encode(values.iter().copied()).expect("encodable");
+        let decoded = batch
+            .iter()
+            .map(|der_bytes| {
+                let asn1 = RawUuidV7Asn1::from_der_bytes(der_bytes).expect("decodable");
+                UnverifiedUuidV7::try_from(asn1).expect("fields").0
+            })
+            .collect::<Vec<_>>();
+        prop_assert_eq!(decoded, values);
+    }
+}
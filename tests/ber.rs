@@ -0,0 +1,240 @@
+//! Checks the tolerant BER/CER decoder against encodings built here by hand.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::FieldError;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::ber;
+
+use common::hex2bytes;
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.der.txt`.
+const DER_HEX: &str = "301f0206019123456789020107030304123003020680030902048d159e26af37bc";
+
+/// The same value as CER: indefinite lengths, a constructed `rand-b` split
+/// into two segments, and a non-minimal version INTEGER.
+const CER_HEX: &str = concat!(
+    "3080",
+    "0206019123456789",
+    "02020007",
+    "030304123f",
+    "030206bf",
+    "2380",
+    "030400048d15",
+    "0306029e26af37bd",
+    "0000",
+    "0000",
+);
+
+fn expected() -> RawUuidV7 {
+    RawUuidV7 {
+        unix_ts_ms: 1722873636745,
+        version: 7,
+        rand_a: 0x123,
+        variant: 0x2,
+        rand_b: 0x0123456789abcdef,
+    }
+}
+
+#[test]
+fn der_is_accepted() {
+    assert_eq!(ber::decode(&hex2bytes(DER_HEX)).ok(), Some(expected()));
+}
+
+#[test]
+fn cer_is_accepted() {
+    assert_eq!(ber::decode(&hex2bytes(CER_HEX)).ok(), Some(expected()));
+}
+
+#[test]
+fn cer_is_canonicalized_to_der() {
+    let der_bytes = ber::canonicalize(&hex2bytes(CER_HEX)).expect("valid BER");
+    assert_eq!(der_bytes, hex2bytes(DER_HEX));
+    assert!(RawUuidV7Asn1::from_der_bytes(&der_bytes).is_ok());
+}
+
+#[test]
+fn prefix_returns_the_following_bytes() {
+    let mut input = hex2bytes(CER_HEX);
+    input.extend_from_slice(&hex2bytes(DER_HEX));
+    let (first, rest) = ber::decode_prefix(&input).expect("valid BER");
+    assert_eq!(first, expected());
+    assert_eq!(rest, hex2bytes(DER_HEX));
+    assert!(matches!(ber::decode(&input), Err(Error::Der(_))));
+}
+
+#[test]
+fn negative_integer_is_rejected() {
+    let input = hex2bytes("301a0201ff0201070303040000030206800309020000000000000000");
+    assert!(matches!(ber::decode(&input), Err(Error::Der(_))));
+}
+
+#[test]
+fn wide_version_is_rejected() {
+    let input = hex2bytes("301b020100020201000303040000030206800309020000000000000000");
+    let expected = FieldError::OutOfRange {
+        field: RawUuidV7Field::Version,
+        value: 256,
+    };
+    assert!(matches!(ber::decode(&input), Err(Error::Field(e)) if e == expected));
+}
+
+#[test]
+fn unused_bits_only_in_last_segment() {
+    // rand-a split after a segment with 4 unused bits.
+    let input = hex2bytes(concat!(
+        "3080",
+        "020100",
+        "020107",
+        "2308",
+        "03020410",
+        "03020000",
+        "030206800309020000000000000000",
+        "0000",
+    ));
+    assert!(matches!(ber::decode(&input), Err(Error::Der(_))));
+}
+
+#[test]
+fn primitive_indefinite_length_is_rejected() {
+    let input = hex2bytes("30800280010000000201070303040000030206800309020000000000000000");
+    assert!(matches!(ber::decode(&input), Err(Error::Der(_))));
+}
+
+#[test]
+fn deep_nesting_is_rejected() {
+    let mut input = hex2bytes("3080020100020107");
+    input.extend(std::iter::repeat_n([0x23, 0x80], 64).flatten());
+    input.extend_from_slice(&hex2bytes("03030400"));
+    input.extend(std::iter::repeat_n([0x00, 0x00], 64).flatten());
+    assert!(matches!(ber::decode(&input), Err(Error::Der(_))));
+}
+
+/// How one element is encoded.
+#[derive(Debug, Clone, Copy)]
+struct Style {
+    indefinite: bool,
+    long_form_length: bool,
+    /// Extra leading zero bytes of an INTEGER.
+    padding: usize,
+    /// Where a BIT STRING is split into two segments, if at all.
+    split: Option<usize>,
+    /// Set the unused bits of a BIT STRING.
+    dirty: bool,
+}
+
+fn any_style() -> impl Strategy<Value = Style> {
+    (
+        any::<bool>(),
+        any::<bool>(),
+        0usize..4,
+        proptest::option::of(0usize..8),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(indefinite, long_form_length, padding, split, dirty)| Style {
+                indefinite,
+                long_form_length,
+                padding,
+                split,
+                dirty,
+            },
+        )
+}
+
+fn element(tag: u8, content: &[u8], style: Style) -> Vec<u8> {
+    let mut out = vec![tag];
+    if style.indefinite && tag & 0x20 != 0 {
+        out.push(0x80);
+        out.extend_from_slice(content);
+        out.extend_from_slice(&[0x00, 0x00]);
+        return out;
+    }
+    if style.long_form_length {
+        out.extend_from_slice(&[0x82, (content.len() >> 8) as u8, content.len() as u8]);
+    } else {
+        out.push(content.len() as u8);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+fn integer(value: u64, style: Style) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|b| **b == 0).count().min(7);
+    let mut content = vec![0; style.padding];
+    if bytes[skip] & 0x80 != 0 {
+        content.push(0);
+    }
+    content.extend_from_slice(&bytes[skip..]);
+    element(0x02, &content, style)
+}
+
+fn bit_string(field: RawUuidV7Field, value: u128, style: Style) -> Vec<u8> {
+    let octets = field.bit_len().div_ceil(8);
+    let unused = (octets * 8 - field.bit_len()) as u8;
+    let mut bytes = (value << unused).to_be_bytes()[16 - octets..].to_vec();
+    if style.dirty {
+        bytes[octets - 1] |= (1 << unused) - 1;
+    }
+
+    let primitive = |unused: u8, bytes: &[u8]| {
+        let mut content = vec![unused];
+        content.extend_from_slice(bytes);
+        element(0x03, &content, style)
+    };
+    match style.split {
+        Some(at) if at < octets => {
+            let mut content = primitive(0, &bytes[..at]);
+            content.extend(primitive(unused, &bytes[at..]));
+            element(0x23, &content, style)
+        }
+        _ => primitive(unused, &bytes),
+    }
+}
+
+fn ber_bytes(raw_uuid: RawUuidV7, styles: [Style; 6]) -> Vec<u8> {
+    let mut content = integer(raw_uuid.unix_ts_ms, styles[0]);
+    content.extend(integer(raw_uuid.version.into(), styles[1]));
+    content.extend(bit_string(
+        RawUuidV7Field::RandA,
+        raw_uuid.rand_a.into(),
+        styles[2],
+    ));
+    content.extend(bit_string(
+        RawUuidV7Field::Variant,
+        raw_uuid.variant.into(),
+        styles[3],
+    ));
+    content.extend(bit_string(
+        RawUuidV7Field::RandB,
+        raw_uuid.rand_b.into(),
+        styles[4],
+    ));
+    element(0x30, &content, styles[5])
+}
+
+proptest! {
+    #[test]
+    fn any_ber_style_decodes_to_the_same_value(
+        value in any::<u128>(),
+        styles in proptest::array::uniform6(any_style()),
+    ) {
+        let raw_uuid = RawUuidV7::from(value);
+        let input = ber_bytes(raw_uuid, styles);
+        prop_assert_eq!(ber::decode(&input).ok(), Some(raw_uuid));
+
+        let der_bytes = RawUuidV7Asn1::try_from(raw_uuid)
+            .expect("encodable")
+            .to_der_bytes()
+            .expect("encodable");
+        prop_assert_eq!(ber::canonicalize(&input).ok(), Some(der_bytes));
+    }
+}
@@ -0,0 +1,76 @@
+//! Helpers shared by the integration tests.
+//!
+//! Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use core::fmt::Debug;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::RawUuidV7;
+
+/// Splits a vector corpus into records of whitespace-separated fields,
+/// skipping blank lines and `#` comments.
+pub fn records(corpus: &str) -> impl Iterator<Item = Vec<&str>> {
+    corpus
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.split_whitespace().collect())
+}
+
+pub fn hex2bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("hex"))
+        .collect()
+}
+
+pub fn parse_hex(field: &str) -> u64 {
+    u64::from_str_radix(field.trim_start_matches("0x"), 16).expect("hex field")
+}
+
+/// Returns the fields of a `RawUuidV7` vector record.
+pub fn raw_uuid_of(record: &[&str]) -> RawUuidV7 {
+    RawUuidV7 {
+        unix_ts_ms: record[1].parse().expect("timestamp"),
+        version: record[2].parse().expect("version"),
+        rand_a: parse_hex(record[3]) as u16,
+        variant: parse_hex(record[4]) as u8,
+        rand_b: parse_hex(record[5]),
+    }
+}
+
+/// `mixed-1` from the vector corpus.
+pub fn mixed_1() -> RawUuidV7 {
+    RawUuidV7 {
+        unix_ts_ms: 1722873636745,
+        version: 7,
+        rand_a: 0x123,
+        variant: 0x2,
+        rand_b: 0x0123456789abcdef,
+    }
+}
+
+/// Any `RawUuidV7` whose fields fit into their slots, except for a version
+/// of any width.
+pub fn any_raw_uuid_v7() -> impl Strategy<Value = RawUuidV7> {
+    (any::<u128>(), any::<u8>()).prop_map(|(value, version)| RawUuidV7 {
+        version,
+        ..RawUuidV7::from(value)
+    })
+}
+
+/// Asserts that `decode` fails on each input with the expected error, as
+/// picked out of [`Error`] by `unwrap`.
+pub fn assert_rejected<I: Debug, T: Debug, E: PartialEq + Debug>(
+    cases: impl IntoIterator<Item = (I, E)>,
+    decode: impl Fn(&I) -> Result<T, Error>,
+    unwrap: impl Fn(&Error) -> Option<&E>,
+) {
+    for (input, expected) in cases {
+        let result = decode(&input);
+        let actual = result.as_ref().err().and_then(&unwrap);
+        assert_eq!(actual, Some(&expected), "{input:?}: {result:?}");
+    }
+}
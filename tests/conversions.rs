@@ -0,0 +1,192 @@
+//! Property tests for the conversion matrix between the UUIDv7 types,
+//! `u128`, `[u8; 16]`, `uuid::Uuid`, `uuid::Timestamp` and `SystemTime`.
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::FieldError;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::UuidV7Error;
+
+use uuid::Timestamp;
+use uuid::Uuid;
+
+const VERSION_MASK: u128 = 0xF << 76;
+const VARIANT_MASK: u128 = 0x3 << 62;
+
+/// Any 128-bit value, valid UUIDv7 or not.
+fn any_unverified() -> impl Strategy<Value = UnverifiedUuidV7> {
+    any::<u128>().prop_map(UnverifiedUuidV7)
+}
+
+/// A 128-bit value with the UUIDv7 version and variant bits set.
+fn any_uuid_v7() -> impl Strategy<Value = UuidV7> {
+    any::<u128>().prop_map(|value| {
+        let value = (value & !VERSION_MASK & !VARIANT_MASK) | (7 << 76) | (2 << 62);
+        UuidV7::try_from(value).expect("valid UUIDv7")
+    })
+}
+
+/// A `RawUuidV7` whose fields all fit into their slots.
+fn any_valid_raw_uuid_v7() -> impl Strategy<Value = RawUuidV7> {
+    any::<u128>().prop_map(RawUuidV7::from)
+}
+
+/// A `RawUuidV7` whose timestamp is wider than 48 bits.
+fn wide_raw_uuid_v7() -> impl Strategy<Value = RawUuidV7> {
+    (any::<u128>(), 1u64 << 48..).prop_map(|(value, unix_ts_ms)| RawUuidV7 {
+        unix_ts_ms,
+        ..RawUuidV7::from(value)
+    })
+}
+
+fn timestamp_ms(timestamp: Timestamp) -> u64 {
+    let (seconds, nanos) = timestamp.to_unix();
+    seconds * 1000 + u64::from(nanos / 1_000_000)
+}
+
+proptest! {
+    #[test]
+    fn unverified_round_trips_through_u128(unverified in any_unverified()) {
+        prop_assert_eq!(UnverifiedUuidV7::from(u128::from(unverified)), unverified);
+    }
+
+    #[test]
+    fn unverified_round_trips_through_bytes(unverified in any_unverified()) {
+        let bytes: [u8; 16] = unverified.into();
+        prop_assert_eq!(bytes, unverified.0.to_be_bytes());
+        prop_assert_eq!(UnverifiedUuidV7::from(bytes), unverified);
+    }
+
+    #[test]
+    fn unverified_round_trips_through_uuid(unverified in any_unverified()) {
+        let uuid = Uuid::from(unverified);
+        prop_assert_eq!(uuid.as_u128(), unverified.0);
+        prop_assert_eq!(UnverifiedUuidV7::from(uuid), unverified);
+    }
+
+    #[test]
+    fn unverified_round_trips_through_raw(unverified in any_unverified()) {
+        let raw_uuid = RawUuidV7::from(unverified);
+        prop_assert_eq!(UnverifiedUuidV7::try_from(raw_uuid), Ok(unverified));
+        prop_assert_eq!(u128::try_from(raw_uuid), Ok(unverified.0));
+        prop_assert_eq!(<[u8; 16]>::try_from(raw_uuid), Ok(unverified.0.to_be_bytes()));
+        prop_assert_eq!(Uuid::try_from(raw_uuid), Ok(Uuid::from_u128(unverified.0)));
+    }
+
+    #[test]
+    fn uuid_v7_round_trips_through_every_representation(uuid_v7 in any_uuid_v7()) {
+        let value = u128::from(uuid_v7);
+        prop_assert_eq!(UuidV7::try_from(value), Ok(uuid_v7));
+
+        let bytes: [u8; 16] = uuid_v7.into();
+        prop_assert_eq!(UuidV7::try_from(bytes), Ok(uuid_v7));
+
+        let uuid = Uuid::from(uuid_v7);
+        prop_assert_eq!(uuid.get_version_num(), 7);
+        prop_assert_eq!(UuidV7::try_from(uuid), Ok(uuid_v7));
+
+        let unverified = UnverifiedUuidV7::from(uuid_v7);
+        prop_assert_eq!(UuidV7::try_from(unverified), Ok(uuid_v7));
+
+        let raw_uuid = RawUuidV7::from(uuid_v7);
+        prop_assert_eq!(UuidV7::try_from(raw_uuid).ok(), Some(uuid_v7));
+    }
+
+    #[test]
+    fn paths_between_the_same_types_agree(unverified in any_unverified()) {
+        let via_bytes = RawUuidV7::from(<[u8; 16]>::from(unverified));
+        let via_uuid = RawUuidV7::from(Uuid::from(unverified));
+        let via_u128 = RawUuidV7::from(u128::from(unverified));
+        prop_assert_eq!(via_bytes, RawUuidV7::from(unverified));
+        prop_assert_eq!(via_uuid, RawUuidV7::from(unverified));
+        prop_assert_eq!(via_u128, RawUuidV7::from(unverified));
+    }
+
+    #[test]
+    fn invalid_version_is_reported(value in any::<u128>(), version in 0u8..16) {
+        prop_assume!(version != 7);
+        let value = (value & !VERSION_MASK & !VARIANT_MASK) | (u128::from(version) << 76) | (2 << 62);
+        prop_assert_eq!(UuidV7::try_from(value), Err(UuidV7Error::InvalidVersion(version)));
+        prop_assert_eq!(UuidV7::try_from(value.to_be_bytes()), Err(UuidV7Error::InvalidVersion(version)));
+        prop_assert_eq!(UuidV7::try_from(Uuid::from_u128(value)), Err(UuidV7Error::InvalidVersion(version)));
+        prop_assert!(matches!(
+            UuidV7::try_from(RawUuidV7::from(value)),
+            Err(Error::Uuid(UuidV7Error::InvalidVersion(v))) if v == version
+        ));
+    }
+
+    #[test]
+    fn invalid_variant_is_reported(value in any::<u128>(), variant in 0u8..4) {
+        prop_assume!(variant != 2);
+        let value = (value & !VERSION_MASK & !VARIANT_MASK) | (7 << 76) | (u128::from(variant) << 62);
+        prop_assert_eq!(UuidV7::try_from(value), Err(UuidV7Error::InvalidVariant(variant)));
+        prop_assert_eq!(UuidV7::try_from(value.to_be_bytes()), Err(UuidV7Error::InvalidVariant(variant)));
+        prop_assert_eq!(UuidV7::try_from(Uuid::from_u128(value)), Err(UuidV7Error::InvalidVariant(variant)));
+    }
+
+    #[test]
+    fn wide_raw_fields_are_reported(raw_uuid in wide_raw_uuid_v7()) {
+        let expected = FieldError::OutOfRange {
+            field: RawUuidV7Field::UnixTsMs,
+            value: raw_uuid.unix_ts_ms,
+        };
+        prop_assert_eq!(u128::try_from(raw_uuid), Err(expected));
+        prop_assert_eq!(<[u8; 16]>::try_from(raw_uuid), Err(expected));
+        prop_assert_eq!(Uuid::try_from(raw_uuid), Err(expected));
+        prop_assert_eq!(Timestamp::try_from(raw_uuid).map(timestamp_ms), Err(expected));
+        prop_assert!(matches!(UuidV7::try_from(raw_uuid), Err(Error::Field(e)) if e == expected));
+    }
+
+    #[test]
+    fn timestamp_matches_uuid_crate(uuid_v7 in any_uuid_v7()) {
+        let timestamp = Timestamp::from(uuid_v7);
+        let expected = Uuid::from(uuid_v7).get_timestamp().expect("UUIDv7 has a timestamp");
+        prop_assert_eq!(timestamp.to_unix(), expected.to_unix());
+        prop_assert_eq!(timestamp_ms(timestamp), UnverifiedUuidV7::from(uuid_v7).unix_ts_ms());
+    }
+
+    #[test]
+    fn raw_timestamp_matches_unverified(raw_uuid in any_valid_raw_uuid_v7()) {
+        let timestamp = Timestamp::try_from(raw_uuid).expect("fields fit");
+        prop_assert_eq!(timestamp_ms(timestamp), raw_uuid.unix_ts_ms);
+    }
+}
+
+#[cfg(feature = "std")]
+mod system_time {
+    use std::time::Duration;
+    use std::time::SystemTime;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn system_time_has_millisecond_precision(uuid_v7 in any_uuid_v7()) {
+            let unverified = UnverifiedUuidV7::from(uuid_v7);
+            let expected = SystemTime::UNIX_EPOCH + Duration::from_millis(unverified.unix_ts_ms());
+            prop_assert_eq!(SystemTime::from(uuid_v7), expected);
+            prop_assert_eq!(SystemTime::from(unverified), expected);
+            prop_assert_eq!(SystemTime::try_from(RawUuidV7::from(uuid_v7)), Ok(expected));
+        }
+
+        #[test]
+        fn system_time_agrees_with_timestamp(uuid_v7 in any_uuid_v7()) {
+            let (seconds, nanos) = Timestamp::from(uuid_v7).to_unix();
+            let expected = SystemTime::UNIX_EPOCH + Duration::new(seconds, nanos);
+            prop_assert_eq!(SystemTime::from(uuid_v7), expected);
+        }
+
+        #[test]
+        fn system_time_truncates_full_precision(uuid_v7 in any_uuid_v7()) {
+            let unverified = UnverifiedUuidV7::from(uuid_v7);
+            let precise = unverified.to_system_time();
+            let truncated = SystemTime::from(unverified);
+            let lag = precise.duration_since(truncated).expect("not after the precise time");
+            prop_assert!(lag < Duration::from_millis(1));
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! Checks the dumpasn1-style inspector against the DER vectors and against
+//! encodings broken here by hand.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::dump;
+use rs_asn1der2uuid7::dump::Component;
+use rs_asn1der2uuid7::dump::Entry;
+use rs_asn1der2uuid7::dump::Problem;
+use rs_asn1der2uuid7::dump::Schema;
+use rs_asn1der2uuid7::dump::Value;
+
+use common::hex2bytes;
+use common::records;
+
+const RAW_UUID_V7_VECTORS: &str = include_str!("vectors/raw-uuid-v7.der.txt");
+const UUID_V7_VECTORS: &str = include_str!("vectors/uuid-v7.der.txt");
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.der.txt`.
+const DER_HEX: &str = "301f0206019123456789020107030304123003020680030902048d159e26af37bc";
+
+const MIXED_1_DUMP: &str = "    0   31: SEQUENCE {  -- RawUuidV7
+    2    6:   INTEGER 01 91 23 45 67 89  -- unix-ts-ms: 1722873636745 (2024-08-05T16:00:36.745Z)
+   10    1:   INTEGER 07  -- version: 7 (UUIDv7, Unix Epoch time-based)
+   13    3:   BIT STRING, 4 unused bits 12 30  -- rand-a: 0x123
+   18    2:   BIT STRING, 6 unused bits 80  -- variant: 0b10 (RFC 9562)
+   22    9:   BIT STRING, 2 unused bits 04 8D 15 9E 26 AF 37 BC  -- rand-b: 0x0123456789abcdef
+          : }
+";
+
+fn problems(hex: &str, schema: Schema) -> Vec<Problem> {
+    dump::dump(&hex2bytes(hex), schema)
+        .problems()
+        .copied()
+        .collect()
+}
+
+fn values(der_bytes: &[u8], schema: Schema) -> Vec<(Component, Value)> {
+    dump::dump(der_bytes, schema)
+        .entries()
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Element(element) => element.component.zip(element.value),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn mixed_1_is_listed() {
+    let der_bytes = hex2bytes(DER_HEX);
+    let dump = dump::dump(&der_bytes, Schema::RawUuidV7);
+    assert!(dump.is_der());
+    assert_eq!(dump.to_string(), MIXED_1_DUMP);
+}
+
+#[test]
+fn raw_uuid_v7_vectors_are_der() {
+    for record in records(RAW_UUID_V7_VECTORS) {
+        let der_bytes = hex2bytes(record[6]);
+        let dump = dump::dump(&der_bytes, Schema::RawUuidV7);
+        assert!(dump.is_der(), "{}: {dump}", record[0]);
+
+        let unix_ts_ms = record[1].parse::<i128>().expect("timestamp");
+        let values = values(&der_bytes, Schema::RawUuidV7);
+        assert_eq!(values.len(), 5, "{}", record[0]);
+        assert_eq!(
+            values[0],
+            (
+                Component::Field(RawUuidV7Field::UnixTsMs),
+                Value::Integer(unix_ts_ms)
+            )
+        );
+    }
+}
+
+#[test]
+fn uuid_v7_vectors_are_der() {
+    for record in records(UUID_V7_VECTORS) {
+        let der_bytes = hex2bytes(record[2]);
+        let dump = dump::dump(&der_bytes, Schema::UuidV7);
+        assert!(dump.is_der(), "{}: {dump}", record[0]);
+        let values = values(&der_bytes, Schema::UuidV7);
+        assert_eq!(values.len(), 2, "{}", record[0]);
+        assert_eq!(values[1].0, Component::RandAb);
+    }
+}
+
+#[test]
+fn problems_point_to_the_byte_at_fault() {
+    let cases = [
+        // Non-minimal SEQUENCE length.
+        (
+            "30811f0206019123456789020107030304123003020680030902048d159e26af37bc",
+            vec![Problem::NonMinimalLength { offset: 1 }],
+        ),
+        // Redundant leading zero byte in the version INTEGER.
+        (
+            "3020020601912345678902020007030304123003020680030902048d159e26af37bc",
+            vec![Problem::NonMinimalInteger { offset: 12 }],
+        ),
+        // Non-zero unused bits in rand-a.
+        (
+            "301f0206019123456789020107030304123103020680030902048d159e26af37bc",
+            vec![Problem::NonZeroUnusedBits { offset: 17 }],
+        ),
+        // Negative timestamp.
+        (
+            "301f0206f19123456789020107030304123003020680030902048d159e26af37bc",
+            vec![Problem::NegativeInteger { offset: 4 }],
+        ),
+        // rand-a with 13 bits.
+        (
+            "301f0206019123456789020107030303123003020680030902048d159e26af37bc",
+            vec![Problem::InvalidBitLength {
+                offset: 15,
+                expected: 12,
+                actual: 13,
+            }],
+        ),
+        // version as a BIT STRING.
+        (
+            "301f0206019123456789030107030304123003020680030902048d159e26af37bc",
+            vec![Problem::UnexpectedTag {
+                offset: 10,
+                expected: 0x02,
+                actual: 0x03,
+            }],
+        ),
+        // A byte after the SEQUENCE.
+        (
+            "301f0206019123456789020107030304123003020680030902048d159e26af37bc00",
+            vec![Problem::TrailingData { offset: 33 }],
+        ),
+        // rand-b cut short.
+        (
+            "301f0206019123456789020107030304123003020680030902048d15",
+            vec![
+                Problem::Truncated {
+                    offset: 28,
+                    missing: 5,
+                },
+                Problem::Truncated {
+                    offset: 28,
+                    missing: 5,
+                },
+            ],
+        ),
+        // No rand-b.
+        (
+            "3014020601912345678902010703030412300302068000",
+            vec![
+                Problem::MissingComponent {
+                    offset: 22,
+                    component: Component::Field(RawUuidV7Field::RandB),
+                },
+                Problem::TrailingData { offset: 22 },
+            ],
+        ),
+    ];
+    for (hex, expected) in cases {
+        assert_eq!(problems(hex, Schema::RawUuidV7), expected, "{hex}");
+    }
+}
+
+#[test]
+fn cer_is_walked_to_the_end() {
+    let cer_hex = concat!(
+        "3080",
+        "0206019123456789",
+        "02020007",
+        "030304123f",
+        "030206bf",
+        "2380",
+        "030400048d15",
+        "0306029e26af37bd",
+        "0000",
+        "0000",
+    );
+    let expected = [
+        Problem::IndefiniteLength { offset: 1 },
+        Problem::NonMinimalInteger { offset: 12 },
+        Problem::NonZeroUnusedBits { offset: 18 },
+        Problem::NonZeroUnusedBits { offset: 22 },
+        Problem::UnexpectedTag {
+            offset: 23,
+            expected: 0x03,
+            actual: 0x23,
+        },
+        Problem::IndefiniteLength { offset: 24 },
+    ];
+    assert_eq!(problems(cer_hex, Schema::RawUuidV7), expected);
+}
+
+#[test]
+fn meanings_are_shown() {
+    let der_bytes = hex2bytes("301f0206019123456789020104030304123003020640030902048d159e26af37bc");
+    let text = dump::dump(&der_bytes, Schema::RawUuidV7).to_string();
+    assert!(
+        text.contains("version: 4 (not UUIDv7, expected 7)"),
+        "{text}"
+    );
+    assert!(
+        text.contains("variant: 0b01 (reserved, NCS backward compatibility)"),
+        "{text}"
+    );
+
+    let max = RawUuidV7 {
+        unix_ts_ms: 0xFFFF_FFFF_FFFF,
+        ..RawUuidV7::from(0u128)
+    };
+    let der_bytes = RawUuidV7Asn1::try_from(max)
+        .expect("encodable")
+        .to_der_bytes()
+        .expect("encodable");
+    let text = dump::dump(&der_bytes, Schema::RawUuidV7).to_string();
+    assert!(text.contains("(10889-08-02T05:31:50.655Z)"), "{text}");
+}
+
+#[test]
+fn unreadable_input_is_a_single_problem() {
+    assert_eq!(
+        problems("", Schema::RawUuidV7),
+        [Problem::Truncated {
+            offset: 0,
+            missing: 1,
+        }]
+    );
+    assert_eq!(
+        problems("3f00", Schema::UuidV7),
+        [Problem::HighTagNumber { offset: 0 }]
+    );
+}
+
+proptest! {
+    #[test]
+    fn canonical_der_has_no_problems(value in any::<u128>()) {
+        let der_bytes = RawUuidV7Asn1::try_from(RawUuidV7::from(value))
+            .expect("encodable")
+            .to_der_bytes()
+            .expect("encodable");
+        let dump = dump::dump(&der_bytes, Schema::RawUuidV7);
+        prop_assert!(dump.is_der(), "{}", dump);
+    }
+
+    #[test]
+    fn problems_stay_within_the_input(bytes in proptest::collection::vec(any::<u8>(), 0..48)) {
+        for schema in [Schema::RawUuidV7, Schema::UuidV7] {
+            let dump = dump::dump(&bytes, schema);
+            let _ = dump.to_string();
+            for problem in dump.problems() {
+                prop_assert!(problem.offset() <= bytes.len(), "{}", problem);
+            }
+        }
+    }
+}
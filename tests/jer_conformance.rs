@@ -0,0 +1,324 @@
+//! Checks the JER codec against the golden vectors in `tests/vectors`.
+//!
+//! The vectors are written offline by the encoders in `gen-vectors.py`; the
+//! header of each file says whether asn1tools cross-checked them against
+//! `uuid-v7.asn1`.
+
+mod common;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::FieldError;
+use rs_asn1der2uuid7::JerError;
+use rs_asn1der2uuid7::RawUuidV7;
+#[cfg(feature = "alloc")]
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::UuidV7;
+
+use uuid::Uuid;
+
+#[cfg(feature = "alloc")]
+use common::any_raw_uuid_v7;
+use common::assert_rejected;
+#[cfg(feature = "alloc")]
+use common::hex2bytes;
+use common::mixed_1;
+use common::raw_uuid_of;
+use common::records;
+
+const RAW_UUID_V7_VECTORS: &str = include_str!("vectors/raw-uuid-v7.jer.txt");
+const UUID_V7_VECTORS: &str = include_str!("vectors/uuid-v7.jer.txt");
+#[cfg(feature = "alloc")]
+const RAW_UUID_V7_DER_VECTORS: &str = include_str!("vectors/raw-uuid-v7.der.txt");
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.jer.txt`, reordered, spread
+/// over lines, with a plain hex string and escapes.
+const MIXED_1_LOOSE: &str = r#"
+{
+  "rand-b": { "length": 62, "value": "048d159e26af37bc" },
+  "variant": "80",
+  "rand-a": { "value": "1230", "length": 12 },
+  "version": 7,
+  "unix\u002dts-ms": 1722873636745
+}
+"#;
+
+#[test]
+fn raw_uuid_v7_codec_matches_corpus() {
+    for record in records(RAW_UUID_V7_VECTORS) {
+        let raw_uuid = raw_uuid_of(&record);
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            raw_uuid.to_jer_string().ok().as_deref(),
+            Some(record[6]),
+            "{}",
+            record[0]
+        );
+
+        let decoded = RawUuidV7::from_jer(record[6]).expect("decodable");
+        assert_eq!(decoded, raw_uuid, "{}", record[0]);
+    }
+}
+
+#[test]
+fn uuid_v7_codec_matches_corpus() {
+    for record in records(UUID_V7_VECTORS) {
+        let uuid = Uuid::parse_str(record[1]).expect("uuid");
+        let uuid_v7 = UuidV7::try_from(uuid).expect("valid UUIDv7");
+        #[cfg(feature = "alloc")]
+        assert_eq!(uuid_v7.to_jer_string(), record[2], "{}", record[0]);
+
+        let decoded = UuidV7::from_jer(record[2]).expect("decodable");
+        assert_eq!(decoded, uuid_v7, "{}", record[0]);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn jer_decodes_to_the_same_value_as_der() {
+    let der_records = records(RAW_UUID_V7_DER_VECTORS);
+    for (jer_record, der_record) in records(RAW_UUID_V7_VECTORS).zip(der_records) {
+        assert_eq!(jer_record[0], der_record[0]);
+        let der_bytes = (0..der_record[6].len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&der_record[6][i..i + 2], 16).expect("hex"))
+            .collect::<Vec<u8>>();
+        let from_der = RawUuidV7Asn1::from_der_bytes(&der_bytes)
+            .and_then(|asn1| Ok(RawUuidV7::try_from(asn1)?))
+            .expect("valid DER");
+        let from_jer = RawUuidV7::from_jer(jer_record[6]).expect("valid JER");
+        assert_eq!(from_jer, from_der, "{}", jer_record[0]);
+    }
+}
+
+#[test]
+fn loose_jer_is_accepted() {
+    assert_eq!(RawUuidV7::from_jer(MIXED_1_LOOSE).ok(), Some(mixed_1()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn wide_bit_string_field_is_rejected() {
+    let raw_uuid = RawUuidV7 {
+        rand_a: 0x1000,
+        ..RawUuidV7::from(0u128)
+    };
+    assert!(matches!(raw_uuid.to_jer_string(), Err(Error::Field(_))));
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+    let cases = [
+        ("", JerError::UnexpectedEnd),
+        (
+            "[]",
+            JerError::UnexpectedCharacter {
+                character: '[',
+                position: 0,
+                expected: "an object",
+            },
+        ),
+        (
+            "{\"version\":7,\"colour\":1}",
+            JerError::UnknownMember { position: 13 },
+        ),
+        (
+            "{\"version\":7,\"version\":7}",
+            JerError::DuplicateMember {
+                name: "version",
+                position: 13,
+            },
+        ),
+        (
+            "{\"version\":7}",
+            JerError::MissingMember { name: "unix-ts-ms" },
+        ),
+        (
+            "{\"version\":07}",
+            JerError::NonMinimalInteger {
+                field: RawUuidV7Field::Version,
+                position: 11,
+            },
+        ),
+        (
+            "{\"version\":-7}",
+            JerError::NegativeInteger {
+                field: RawUuidV7Field::Version,
+                position: 11,
+            },
+        ),
+        (
+            "{\"version\":7.0}",
+            JerError::UnexpectedCharacter {
+                character: '.',
+                position: 12,
+                expected: "an integer",
+            },
+        ),
+        (
+            "{\"unix-ts-ms\":18446744073709551616}",
+            JerError::IntegerOverflow {
+                field: RawUuidV7Field::UnixTsMs,
+                position: 14,
+            },
+        ),
+        (
+            "{\"rand-a\":\"123\"}",
+            JerError::InvalidHex {
+                field: RawUuidV7Field::RandA,
+                position: 10,
+            },
+        ),
+        (
+            "{\"rand-a\":\"12g0\"}",
+            JerError::InvalidHex {
+                field: RawUuidV7Field::RandA,
+                position: 10,
+            },
+        ),
+        (
+            "{\"rand-a\":\"0000000000000000000000000000000000\"}",
+            JerError::StringTooLong { position: 10 },
+        ),
+        ("{\"version\":7", JerError::UnexpectedEnd),
+    ];
+    assert_rejected(
+        cases,
+        |jer| RawUuidV7::from_jer(jer),
+        |e| match e {
+            Error::Jer(e) => Some(e),
+            _ => None,
+        },
+    );
+}
+
+#[test]
+fn trailing_content_is_rejected() {
+    let record = records(RAW_UUID_V7_VECTORS).next().expect("vectors");
+    let jer = format!("{} {{}}", record[6]);
+    let position = record[6].len() + 1;
+    let result = RawUuidV7::from_jer(&jer);
+    assert!(
+        matches!(result, Err(Error::Jer(JerError::TrailingData { position: p })) if p == position),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn bit_string_errors_are_field_errors() {
+    let cases = [
+        (
+            "{\"length\":11,\"value\":\"1230\"}",
+            FieldError::InvalidBitLength {
+                field: RawUuidV7Field::RandA,
+                expected: 12,
+                actual: 11,
+            },
+        ),
+        (
+            "\"1231\"",
+            FieldError::NonZeroUnusedBits {
+                field: RawUuidV7Field::RandA,
+            },
+        ),
+    ];
+    for (rand_a, expected) in cases {
+        let jer = MIXED_1_LOOSE.replace(r#"{ "value": "1230", "length": 12 }"#, rand_a);
+        let result = RawUuidV7::from_jer(&jer);
+        assert!(
+            matches!(result, Err(Error::Field(e)) if e == expected),
+            "{rand_a}: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn wide_timestamps_are_rejected() {
+    fn is_expected<T>(result: &Result<T, Error>) -> bool {
+        let expected = FieldError::OutOfRange {
+            field: RawUuidV7Field::UnixTsMs,
+            value: 1 << 48,
+        };
+        matches!(result, Err(Error::Field(e)) if *e == expected)
+    }
+
+    #[cfg(feature = "alloc")]
+    {
+        let raw_uuid = RawUuidV7 {
+            unix_ts_ms: 1 << 48,
+            ..mixed_1()
+        };
+        let result = raw_uuid.to_jer_string();
+        assert!(is_expected(&result), "{result:?}");
+    }
+
+    // mixed-1 with unix-ts-ms = 2^48.
+    let jer = MIXED_1_LOOSE.replacen("1722873636745", "281474976710656", 1);
+    let result = RawUuidV7::from_jer(&jer);
+    assert!(is_expected(&result), "{result:?}");
+
+    let jer = format!(
+        "{{\"unix-ts-ms\":{},\"rand-ab\":\"00000000000000000000\"}}",
+        1u64 << 48
+    );
+    let result = UuidV7::from_jer(&jer);
+    assert!(is_expected(&result), "{result:?}");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn wide_timestamps_are_rejected_as_by_der() {
+    // mixed-1 with unix-ts-ms = 2^48.
+    let der_bytes =
+        hex2bytes("3020020701000000000000020107030304123003020680030902048d159e26af37bc");
+    let from_der = RawUuidV7Asn1::from_der_bytes(&der_bytes)
+        .and_then(|asn1| Ok(RawUuidV7::try_from(asn1)?))
+        .expect_err("out of range");
+    let jer = MIXED_1_LOOSE.replacen("1722873636745", "281474976710656", 1);
+    let from_jer = RawUuidV7::from_jer(&jer).expect_err("out of range");
+    assert!(
+        matches!((&from_jer, &from_der), (Error::Field(a), Error::Field(b)) if a == b),
+        "{from_jer:?} {from_der:?}"
+    );
+    assert_eq!(from_jer.code(), from_der.code());
+}
+
+#[cfg(feature = "alloc")]
+proptest! {
+    #[test]
+    fn raw_uuid_v7_round_trips(raw_uuid in any_raw_uuid_v7()) {
+        let jer = raw_uuid.to_jer_string().expect("encodable");
+        prop_assert_eq!(RawUuidV7::from_jer(&jer).ok(), Some(raw_uuid));
+    }
+
+    #[test]
+    fn uuid_v7_round_trips(value in any::<u128>()) {
+        let value = (value & !(0xF << 76) & !(0x3 << 62)) | (7 << 76) | (2 << 62);
+        let uuid_v7 = UuidV7::try_from(value).expect("valid UUIDv7");
+        prop_assert_eq!(UuidV7::from_jer(&uuid_v7.to_jer_string()).ok(), Some(uuid_v7));
+    }
+
+    #[test]
+    fn jer_and_der_agree(value in any::<u128>()) {
+        let raw_uuid = RawUuidV7::from(value);
+        let der_bytes = RawUuidV7Asn1::try_from(raw_uuid)
+            .expect("encodable")
+            .to_der_bytes()
+            .expect("encodable");
+        let from_der = RawUuidV7Asn1::from_der_bytes(&der_bytes)
+            .and_then(|asn1| Ok(RawUuidV7::try_from(asn1)?))
+            .expect("valid DER");
+        let jer = raw_uuid.to_jer_string().expect("encodable");
+        prop_assert_eq!(RawUuidV7::from_jer(&jer).ok(), Some(from_der));
+    }
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_text_does_not_panic(text in "\\PC{0,64}") {
+        let _ = RawUuidV7::from_jer(&text);
+        let _ = UuidV7::from_jer(&text);
+    }
+}
@@ -0,0 +1,223 @@
+//! Checks the counter of each monotonic strategy at the edges: counter
+//! rollover, clocks that go backwards and the largest timestamp.
+
+use core::time::Duration;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::ErrorCode;
+use rs_asn1der2uuid7::MonotonicGenerator;
+use rs_asn1der2uuid7::MonotonicStrategy;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::TimeError;
+use rs_asn1der2uuid7::UuidV7Seeds;
+
+/// 2024-08-05T16:00:36.745Z.
+const SINCE_EPOCH: Duration = Duration::from_millis(1722873636745);
+
+const UNIX_TS_MS_MAX: u64 = 0xFFFF_FFFF_FFFF;
+const RAND_A_MAX: u16 = 0x0FFF;
+const RAND_B_MAX: u64 = 0x3FFF_FFFF_FFFF_FFFF;
+
+/// Random bits whose `rand_a` part starts the counter of method 1 at 0x7FF.
+const RAND_A_HIGH: u128 = 0x07FF << 64;
+
+fn strategies() -> [MonotonicStrategy; 3] {
+    [
+        MonotonicStrategy::FixedCounterRandA,
+        MonotonicStrategy::RandomIncrementRandB {
+            max_increment: 1 << 20,
+        },
+        MonotonicStrategy::SubMillisecondRandA,
+    ]
+}
+
+fn is_strictly_increasing(values: &[u128]) -> bool {
+    values.windows(2).all(|pair| pair[0] < pair[1])
+}
+
+fn raw_uuids(values: &[u128]) -> Vec<RawUuidV7> {
+    values.iter().map(|value| RawUuidV7::from(*value)).collect()
+}
+
+#[test]
+fn rand_a_counter_rolls_over_into_the_next_millisecond() {
+    let mut generator = MonotonicGenerator::new(MonotonicStrategy::FixedCounterRandA);
+    let values = (0..=0x801)
+        .map(|_| {
+            generator
+                .next_u128_from(SINCE_EPOCH, RAND_A_HIGH)
+                .expect("in range")
+        })
+        .collect::<Vec<_>>();
+
+    assert!(is_strictly_increasing(&values));
+    let first = RawUuidV7::from(values[0]);
+    let last_of_ms = RawUuidV7::from(values[0x800]);
+    let rolled = RawUuidV7::from(values[0x801]);
+    assert_eq!(first.rand_a, 0x7FF);
+    assert_eq!(last_of_ms.rand_a, RAND_A_MAX);
+    assert_eq!(last_of_ms.unix_ts_ms, first.unix_ts_ms);
+    assert_eq!(rolled.unix_ts_ms, first.unix_ts_ms + 1);
+    assert_eq!(rolled.rand_a, 0x7FF);
+}
+
+#[test]
+fn rand_b_counter_rolls_over_near_its_maximum() {
+    let mut generator = MonotonicGenerator::new(MonotonicStrategy::RandomIncrementRandB {
+        max_increment: 1 << 61,
+    });
+    // Starts the counter at RAND_B_MAX / 2 and increments it by 2^61.
+    let random_bytes = (((1u128 << 61) - 1) << 64) | u128::from(u64::MAX);
+    let values = (0..3)
+        .map(|_| {
+            generator
+                .next_u128_from(SINCE_EPOCH, random_bytes)
+                .expect("in range")
+        })
+        .collect::<Vec<_>>();
+
+    assert!(is_strictly_increasing(&values));
+    let raw_uuids = raw_uuids(&values);
+    assert_eq!(raw_uuids[0].rand_b, RAND_B_MAX >> 1);
+    assert_eq!(raw_uuids[1].rand_b, RAND_B_MAX);
+    assert_eq!(raw_uuids[1].unix_ts_ms, raw_uuids[0].unix_ts_ms);
+    assert_eq!(raw_uuids[2].unix_ts_ms, raw_uuids[0].unix_ts_ms + 1);
+    assert_eq!(raw_uuids[2].rand_b, RAND_B_MAX >> 1);
+}
+
+#[test]
+fn sub_millisecond_fraction_survives_a_backward_clock() {
+    let mut generator = MonotonicGenerator::new(MonotonicStrategy::SubMillisecondRandA);
+    let late = SINCE_EPOCH + Duration::from_micros(999);
+    let early = SINCE_EPOCH + Duration::from_micros(100);
+    let mut next = |since_epoch| generator.next_u128_from(since_epoch, 0).expect("in range");
+    let mut values = vec![next(late)];
+    // 999 µs is fraction 4091, so four more values use up the millisecond.
+    values.extend((0..6).map(|_| next(early)));
+    values.push(next(SINCE_EPOCH));
+
+    assert!(is_strictly_increasing(&values));
+    let raw_uuids = raw_uuids(&values);
+    let start_ms = SINCE_EPOCH.as_millis() as u64;
+    assert_eq!(raw_uuids[0].rand_a, 4091);
+    assert_eq!(raw_uuids[4].rand_a, RAND_A_MAX);
+    assert_eq!(raw_uuids[4].unix_ts_ms, start_ms);
+    assert_eq!(
+        (raw_uuids[5].unix_ts_ms, raw_uuids[5].rand_a),
+        (start_ms + 1, 0)
+    );
+    assert_eq!(
+        (raw_uuids[7].unix_ts_ms, raw_uuids[7].rand_a),
+        (start_ms + 1, 2)
+    );
+}
+
+#[test]
+fn timestamp_is_clamped_to_its_maximum() {
+    let beyond = Duration::from_millis(UNIX_TS_MS_MAX + 5);
+    for strategy in strategies() {
+        let mut generator = MonotonicGenerator::new(strategy);
+        let value = generator.next_u128_from(beyond, 0).expect("in range");
+        assert_eq!(
+            RawUuidV7::from(value).unix_ts_ms,
+            UNIX_TS_MS_MAX,
+            "{strategy:?}"
+        );
+    }
+}
+
+#[test]
+fn exhausted_counter_at_the_largest_timestamp_is_an_error() {
+    let max = Duration::from_millis(UNIX_TS_MS_MAX);
+    let beyond = Duration::from_millis(UNIX_TS_MS_MAX + 5);
+    // The `rand_b` counter takes too long to exhaust with small increments;
+    // see the next test.
+    for strategy in [
+        MonotonicStrategy::FixedCounterRandA,
+        MonotonicStrategy::SubMillisecondRandA,
+    ] {
+        let mut generator = MonotonicGenerator::new(strategy);
+        let mut values = Vec::new();
+        let error = loop {
+            match generator.next_u128_from(max, RAND_A_HIGH) {
+                Ok(value) => values.push(value),
+                Err(error) => break error,
+            }
+        };
+
+        assert!(!values.is_empty(), "{strategy:?}");
+        assert!(is_strictly_increasing(&values), "{strategy:?}");
+        assert!(
+            values
+                .iter()
+                .all(|value| RawUuidV7::from(*value).unix_ts_ms == UNIX_TS_MS_MAX),
+            "{strategy:?}"
+        );
+        assert!(
+            matches!(error, Error::Time(TimeError::Overflow)),
+            "{strategy:?}: {error:?}"
+        );
+        assert_eq!(error.code(), ErrorCode::TimestampOverflow);
+        // The generator stays exhausted, whatever the clock says.
+        let result = generator.next_u128_from(beyond, 0);
+        assert!(result.is_err(), "{strategy:?}: {result:?}");
+    }
+}
+
+#[test]
+fn rand_b_counter_is_exhausted_at_the_largest_timestamp() {
+    let mut generator = MonotonicGenerator::new(MonotonicStrategy::RandomIncrementRandB {
+        max_increment: 1 << 61,
+    });
+    let max = Duration::from_millis(UNIX_TS_MS_MAX);
+    let random_bytes = (((1u128 << 61) - 1) << 64) | u128::from(u64::MAX);
+    let first = generator.next_u128_from(max, random_bytes).expect("first");
+    let second = generator.next_u128_from(max, random_bytes).expect("second");
+    assert_eq!(RawUuidV7::from(first).rand_b, RAND_B_MAX >> 1);
+    assert_eq!(RawUuidV7::from(second).rand_b, RAND_B_MAX);
+
+    for _ in 0..2 {
+        let result = generator.next_u128_from(max, random_bytes);
+        assert!(
+            matches!(result, Err(Error::Time(TimeError::Overflow))),
+            "{result:?}"
+        );
+    }
+}
+
+#[test]
+fn seeds_keep_only_48_timestamp_bits() {
+    let seeds = UuidV7Seeds {
+        unix_ts_ms: u64::MAX,
+        random_bytes: 0,
+    };
+    let raw_uuid = RawUuidV7::from(seeds.to_u128());
+    assert_eq!(raw_uuid.unix_ts_ms, UNIX_TS_MS_MAX);
+    assert_eq!(raw_uuid.version, 7);
+    assert_eq!(raw_uuid.variant, 0b10);
+    assert_eq!((raw_uuid.rand_a, raw_uuid.rand_b), (0, 0));
+}
+
+proptest! {
+    #[test]
+    fn output_is_strictly_increasing_under_any_clock(
+        offsets in proptest::collection::vec(0u64..5_000, 1..256),
+        random_bytes in proptest::collection::vec(any::<u128>(), 256),
+    ) {
+        for strategy in strategies() {
+            let mut generator = MonotonicGenerator::new(strategy);
+            let values = offsets
+                .iter()
+                .zip(&random_bytes)
+                .map(|(offset, random_bytes)| {
+                    let since_epoch = SINCE_EPOCH + Duration::from_micros(*offset);
+                    generator.next_u128_from(since_epoch, *random_bytes)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .expect("in range");
+            prop_assert!(is_strictly_increasing(&values), "{:?}", strategy);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Checks the OER codec against the golden vectors in `tests/vectors`.
+//!
+//! The vectors are written offline by the encoders in `gen-vectors.py`; the
+//! header of each file says whether asn1tools cross-checked them against
+//! `uuid-v7.asn1`.
+
+mod common;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::FieldError;
+use rs_asn1der2uuid7::OerError;
+use rs_asn1der2uuid7::OerRules;
+use rs_asn1der2uuid7::RAW_UUID_V7_OER_MAX_LEN;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::UUID_V7_OER_MAX_LEN;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::UuidV7Seeds;
+
+use uuid::Uuid;
+
+use common::any_raw_uuid_v7;
+use common::assert_rejected;
+use common::hex2bytes;
+use common::mixed_1;
+use common::raw_uuid_of;
+use common::records;
+
+const RAW_UUID_V7_VECTORS: &str = include_str!("vectors/raw-uuid-v7.oer.txt");
+const UUID_V7_VECTORS: &str = include_str!("vectors/uuid-v7.oer.txt");
+
+const RULES: [OerRules; 2] = [OerRules::Basic, OerRules::Canonical];
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.oer.txt`.
+const MIXED_1_HEX: &str = "060191234567890107123080048d159e26af37bc";
+
+#[test]
+fn raw_uuid_v7_codec_matches_corpus() {
+    for record in records(RAW_UUID_V7_VECTORS) {
+        let raw_uuid = raw_uuid_of(&record);
+        let oer_bytes = hex2bytes(record[6]);
+        let mut buf = [0u8; RAW_UUID_V7_OER_MAX_LEN];
+        let encoded = raw_uuid.encode_oer(&mut buf).expect("encodable");
+        assert_eq!(encoded, oer_bytes, "{}", record[0]);
+
+        for rules in RULES {
+            let decoded = RawUuidV7::decode_oer(&oer_bytes, rules).expect("decodable");
+            assert_eq!(decoded, raw_uuid, "{} {:?}", record[0], rules);
+        }
+    }
+}
+
+#[test]
+fn uuid_v7_codec_matches_corpus() {
+    for record in records(UUID_V7_VECTORS) {
+        let uuid = Uuid::parse_str(record[1]).expect("uuid");
+        let uuid_v7 = UuidV7::try_from(uuid).expect("valid UUIDv7");
+        let oer_bytes = hex2bytes(record[2]);
+        let mut buf = [0u8; UUID_V7_OER_MAX_LEN];
+        let encoded = uuid_v7.encode_oer(&mut buf).expect("encodable");
+        assert_eq!(encoded, oer_bytes, "{}", record[0]);
+
+        for rules in RULES {
+            let decoded = UuidV7::decode_oer(&oer_bytes, rules).expect("decodable");
+            assert_eq!(decoded, uuid_v7, "{} {:?}", record[0], rules);
+        }
+    }
+}
+
+#[test]
+fn max_oer_lengths_are_tight() {
+    let widest = RawUuidV7 {
+        unix_ts_ms: 0xFFFF_FFFF_FFFF,
+        version: u8::MAX,
+        ..RawUuidV7::from(u128::MAX)
+    };
+    let mut buf = [0u8; RAW_UUID_V7_OER_MAX_LEN];
+    let encoded = widest.encode_oer(&mut buf).expect("encodable");
+    assert_eq!(encoded.len(), RAW_UUID_V7_OER_MAX_LEN);
+
+    let seeds = UuidV7Seeds {
+        unix_ts_ms: 0xFFFF_FFFF_FFFF,
+        random_bytes: u128::MAX,
+    };
+    let uuid_v7 = UuidV7::try_from(u128::from(seeds)).expect("valid UUIDv7");
+    let mut buf = [0u8; UUID_V7_OER_MAX_LEN];
+    let encoded = uuid_v7.encode_oer(&mut buf).expect("encodable");
+    assert_eq!(encoded.len(), UUID_V7_OER_MAX_LEN);
+}
+
+#[test]
+fn small_buffer_is_rejected() {
+    let mut buf = [0u8; 4];
+    let result = mixed_1().encode_oer(&mut buf);
+    assert!(matches!(
+        result,
+        Err(Error::Oer(OerError::BufferTooSmall {
+            needed: 20,
+            actual: 4
+        }))
+    ));
+}
+
+#[test]
+fn wide_bit_string_field_is_rejected() {
+    let raw_uuid = RawUuidV7 {
+        rand_a: 0x1000,
+        ..RawUuidV7::from(0u128)
+    };
+    let mut buf = [0u8; RAW_UUID_V7_OER_MAX_LEN];
+    assert!(matches!(
+        raw_uuid.encode_oer(&mut buf),
+        Err(Error::Field(_))
+    ));
+}
+
+#[test]
+fn non_canonical_input_is_basic_only() {
+    let cases = [
+        (
+            "8106".to_string() + &MIXED_1_HEX[2..],
+            OerError::NonCanonicalLength { offset: 0 },
+        ),
+        (
+            MIXED_1_HEX.replacen("0107", "020007", 1),
+            OerError::NonMinimalInteger {
+                field: RawUuidV7Field::Version,
+                offset: 7,
+            },
+        ),
+        (
+            MIXED_1_HEX.replacen("1230", "123f", 1),
+            OerError::NonZeroUnusedBits {
+                field: RawUuidV7Field::RandA,
+                offset: 9,
+            },
+        ),
+    ];
+    for (hex, expected) in cases {
+        let oer_bytes = hex2bytes(&hex);
+        let basic = RawUuidV7::decode_oer(&oer_bytes, OerRules::Basic);
+        assert_eq!(basic.ok(), Some(mixed_1()), "{hex}");
+
+        let canonical = RawUuidV7::decode_oer(&oer_bytes, OerRules::Canonical);
+        assert!(
+            matches!(canonical, Err(Error::Oer(e)) if e == expected),
+            "{hex}: {canonical:?}"
+        );
+    }
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+    let cases = [
+        ("".to_string(), OerError::Truncated { offset: 0 }),
+        ("00".to_string(), OerError::InvalidLength { offset: 0 }),
+        ("80".to_string(), OerError::InvalidLength { offset: 0 }),
+        (
+            MIXED_1_HEX[..MIXED_1_HEX.len() - 2].to_string(),
+            OerError::Truncated { offset: 12 },
+        ),
+        (
+            "01ff".to_string() + &MIXED_1_HEX[14..],
+            OerError::NegativeInteger {
+                field: RawUuidV7Field::UnixTsMs,
+                offset: 0,
+            },
+        ),
+        (
+            "09010000000000000000".to_string() + &MIXED_1_HEX[14..],
+            OerError::IntegerOverflow {
+                field: RawUuidV7Field::UnixTsMs,
+                offset: 0,
+            },
+        ),
+        (
+            MIXED_1_HEX.to_string() + "ff",
+            OerError::TrailingData {
+                decoded: 20,
+                remaining: 1,
+            },
+        ),
+    ];
+    for rules in RULES {
+        assert_rejected(
+            cases.clone(),
+            |hex| RawUuidV7::decode_oer(&hex2bytes(hex), rules),
+            |e| match e {
+                Error::Oer(e) => Some(e),
+                _ => None,
+            },
+        );
+    }
+}
+
+#[test]
+fn wide_version_is_rejected() {
+    let oer_bytes = hex2bytes(&MIXED_1_HEX.replacen("0107", "020100", 1));
+    let result = RawUuidV7::decode_oer(&oer_bytes, OerRules::Canonical);
+    assert!(matches!(result, Err(Error::Field(_))));
+}
+
+#[test]
+fn wide_timestamps_are_rejected() {
+    fn is_expected<T>(result: &Result<T, Error>) -> bool {
+        let expected = FieldError::OutOfRange {
+            field: RawUuidV7Field::UnixTsMs,
+            value: 1 << 48,
+        };
+        matches!(result, Err(Error::Field(e)) if *e == expected)
+    }
+
+    let raw_uuid = RawUuidV7 {
+        unix_ts_ms: 1 << 48,
+        ..mixed_1()
+    };
+    let mut buf = [0u8; RAW_UUID_V7_OER_MAX_LEN];
+    let result = raw_uuid.encode_oer(&mut buf).map(|_| ());
+    assert!(is_expected(&result), "{result:?}");
+
+    // mixed-1 with unix-ts-ms = 2^48.
+    let oer_bytes = hex2bytes(&MIXED_1_HEX.replacen("06019123456789", "0701000000000000", 1));
+    for rules in RULES {
+        let result = RawUuidV7::decode_oer(&oer_bytes, rules);
+        assert!(is_expected(&result), "{rules:?}: {result:?}");
+    }
+
+    // unix-ts-ms = 2^48, rand-ab = 0.
+    let oer_bytes = hex2bytes("0701000000000000000000000000000000000000");
+    let result = UuidV7::decode_oer(&oer_bytes, OerRules::Canonical);
+    assert!(is_expected(&result), "{result:?}");
+}
+
+proptest! {
+    #[test]
+    fn raw_uuid_v7_round_trips(raw_uuid in any_raw_uuid_v7()) {
+        let mut buf = [0u8; RAW_UUID_V7_OER_MAX_LEN];
+        let encoded = raw_uuid.encode_oer(&mut buf).expect("encodable");
+        prop_assert_eq!(RawUuidV7::decode_oer(encoded, OerRules::Canonical).ok(), Some(raw_uuid));
+    }
+
+    #[test]
+    fn uuid_v7_round_trips(value in any::<u128>()) {
+        let value = (value & !(0xF << 76) & !(0x3 << 62)) | (7 << 76) | (2 << 62);
+        let uuid_v7 = UuidV7::try_from(value).expect("valid UUIDv7");
+        let mut buf = [0u8; UUID_V7_OER_MAX_LEN];
+        let encoded = uuid_v7.encode_oer(&mut buf).expect("encodable");
+        prop_assert_eq!(UuidV7::decode_oer(encoded, OerRules::Canonical).ok(), Some(uuid_v7));
+    }
+}
@@ -0,0 +1,115 @@
+//! Property tests for the ordering of `UuidV7` and the time-range bounds.
+
+use std::collections::HashSet;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::FieldError;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::UuidV7;
+
+const UNIX_TS_MS_MAX: u64 = 0xFFFF_FFFF_FFFF;
+
+fn any_uuid_v7() -> impl Strategy<Value = UuidV7> {
+    any::<u128>().prop_map(|value| {
+        let value = (value & !(0xF << 76) & !(0x3 << 62)) | (7 << 76) | (2 << 62);
+        UuidV7::try_from(value).expect("valid UUIDv7")
+    })
+}
+
+/// A timestamp from the whole 48-bit range, so that the DER INTEGER
+/// encoding of it varies in length.
+fn any_unix_ts_ms() -> impl Strategy<Value = u64> {
+    prop_oneof![
+        0..=UNIX_TS_MS_MAX,
+        (0u32..48).prop_map(|shift| 1 << shift),
+        (1u32..=48).prop_map(|shift| (1 << shift) - 1),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn order_matches_byte_order(a in any_uuid_v7(), b in any_uuid_v7()) {
+        let a_bytes: [u8; 16] = a.into();
+        let b_bytes: [u8; 16] = b.into();
+        prop_assert_eq!(a.cmp(&b), a_bytes.cmp(&b_bytes));
+    }
+
+    #[test]
+    fn hash_matches_equality(a in any_uuid_v7()) {
+        let copy = UuidV7::try_from(u128::from(a)).expect("valid UUIDv7");
+        let set: HashSet<UuidV7> = [a, copy].into_iter().collect();
+        prop_assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn bounds_enclose_every_uuid_with_the_timestamp(uuid_v7 in any_uuid_v7()) {
+        let unix_ts_ms = UnverifiedUuidV7::from(uuid_v7).unix_ts_ms();
+        let min = UuidV7::min_for_unix_ts_ms(unix_ts_ms).expect("48-bit timestamp");
+        let max = UuidV7::max_for_unix_ts_ms(unix_ts_ms).expect("48-bit timestamp");
+        prop_assert!(min <= uuid_v7 && uuid_v7 <= max);
+        prop_assert_eq!(UnverifiedUuidV7::from(min).unix_ts_ms(), unix_ts_ms);
+        prop_assert_eq!(UnverifiedUuidV7::from(max).unix_ts_ms(), unix_ts_ms);
+    }
+
+    #[test]
+    fn range_matches_timestamps(
+        uuid_v7 in any_uuid_v7(),
+        start_ms in any_unix_ts_ms(),
+        end_ms in any_unix_ts_ms(),
+    ) {
+        let range = UuidV7::range_for_unix_ts_ms(start_ms, end_ms).expect("48-bit timestamps");
+        let unix_ts_ms = UnverifiedUuidV7::from(uuid_v7).unix_ts_ms();
+        prop_assert_eq!(range.contains(&uuid_v7), (start_ms..=end_ms).contains(&unix_ts_ms));
+    }
+
+    #[test]
+    fn wide_timestamps_are_rejected(unix_ts_ms in UNIX_TS_MS_MAX + 1..) {
+        let expected = FieldError::OutOfRange { field: RawUuidV7Field::UnixTsMs, value: unix_ts_ms };
+        prop_assert_eq!(UuidV7::min_for_unix_ts_ms(unix_ts_ms), Err(expected));
+        prop_assert_eq!(UuidV7::max_for_unix_ts_ms(unix_ts_ms), Err(expected));
+        prop_assert_eq!(UuidV7::range_for_unix_ts_ms(0, unix_ts_ms), Err(expected));
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod der {
+    use rs_asn1der2uuid7::RawUuidV7;
+    use rs_asn1der2uuid7::RawUuidV7Asn1;
+
+    use super::*;
+
+    fn der_bytes(uuid_v7: UuidV7) -> Vec<u8> {
+        let asn1 = RawUuidV7Asn1::try_from(RawUuidV7::from(uuid_v7)).expect("encodable");
+        asn1.to_der_bytes().expect("encodable")
+    }
+
+    fn with_timestamp() -> impl Strategy<Value = UuidV7> {
+        (any_uuid_v7(), any_unix_ts_ms()).prop_map(|(uuid_v7, unix_ts_ms)| {
+            let value = (u128::from(uuid_v7) & ((1 << 80) - 1)) | (u128::from(unix_ts_ms) << 80);
+            UuidV7::try_from(value).expect("valid UUIDv7")
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn der_order_matches_uuid_order(a in with_timestamp(), b in with_timestamp()) {
+            prop_assert_eq!(der_bytes(a).cmp(&der_bytes(b)), a.cmp(&b));
+        }
+
+        #[test]
+        fn der_range_matches_uuid_range(
+            uuid_v7 in with_timestamp(),
+            start_ms in any_unix_ts_ms(),
+            end_ms in any_unix_ts_ms(),
+        ) {
+            let der_range = RawUuidV7Asn1::der_range_for_unix_ts_ms(start_ms, end_ms)
+                .expect("48-bit timestamps");
+            let range = UuidV7::range_for_unix_ts_ms(start_ms, end_ms).expect("48-bit timestamps");
+            prop_assert_eq!(der_range.start(), &der_bytes(*range.start()));
+            prop_assert_eq!(der_range.end(), &der_bytes(*range.end()));
+            prop_assert_eq!(der_range.contains(&der_bytes(uuid_v7)), range.contains(&uuid_v7));
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Checks PEM armoring and base64 transport of the DER types.
+
+#![cfg(feature = "pem")]
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::ErrorCode;
+use rs_asn1der2uuid7::LabelPolicy;
+use rs_asn1der2uuid7::PemError;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::UuidV7Asn1;
+use rs_asn1der2uuid7::pem;
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.der.txt`.
+const RAW_PEM: &str = "\
+-----BEGIN UUIDV7-----
+MB8CBgGRI0VniQIBBwMDBBIwAwIGgAMJAgSNFZ4mrze8
+-----END UUIDV7-----
+";
+
+/// `mixed-1` from `tests/vectors/uuid-v7.der.txt`.
+const COMPACT_PEM: &str = "\
+-----BEGIN COMPACT UUIDV7-----
+MBUCBgGRI0VniQMLBhIwSNFZ4mrze8A=
+-----END COMPACT UUIDV7-----
+";
+
+fn raw_asn1() -> RawUuidV7Asn1 {
+    let raw_uuid = RawUuidV7 {
+        unix_ts_ms: 1722873636745,
+        version: 7,
+        rand_a: 0x123,
+        variant: 0x2,
+        rand_b: 0x0123456789abcdef,
+    };
+    RawUuidV7Asn1::try_from(raw_uuid).expect("encodable")
+}
+
+fn compact_asn1() -> UuidV7Asn1 {
+    let uuid_v7 = UuidV7::try_from(0x01912345_6789_7123_8123_456789abcdef_u128).expect("valid");
+    UuidV7Asn1::try_from(uuid_v7).expect("encodable")
+}
+
+#[test]
+fn raw_uuid_v7_pem_round_trips() {
+    let asn1 = raw_asn1();
+    assert_eq!(asn1.to_pem_string().ok().as_deref(), Some(RAW_PEM));
+    for policy in [LabelPolicy::Any, LabelPolicy::Strict] {
+        assert_eq!(
+            RawUuidV7Asn1::from_pem_str(RAW_PEM, policy).ok(),
+            Some(asn1.clone())
+        );
+    }
+}
+
+#[test]
+fn uuid_v7_pem_round_trips() {
+    let asn1 = compact_asn1();
+    assert_eq!(asn1.to_pem_string().ok().as_deref(), Some(COMPACT_PEM));
+    assert_eq!(
+        UuidV7Asn1::from_pem_str(COMPACT_PEM, LabelPolicy::Strict).ok(),
+        Some(asn1)
+    );
+}
+
+#[test]
+fn strict_policy_rejects_other_labels() {
+    let relabeled = RAW_PEM.replace("UUIDV7", "CERTIFICATE");
+    assert_eq!(
+        RawUuidV7Asn1::from_pem_str(&relabeled, LabelPolicy::Any).ok(),
+        Some(raw_asn1())
+    );
+
+    let result = RawUuidV7Asn1::from_pem_str(&relabeled, LabelPolicy::Strict);
+    let expected = PemError::UnexpectedLabel {
+        expected: pem::RAW_UUID_V7_PEM_LABEL,
+    };
+    assert!(matches!(&result, Err(Error::Pem(e)) if *e == expected));
+    assert_eq!(result.err().map(|e| e.code()), Some(ErrorCode::Pem));
+
+    // The compact label is a different type.
+    let result = RawUuidV7Asn1::from_pem_str(COMPACT_PEM, LabelPolicy::Strict);
+    assert!(matches!(result, Err(Error::Pem(_))));
+}
+
+#[test]
+fn malformed_pem_is_rejected() {
+    let truncated = &RAW_PEM[..RAW_PEM.len() - 10];
+    let result = RawUuidV7Asn1::from_pem_str(truncated, LabelPolicy::Any);
+    assert!(matches!(result, Err(Error::Pem(PemError::Pem(_)))));
+
+    let mismatched = RAW_PEM.replace("END UUIDV7", "END OTHER");
+    let result = RawUuidV7Asn1::from_pem_str(&mismatched, LabelPolicy::Any);
+    assert!(matches!(result, Err(Error::Pem(PemError::Pem(_)))));
+}
+
+#[test]
+fn wrong_der_type_is_rejected() {
+    // Well-formed PEM holding a compact value.
+    let relabeled = COMPACT_PEM.replace("COMPACT UUIDV7", "UUIDV7");
+    let result = RawUuidV7Asn1::from_pem_str(&relabeled, LabelPolicy::Strict);
+    assert!(matches!(result, Err(Error::Der(_))));
+}
+
+#[test]
+fn base64_round_trips() {
+    let asn1 = raw_asn1();
+    let text = asn1.to_base64().expect("encodable");
+    assert_eq!(text, "MB8CBgGRI0VniQIBBwMDBBIwAwIGgAMJAgSNFZ4mrze8");
+    assert_eq!(
+        RawUuidV7Asn1::from_base64(&format!(" {text}\n")).ok(),
+        Some(asn1)
+    );
+
+    let asn1 = compact_asn1();
+    let text = asn1.to_base64().expect("encodable");
+    assert_eq!(UuidV7Asn1::from_base64(&text).ok(), Some(asn1));
+}
+
+#[test]
+fn malformed_base64_is_rejected() {
+    let result = RawUuidV7Asn1::from_base64("MB8C*gGR");
+    assert!(matches!(result, Err(Error::Pem(PemError::Base64(_)))));
+}
+
+#[test]
+fn stream_skips_explanatory_text() {
+    let asn1 = raw_asn1();
+    let stream = format!("first record\n{RAW_PEM}\nsecond record\n{RAW_PEM}trailer\n");
+    let records = pem::from_pem_stream::<RawUuidV7Asn1>(&stream, LabelPolicy::Strict);
+    assert_eq!(records.ok(), Some(vec![asn1.clone(), asn1]));
+    assert_eq!(
+        pem::from_pem_stream::<RawUuidV7Asn1>("no PEM here\n", LabelPolicy::Strict).ok(),
+        Some(vec![])
+    );
+}
+
+#[test]
+fn stream_stops_at_the_first_error() {
+    let stream = format!("{RAW_PEM}{COMPACT_PEM}{RAW_PEM}");
+    let records = pem::pem_records::<RawUuidV7Asn1>(&stream, LabelPolicy::Strict)
+        .map(|record| record.is_ok())
+        .collect::<Vec<_>>();
+    assert_eq!(records, [true, false]);
+
+    let unterminated = format!("{RAW_PEM}-----BEGIN UUIDV7-----\nMB8C\n");
+    let records = pem::from_pem_stream::<RawUuidV7Asn1>(&unterminated, LabelPolicy::Any);
+    assert!(matches!(records, Err(Error::Pem(PemError::Pem(_)))));
+}
+
+proptest! {
+    #[test]
+    fn pem_stream_round_trips(values in proptest::collection::vec(any::<u128>(), 0..8)) {
+        let records = values
+            .iter()
+            .map(|value| RawUuidV7Asn1::try_from(RawUuidV7::from(*value)).expect("encodable"))
+            .collect::<Vec<_>>();
+        let stream = pem::to_pem_stream(&records).expect("encodable");
+        prop_assert_eq!(
+            pem::from_pem_stream::<RawUuidV7Asn1>(&stream, LabelPolicy::Strict).ok(),
+            Some(records)
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Checks the PER codec against the golden vectors in `tests/vectors`.
+//!
+//! The vectors are written offline by the encoders in `gen-vectors.py`; the
+//! header of each file says whether asn1tools cross-checked them against
+//! `uuid-v7.asn1`.
+
+mod common;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::FieldError;
+use rs_asn1der2uuid7::PerError;
+use rs_asn1der2uuid7::PerVariant;
+use rs_asn1der2uuid7::RAW_UUID_V7_PER_MAX_LEN;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::UUID_V7_PER_MAX_LEN;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::UuidV7Seeds;
+
+use uuid::Uuid;
+
+use common::any_raw_uuid_v7;
+use common::assert_rejected;
+use common::hex2bytes;
+use common::mixed_1;
+use common::raw_uuid_of;
+use common::records;
+
+const RAW_UUID_V7_VECTORS: &str = include_str!("vectors/raw-uuid-v7.per.txt");
+const UUID_V7_VECTORS: &str = include_str!("vectors/uuid-v7.per.txt");
+
+const VARIANTS: [PerVariant; 2] = [PerVariant::Unaligned, PerVariant::Aligned];
+
+#[test]
+fn raw_uuid_v7_codec_matches_corpus() {
+    for record in records(RAW_UUID_V7_VECTORS) {
+        let raw_uuid = raw_uuid_of(&record);
+        for (variant, hex) in VARIANTS.into_iter().zip(&record[6..8]) {
+            let per_bytes = hex2bytes(hex);
+            let mut buf = [0u8; RAW_UUID_V7_PER_MAX_LEN];
+            let encoded = raw_uuid.encode_per(variant, &mut buf).expect("encodable");
+            assert_eq!(encoded, per_bytes, "{} {:?}", record[0], variant);
+
+            let decoded = RawUuidV7::decode_per(&per_bytes, variant).expect("decodable");
+            assert_eq!(decoded, raw_uuid, "{} {:?}", record[0], variant);
+        }
+    }
+}
+
+#[test]
+fn uuid_v7_codec_matches_corpus() {
+    for record in records(UUID_V7_VECTORS) {
+        let uuid = Uuid::parse_str(record[1]).expect("uuid");
+        let uuid_v7 = UuidV7::try_from(uuid).expect("valid UUIDv7");
+        for (variant, hex) in VARIANTS.into_iter().zip(&record[2..4]) {
+            let per_bytes = hex2bytes(hex);
+            let mut buf = [0u8; UUID_V7_PER_MAX_LEN];
+            let encoded = uuid_v7.encode_per(variant, &mut buf).expect("encodable");
+            assert_eq!(encoded, per_bytes, "{} {:?}", record[0], variant);
+
+            let decoded = UuidV7::decode_per(&per_bytes, variant).expect("decodable");
+            assert_eq!(decoded, uuid_v7, "{} {:?}", record[0], variant);
+        }
+    }
+}
+
+#[test]
+fn max_per_lengths_are_tight() {
+    let widest = RawUuidV7 {
+        unix_ts_ms: 0xFFFF_FFFF_FFFF,
+        version: u8::MAX,
+        ..RawUuidV7::from(u128::MAX)
+    };
+    let seeds = UuidV7Seeds {
+        unix_ts_ms: 0xFFFF_FFFF_FFFF,
+        random_bytes: u128::MAX,
+    };
+    let uuid_v7 = UuidV7::try_from(u128::from(seeds)).expect("valid UUIDv7");
+    for variant in VARIANTS {
+        let mut buf = [0u8; RAW_UUID_V7_PER_MAX_LEN];
+        let encoded = widest.encode_per(variant, &mut buf).expect("encodable");
+        assert_eq!(encoded.len(), RAW_UUID_V7_PER_MAX_LEN);
+
+        let mut buf = [0u8; UUID_V7_PER_MAX_LEN];
+        let encoded = uuid_v7.encode_per(variant, &mut buf).expect("encodable");
+        assert_eq!(encoded.len(), UUID_V7_PER_MAX_LEN);
+    }
+}
+
+#[test]
+fn small_buffer_is_rejected() {
+    let raw_uuid = raw_uuid_of(&records(RAW_UUID_V7_VECTORS).last().expect("vectors"));
+    let mut buf = [0u8; 4];
+    let result = raw_uuid.encode_per(PerVariant::Unaligned, &mut buf);
+    assert!(matches!(
+        result,
+        Err(Error::Per(PerError::BufferTooSmall { actual: 4, .. }))
+    ));
+}
+
+#[test]
+fn wide_bit_string_field_is_rejected() {
+    let raw_uuid = RawUuidV7 {
+        rand_a: 0x1000,
+        ..RawUuidV7::from(0u128)
+    };
+    let mut buf = [0u8; RAW_UUID_V7_PER_MAX_LEN];
+    let result = raw_uuid.encode_per(PerVariant::Unaligned, &mut buf);
+    assert!(matches!(result, Err(Error::Field(_))));
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+    let cases = [
+        ("", PerError::Truncated { bit_offset: 0 }),
+        ("00", PerError::InvalidLength { bit_offset: 0 }),
+        ("80", PerError::InvalidLength { bit_offset: 0 }),
+        (
+            "0200010107000800000000000000000000",
+            PerError::NonMinimalInteger {
+                field: RawUuidV7Field::UnixTsMs,
+                bit_offset: 0,
+            },
+        ),
+        (
+            "01ff0107000800000000000000000000",
+            PerError::NegativeInteger {
+                field: RawUuidV7Field::UnixTsMs,
+                bit_offset: 0,
+            },
+        ),
+        (
+            "0100010700080000000000000000ff",
+            PerError::TrailingData {
+                decoded: 14,
+                remaining: 1,
+            },
+        ),
+        (
+            "0100010700080000000000000001",
+            PerError::NonZeroPadding { bit_offset: 108 },
+        ),
+    ];
+    assert_rejected(
+        cases,
+        |hex| RawUuidV7::decode_per(&hex2bytes(hex), PerVariant::Unaligned),
+        |e| match e {
+            Error::Per(e) => Some(e),
+            _ => None,
+        },
+    );
+
+    let aligned_cases = [
+        (
+            "0100010700090000000000000000",
+            PerError::NonZeroPadding { bit_offset: 46 },
+        ),
+        (
+            "0100010700080000000000000001",
+            PerError::NonZeroPadding { bit_offset: 110 },
+        ),
+    ];
+    assert_rejected(
+        aligned_cases,
+        |hex| RawUuidV7::decode_per(&hex2bytes(hex), PerVariant::Aligned),
+        |e| match e {
+            Error::Per(e) => Some(e),
+            _ => None,
+        },
+    );
+}
+
+#[test]
+fn zero_padding_is_accepted() {
+    for variant in VARIANTS {
+        let raw_uuid = RawUuidV7::decode_per(&hex2bytes("0100010700080000000000000000"), variant)
+            .expect("decodable");
+        assert_eq!(
+            raw_uuid,
+            RawUuidV7::from(0x0000_0000_0000_7000_8000_0000_0000_0000)
+        );
+    }
+}
+
+#[test]
+fn wide_timestamps_are_rejected() {
+    fn is_expected<T>(result: &Result<T, Error>) -> bool {
+        let expected = FieldError::OutOfRange {
+            field: RawUuidV7Field::UnixTsMs,
+            value: 1 << 48,
+        };
+        matches!(result, Err(Error::Field(e)) if *e == expected)
+    }
+
+    let raw_uuid = RawUuidV7 {
+        unix_ts_ms: 1 << 48,
+        ..mixed_1()
+    };
+    let record = records(RAW_UUID_V7_VECTORS)
+        .find(|record| record[0] == "mixed-1")
+        .expect("mixed-1");
+    for (variant, hex) in VARIANTS.into_iter().zip(&record[6..8]) {
+        let mut buf = [0u8; RAW_UUID_V7_PER_MAX_LEN];
+        let result = raw_uuid.encode_per(variant, &mut buf).map(|_| ());
+        assert!(is_expected(&result), "{variant:?}: {result:?}");
+
+        // mixed-1 with unix-ts-ms = 2^48.
+        let per_bytes = hex2bytes(&format!("0701000000000000{}", &hex[14..]));
+        let result = RawUuidV7::decode_per(&per_bytes, variant);
+        assert!(is_expected(&result), "{variant:?}: {result:?}");
+    }
+
+    // unix-ts-ms = 2^48, rand-ab = 0.
+    let per_bytes = hex2bytes("0701000000000000000000000000000000");
+    let result = UuidV7::decode_per(&per_bytes, PerVariant::Unaligned);
+    assert!(is_expected(&result), "{result:?}");
+}
+
+proptest! {
+    #[test]
+    fn raw_uuid_v7_round_trips(raw_uuid in any_raw_uuid_v7(), aligned in any::<bool>()) {
+        let variant = if aligned { PerVariant::Aligned } else { PerVariant::Unaligned };
+        let mut buf = [0u8; RAW_UUID_V7_PER_MAX_LEN];
+        let encoded = raw_uuid.encode_per(variant, &mut buf).expect("encodable");
+        prop_assert_eq!(RawUuidV7::decode_per(encoded, variant).ok(), Some(raw_uuid));
+    }
+
+    #[test]
+    fn uuid_v7_round_trips(value in any::<u128>(), aligned in any::<bool>()) {
+        let variant = if aligned { PerVariant::Aligned } else { PerVariant::Unaligned };
+        let value = (value & !(0xF << 76) & !(0x3 << 62)) | (7 << 76) | (2 << 62);
+        let uuid_v7 = UuidV7::try_from(value).expect("valid UUIDv7");
+        let mut buf = [0u8; UUID_V7_PER_MAX_LEN];
+        let encoded = uuid_v7.encode_per(variant, &mut buf).expect("encodable");
+        prop_assert_eq!(UuidV7::decode_per(encoded, variant).ok(), Some(uuid_v7));
+    }
+}
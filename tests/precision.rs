@@ -0,0 +1,148 @@
+//! Checks the sub-millisecond fraction: its conversions, the range of the
+//! times it accepts and the order it gives within one millisecond.
+
+use core::time::Duration;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::TimeError;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::UuidV7Seeds;
+use rs_asn1der2uuid7::precision::fraction_to_nanos;
+use rs_asn1der2uuid7::precision::nanos_to_fraction;
+
+use uuid::NoContext;
+use uuid::Timestamp;
+
+/// 2024-08-05T16:00:36.745Z.
+const SINCE_EPOCH: Duration = Duration::from_millis(1722873636745);
+
+const UNIX_TS_MS_MAX: u64 = 0xFFFF_FFFF_FFFF;
+
+#[test]
+fn fraction_bounds_round_trip() {
+    assert_eq!(nanos_to_fraction(0), 0);
+    assert_eq!(fraction_to_nanos(0), 0);
+
+    assert_eq!(nanos_to_fraction(999_999), 0x0FFF);
+    assert_eq!(fraction_to_nanos(0x0FFF), 999_756);
+    assert_eq!(nanos_to_fraction(fraction_to_nanos(0x0FFF)), 0x0FFF);
+
+    // Whole milliseconds are dropped, and so are bits above the fraction.
+    assert_eq!(nanos_to_fraction(1_000_000), 0);
+    assert_eq!(nanos_to_fraction(1_999_999), 0x0FFF);
+    assert_eq!(fraction_to_nanos(0x1FFF), fraction_to_nanos(0x0FFF));
+}
+
+#[test]
+fn seeds_carry_the_fraction_in_rand_a() {
+    let since_epoch = SINCE_EPOCH + Duration::from_nanos(500_000);
+    let seeds = UuidV7Seeds::from_duration_since_epoch(since_epoch, u128::MAX).expect("in range");
+    assert_eq!(seeds.unix_ts_ms, 1722873636745);
+
+    let unverified = UnverifiedUuidV7(seeds.to_u128());
+    assert_eq!(unverified.rand_a(), 0x0800);
+    assert_eq!(unverified.sub_ms_nanos(), 500_000);
+    assert_eq!(unverified.to_duration_since_epoch(), since_epoch);
+    // The rest of the random bits is kept.
+    assert_eq!(unverified.rand_b(), 0x3FFF_FFFF_FFFF_FFFF);
+
+    let raw_uuid = RawUuidV7::from(seeds.to_u128());
+    assert_eq!(raw_uuid.sub_ms_nanos(), 500_000);
+    assert_eq!(raw_uuid.to_duration_since_epoch(), since_epoch);
+}
+
+#[test]
+fn times_beyond_48_bits_are_rejected() {
+    let max = Duration::from_millis(UNIX_TS_MS_MAX) + Duration::from_nanos(999_999);
+    let seeds = UuidV7Seeds::from_duration_since_epoch(max, 0).expect("in range");
+    assert_eq!(seeds.unix_ts_ms, UNIX_TS_MS_MAX);
+
+    let beyond = Duration::from_millis(UNIX_TS_MS_MAX + 1);
+    assert_eq!(
+        UuidV7Seeds::from_duration_since_epoch(beyond, 0),
+        Err(TimeError::Overflow)
+    );
+
+    let timestamp = Timestamp::from_unix(NoContext, beyond.as_secs(), beyond.subsec_nanos());
+    assert_eq!(
+        UuidV7Seeds::from_timestamp(&timestamp, 0),
+        Err(TimeError::Overflow)
+    );
+}
+
+#[test]
+fn timestamps_keep_their_fraction() {
+    let timestamp = Timestamp::from_unix(NoContext, 1722873636, 745_250_000);
+    let seeds = UuidV7Seeds::from_timestamp(&timestamp, 0).expect("in range");
+    let unverified = UnverifiedUuidV7(seeds.to_u128());
+    assert_eq!(unverified.unix_ts_ms(), 1722873636745);
+    assert_eq!(unverified.rand_a(), nanos_to_fraction(250_000));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn system_times_are_checked() {
+    use std::time::SystemTime;
+
+    let time = SystemTime::UNIX_EPOCH + SINCE_EPOCH + Duration::from_nanos(250_000);
+    let seeds = UuidV7Seeds::from_system_time(time, 0).expect("in range");
+    assert_eq!(UnverifiedUuidV7(seeds.to_u128()).to_system_time(), time);
+    assert_eq!(RawUuidV7::from(seeds.to_u128()).to_system_time(), time);
+
+    let before = SystemTime::UNIX_EPOCH - Duration::from_millis(1);
+    assert_eq!(
+        UuidV7Seeds::from_system_time(before, 0),
+        Err(TimeError::BeforeUnixEpoch)
+    );
+
+    let beyond = SystemTime::UNIX_EPOCH + Duration::from_millis(UNIX_TS_MS_MAX + 1);
+    assert_eq!(
+        UuidV7Seeds::from_system_time(beyond, 0),
+        Err(TimeError::Overflow)
+    );
+}
+
+proptest! {
+    #[test]
+    fn every_fraction_round_trips(fraction in 0u16..0x1000) {
+        let nanos = fraction_to_nanos(fraction);
+        prop_assert!(nanos < 1_000_000);
+        prop_assert_eq!(nanos_to_fraction(nanos), fraction);
+    }
+
+    #[test]
+    fn fractions_keep_the_order_of_nanos(first in 0u32..1_000_000, second in 0u32..1_000_000) {
+        let (first, second) = (first.min(second), first.max(second));
+        prop_assert!(nanos_to_fraction(first) <= nanos_to_fraction(second));
+    }
+
+    #[test]
+    fn sub_millisecond_order_survives_der(
+        first in 0u32..1_000_000,
+        second in 0u32..1_000_000,
+        random_bytes in any::<u128>(),
+    ) {
+        let decoded = |nanos: u32| {
+            let since_epoch = SINCE_EPOCH + Duration::from_nanos(nanos.into());
+            let seeds = UuidV7Seeds::from_duration_since_epoch(since_epoch, random_bytes)
+                .expect("in range");
+            let der = RawUuidV7::from(seeds.to_u128())
+                .to_der_array()
+                .expect("encodable");
+            RawUuidV7::decode_der(&der).expect("decodable")
+        };
+
+        let (first_raw, second_raw) = (decoded(first), decoded(second));
+        prop_assert_eq!(
+            first_raw.to_duration_since_epoch().cmp(&second_raw.to_duration_since_epoch()),
+            nanos_to_fraction(first).cmp(&nanos_to_fraction(second))
+        );
+        if nanos_to_fraction(first) < nanos_to_fraction(second) {
+            let first_uuid = UnverifiedUuidV7::try_from(first_raw).expect("in range");
+            let second_uuid = UnverifiedUuidV7::try_from(second_raw).expect("in range");
+            prop_assert!(first_uuid.0 < second_uuid.0);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Checks that the guarded generator notices backward clock steps and
+//! applies its policy.
+
+use core::time::Duration;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Clock;
+use rs_asn1der2uuid7::ClockRegression;
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::ErrorCode;
+use rs_asn1der2uuid7::GuardedGenerator;
+use rs_asn1der2uuid7::MonotonicStrategy;
+use rs_asn1der2uuid7::RegressionMetrics;
+use rs_asn1der2uuid7::RegressionPolicy;
+use rs_asn1der2uuid7::source::SeededRandom;
+use rs_asn1der2uuid7::source::SteppingClock;
+
+/// 2024-08-05T16:00:36.745Z.
+const START: Duration = Duration::from_millis(1722873636745);
+
+/// A clock that plays back offsets from [`START`] in milliseconds. Sleeping
+/// moves all later readings forward.
+#[derive(Debug)]
+struct ScriptedClock {
+    readings: Vec<i64>,
+    index: usize,
+    slept: Duration,
+}
+
+impl ScriptedClock {
+    fn new(readings: &[i64]) -> Self {
+        Self {
+            readings: readings.to_vec(),
+            index: 0,
+            slept: Duration::ZERO,
+        }
+    }
+}
+
+impl Clock for ScriptedClock {
+    fn now(&mut self) -> Duration {
+        let offset = self.readings[self.index.min(self.readings.len() - 1)];
+        self.index += 1;
+        let now = if offset < 0 {
+            START - Duration::from_millis(offset.unsigned_abs())
+        } else {
+            START + Duration::from_millis(offset.unsigned_abs())
+        };
+        now + self.slept
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        self.slept += duration;
+    }
+}
+
+fn generator(
+    policy: RegressionPolicy,
+    readings: &[i64],
+) -> GuardedGenerator<ScriptedClock, SeededRandom> {
+    GuardedGenerator::new(
+        MonotonicStrategy::FixedCounterRandA,
+        policy,
+        ScriptedClock::new(readings),
+        SeededRandom::new(7),
+    )
+}
+
+fn timestamp(value: u128) -> u64 {
+    (value >> 80) as u64
+}
+
+#[test]
+fn reuse_keeps_the_latest_timestamp() {
+    let mut generator = generator(RegressionPolicy::Reuse, &[0, 5, -20, 3, 6]);
+    let values = (0..5)
+        .map(|_| generator.next_u128().expect("reused"))
+        .collect::<Vec<_>>();
+
+    assert!(
+        values.windows(2).all(|pair| pair[0] < pair[1]),
+        "{values:x?}"
+    );
+    let start_ms = START.as_millis() as u64;
+    assert_eq!(timestamp(values[2]), start_ms + 5);
+    assert_eq!(timestamp(values[3]), start_ms + 5);
+    assert_eq!(timestamp(values[4]), start_ms + 6);
+    assert_eq!(
+        generator.metrics(),
+        RegressionMetrics {
+            regressions: 2,
+            reused: 2,
+            largest_step: Duration::from_millis(25),
+            ..RegressionMetrics::default()
+        }
+    );
+}
+
+#[test]
+fn block_waits_for_the_clock() {
+    let policy = RegressionPolicy::Block {
+        max_wait: Duration::from_millis(50),
+    };
+    let mut generator = generator(policy, &[10, -20]);
+    let first = generator.next_u128().expect("first");
+    let second = generator.next_u128().expect("waited");
+
+    assert!(first < second);
+    assert_eq!(timestamp(second), START.as_millis() as u64 + 10);
+    let metrics = generator.metrics();
+    assert_eq!(metrics.blocked, 1);
+    assert_eq!(metrics.waited, Duration::from_millis(30));
+    assert_eq!(metrics.rejected, 0);
+}
+
+#[test]
+fn block_gives_up_after_max_wait() {
+    let policy = RegressionPolicy::Block {
+        max_wait: Duration::from_millis(50),
+    };
+    let mut generator = generator(policy, &[0, -60]);
+    generator.next_u128().expect("first");
+    let result = generator.next_u128();
+
+    assert!(
+        matches!(result, Err(Error::ClockRegression(_))),
+        "{result:?}"
+    );
+    let metrics = generator.metrics();
+    assert_eq!((metrics.blocked, metrics.rejected), (0, 1));
+    assert_eq!(metrics.waited, Duration::ZERO);
+}
+
+#[test]
+fn reject_returns_the_step() {
+    let mut generator = generator(RegressionPolicy::Reject, &[0, -3, 1]);
+    generator.next_u128().expect("first");
+    let error = generator.next_u128().expect_err("rejected");
+
+    assert_eq!(error.code(), ErrorCode::ClockRegression);
+    assert_eq!(error.code().to_string(), "E019 clock-regression");
+    let Error::ClockRegression(regression) = error else {
+        panic!("{error:?}");
+    };
+    assert_eq!(
+        regression,
+        ClockRegression {
+            last: START,
+            now: START - Duration::from_millis(3),
+        }
+    );
+    assert_eq!(regression.step(), Duration::from_millis(3));
+
+    // A rejected reading does not become the latest one.
+    generator.next_u128().expect("caught up");
+    let metrics = generator.metrics();
+    assert_eq!((metrics.regressions, metrics.rejected), (1, 1));
+
+    generator.reset_metrics();
+    assert_eq!(generator.metrics(), RegressionMetrics::default());
+}
+
+#[test]
+fn forward_clocks_are_not_counted() {
+    let clock = SteppingClock::new(START, Duration::from_micros(100));
+    let mut generator = GuardedGenerator::new(
+        MonotonicStrategy::SubMillisecondRandA,
+        RegressionPolicy::Reject,
+        clock,
+        SeededRandom::new(1),
+    );
+    for _ in 0..1000 {
+        generator.next_uuid_v7().expect("forward");
+    }
+    assert_eq!(generator.metrics(), RegressionMetrics::default());
+}
+
+proptest! {
+    #[test]
+    fn reuse_and_block_stay_monotonic(
+        readings in proptest::collection::vec(-100i64..100, 1..64),
+        block in any::<bool>(),
+    ) {
+        let policy = if block {
+            RegressionPolicy::Block { max_wait: Duration::from_secs(1) }
+        } else {
+            RegressionPolicy::Reuse
+        };
+        let mut generator = generator(policy, &readings);
+        let mut last = None;
+        for _ in 0..readings.len() {
+            let value = generator.next_u128().expect("handled");
+            prop_assert!(last < Some(value));
+            last = Some(value);
+        }
+        let metrics = generator.metrics();
+        prop_assert_eq!(metrics.regressions, metrics.reused + metrics.blocked);
+    }
+}
@@ -0,0 +1,415 @@
+//! Checks the DER codec against the golden vectors in `tests/vectors`.
+//!
+//! The vectors are written offline by the encoders in `gen-vectors.py`; the
+//! header of each file says whether asn1tools cross-checked them against
+//! `uuid-v7.asn1`.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use core::time::Duration;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::BitLayout;
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::ErrorCode;
+use rs_asn1der2uuid7::FieldError;
+use rs_asn1der2uuid7::RAW_UUID_V7_DER_MAX_LEN;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::RawUuidV7Asn1Ref;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::UUID_V7_DER_MAX_LEN;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::UuidV7Asn1;
+use rs_asn1der2uuid7::UuidV7Seeds;
+use rs_asn1der2uuid7::der2uuid_v7;
+use rs_asn1der2uuid7::validate;
+
+use uuid::Uuid;
+
+use common::hex2bytes;
+use common::mixed_1;
+use common::records;
+
+const RAW_UUID_V7_VECTORS: &str = include_str!("vectors/raw-uuid-v7.der.txt");
+const UUID_V7_VECTORS: &str = include_str!("vectors/uuid-v7.der.txt");
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.der.txt`.
+const MIXED_1_DER_HEX: &str = "301f0206019123456789020107030304123003020680030902048d159e26af37bc";
+
+/// `mixed-1` as written by version 0.1.0, with right-aligned BIT STRINGs.
+const MIXED_1_LEGACY_HEX: &str = "301f0206019123456789020107030304012303020602030902\
+0123456789abcdef";
+
+fn parse_u64(s: &str) -> u64 {
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+    .expect("invalid integer in corpus")
+}
+
+struct RawVector {
+    name: String,
+    raw: RawUuidV7,
+    der: Vec<u8>,
+}
+
+fn raw_uuid_v7_vectors() -> Vec<RawVector> {
+    records(RAW_UUID_V7_VECTORS)
+        .map(|fields| {
+            assert_eq!(fields.len(), 7, "malformed record: {fields:?}");
+            RawVector {
+                name: fields[0].into(),
+                raw: RawUuidV7 {
+                    unix_ts_ms: parse_u64(fields[1]),
+                    version: parse_u64(fields[2]) as u8,
+                    rand_a: parse_u64(fields[3]) as u16,
+                    variant: parse_u64(fields[4]) as u8,
+                    rand_b: parse_u64(fields[5]),
+                },
+                der: hex2bytes(fields[6]),
+            }
+        })
+        .collect()
+}
+
+struct CompactVector {
+    name: String,
+    uuid: Uuid,
+    der: Vec<u8>,
+}
+
+fn uuid_v7_vectors() -> Vec<CompactVector> {
+    records(UUID_V7_VECTORS)
+        .map(|fields| {
+            assert_eq!(fields.len(), 3, "malformed record: {fields:?}");
+            CompactVector {
+                name: fields[0].into(),
+                uuid: Uuid::parse_str(fields[1]).expect("invalid uuid in corpus"),
+                der: hex2bytes(fields[2]),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn corpus_is_not_empty() {
+    assert!(!raw_uuid_v7_vectors().is_empty());
+    assert!(!uuid_v7_vectors().is_empty());
+}
+
+#[test]
+fn raw_uuid_v7_encode_matches_corpus() {
+    for v in raw_uuid_v7_vectors() {
+        let asn1 = RawUuidV7Asn1::try_from(v.raw).expect(&v.name);
+        let der = asn1.to_der_bytes().expect(&v.name);
+        assert_eq!(der, v.der, "{}", v.name);
+    }
+}
+
+#[test]
+fn raw_uuid_v7_decode_matches_corpus() {
+    for v in raw_uuid_v7_vectors() {
+        let asn1 = RawUuidV7Asn1::from_der_bytes(&v.der).expect(&v.name);
+        let raw = RawUuidV7::try_from(asn1).expect(&v.name);
+        assert_eq!(raw, v.raw, "{}", v.name);
+    }
+}
+
+#[test]
+fn raw_uuid_v7_slice_codec_matches_corpus() {
+    for v in raw_uuid_v7_vectors() {
+        let mut buf = [0u8; 64];
+        let der = v.raw.encode_der(&mut buf).expect(&v.name);
+        assert_eq!(der, v.der, "{}", v.name);
+        let raw = RawUuidV7::decode_der(&v.der).expect(&v.name);
+        assert_eq!(raw, v.raw, "{}", v.name);
+    }
+}
+
+#[test]
+fn raw_uuid_v7_array_codec_matches_corpus() {
+    for v in raw_uuid_v7_vectors() {
+        let der = v.raw.to_der_array().expect(&v.name);
+        assert_eq!(der.as_bytes(), v.der, "{}", v.name);
+        let asn1 = RawUuidV7Asn1Ref::from_der_bytes(&v.der).expect(&v.name);
+        assert_eq!(asn1.to_der_array().expect(&v.name), der, "{}", v.name);
+        assert_eq!(
+            RawUuidV7::try_from(asn1).expect(&v.name),
+            v.raw,
+            "{}",
+            v.name
+        );
+    }
+}
+
+#[test]
+fn max_der_lengths_are_tight() {
+    let widest = RawUuidV7 {
+        unix_ts_ms: 0xFFFF_FFFF_FFFF,
+        version: u8::MAX,
+        rand_a: 0x0FFF,
+        variant: 0b11,
+        rand_b: 0x3FFF_FFFF_FFFF_FFFF,
+    };
+    let encoded = widest.to_der_array().expect("encodable");
+    assert!(encoded.len() < RAW_UUID_V7_DER_MAX_LEN);
+
+    // Only an ASN.1 value built by hand can hold a wider timestamp.
+    let asn1 = RawUuidV7Asn1 {
+        unix_ts_ms: u64::MAX,
+        ..RawUuidV7Asn1::try_from(widest).expect("encodable")
+    };
+    assert_eq!(
+        asn1.to_der_bytes().expect("encodable").len(),
+        RAW_UUID_V7_DER_MAX_LEN
+    );
+
+    let seeds = UuidV7Seeds {
+        unix_ts_ms: 0xFFFF_FFFF_FFFF,
+        random_bytes: u128::MAX,
+    };
+    let widest = UuidV7::try_from(UnverifiedUuidV7(seeds.to_u128())).expect("valid UUIDv7");
+    assert_eq!(
+        widest.to_der_array().expect("encodable").len(),
+        UUID_V7_DER_MAX_LEN
+    );
+}
+
+#[test]
+fn raw_uuid_v7_and_uuid_v7_vectors_agree() {
+    for (raw, compact) in raw_uuid_v7_vectors().iter().zip(uuid_v7_vectors()) {
+        assert_eq!(raw.name, compact.name);
+        let uuid_v7 = der2uuid_v7(&raw.der).expect(&raw.name);
+        assert_eq!(Uuid::from(uuid_v7), compact.uuid, "{}", raw.name);
+    }
+}
+
+#[test]
+fn uuid_v7_encode_matches_corpus() {
+    for v in uuid_v7_vectors() {
+        let asn1 = RawUuidV7Asn1::try_from(v.uuid).expect(&v.name);
+        let uuid_v7 = UuidV7::try_from(asn1).expect(&v.name);
+        let der = UuidV7Asn1::try_from(uuid_v7)
+            .expect(&v.name)
+            .to_der_bytes()
+            .expect(&v.name);
+        assert_eq!(der, v.der, "{}", v.name);
+    }
+}
+
+#[test]
+fn uuid_v7_decode_matches_corpus() {
+    for v in uuid_v7_vectors() {
+        let asn1 = UuidV7Asn1::from_der_bytes(&v.der).expect(&v.name);
+        let uuid = Uuid::try_from(asn1).expect(&v.name);
+        assert_eq!(uuid, v.uuid, "{}", v.name);
+    }
+}
+
+#[test]
+fn uuid_v7_slice_codec_matches_corpus() {
+    for v in uuid_v7_vectors() {
+        let uuid_v7 = UuidV7::try_from(UnverifiedUuidV7(v.uuid.as_u128())).expect(&v.name);
+        let mut buf = [0u8; 64];
+        let der = uuid_v7.encode_der(&mut buf).expect(&v.name);
+        assert_eq!(der, v.der, "{}", v.name);
+        let decoded = UuidV7::decode_der(&v.der).expect(&v.name);
+        assert_eq!(decoded, uuid_v7, "{}", v.name);
+    }
+}
+
+#[test]
+fn truncated_der_is_reported_as_truncated() {
+    for v in raw_uuid_v7_vectors() {
+        for cut in 0..v.der.len() {
+            let der = &v.der[..cut];
+            let results = [
+                RawUuidV7Asn1::from_der_bytes(der).map(|_| ()),
+                RawUuidV7::decode_der(der).map(|_| ()),
+            ];
+            for result in results {
+                let code = result.map_err(|e| e.code()).err();
+                assert_eq!(code, Some(ErrorCode::Truncated), "{} cut at {cut}", v.name);
+            }
+        }
+    }
+    for v in uuid_v7_vectors() {
+        for cut in 0..v.der.len() {
+            let der = &v.der[..cut];
+            let results = [
+                UuidV7Asn1::from_der_bytes(der).map(|_| ()),
+                UuidV7::decode_der(der).map(|_| ()),
+            ];
+            for result in results {
+                let code = result.map_err(|e| e.code()).err();
+                assert_eq!(code, Some(ErrorCode::Truncated), "{} cut at {cut}", v.name);
+            }
+        }
+    }
+}
+
+#[test]
+fn legacy_layout_is_detected_and_repaired() {
+    let legacy_bytes = hex2bytes(MIXED_1_LEGACY_HEX);
+    let asn1 = RawUuidV7Asn1::from_der_bytes(&legacy_bytes).expect("well-formed DER");
+    assert_eq!(asn1.bit_layout(), BitLayout::LegacyLsbFirst);
+    assert_eq!(asn1.to_raw_uuid_v7_compat(), Ok(mixed_1()));
+    assert_eq!(
+        RawUuidV7::try_from(asn1.clone()),
+        Err(FieldError::NonZeroUnusedBits {
+            field: RawUuidV7Field::RandA,
+        })
+    );
+
+    let repaired = asn1.repair().expect("repairable");
+    assert_eq!(repaired.bit_layout(), BitLayout::MsbFirst);
+    assert_eq!(
+        repaired.to_der_bytes().expect("encodable"),
+        hex2bytes(MIXED_1_DER_HEX)
+    );
+
+    let compat = RawUuidV7Asn1::from_der_bytes_compat(&legacy_bytes).expect("repairable");
+    assert_eq!(compat, repaired);
+}
+
+#[test]
+fn der_layout_is_left_alone_by_repair() {
+    let der_bytes = hex2bytes(MIXED_1_DER_HEX);
+    let asn1 = RawUuidV7Asn1::from_der_bytes(&der_bytes).expect("decodable");
+    assert_eq!(asn1.bit_layout(), BitLayout::MsbFirst);
+    assert_eq!(asn1.clone().repair().expect("unchanged"), asn1);
+    assert_eq!(
+        RawUuidV7Asn1::from_der_bytes_compat(&der_bytes).expect("unchanged"),
+        asn1
+    );
+}
+
+/// A `RawUuidV7` with one BIT STRING field wider than its slot.
+fn wide_bit_string_field() -> impl Strategy<Value = (RawUuidV7, RawUuidV7Field, u64)> {
+    prop_oneof![
+        (0x1000u16..).prop_map(|rand_a| (
+            RawUuidV7 {
+                rand_a,
+                ..mixed_1()
+            },
+            RawUuidV7Field::RandA,
+            u64::from(rand_a)
+        )),
+        (4u8..).prop_map(|variant| (
+            RawUuidV7 {
+                variant,
+                ..mixed_1()
+            },
+            RawUuidV7Field::Variant,
+            u64::from(variant)
+        )),
+        (1u64 << 62..).prop_map(|rand_b| (
+            RawUuidV7 {
+                rand_b,
+                ..mixed_1()
+            },
+            RawUuidV7Field::RandB,
+            rand_b
+        )),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn wide_bit_string_fields_are_not_encoded((raw_uuid, field, value) in wide_bit_string_field()) {
+        let expected = FieldError::OutOfRange { field, value };
+        let is_expected = |result: &Result<_, Error>| {
+            matches!(result, Err(Error::Field(e)) if *e == expected)
+        };
+
+        let owned = RawUuidV7Asn1::try_from(raw_uuid).map(|_| ());
+        prop_assert!(is_expected(&owned), "{:?}", owned);
+        let array = raw_uuid.to_der_array().map(|_| ());
+        prop_assert!(is_expected(&array), "{:?}", array);
+        let mut buf = [0u8; RAW_UUID_V7_DER_MAX_LEN];
+        let slice = raw_uuid.encode_der(&mut buf).map(|_| ());
+        prop_assert!(is_expected(&slice), "{:?}", slice);
+    }
+}
+
+#[test]
+fn timestamps_beyond_48_bits_are_not_encoded() {
+    let raw_uuid = RawUuidV7 {
+        unix_ts_ms: 1 << 48,
+        ..mixed_1()
+    };
+    let expected = FieldError::OutOfRange {
+        field: RawUuidV7Field::UnixTsMs,
+        value: 1 << 48,
+    };
+    let is_expected =
+        |result: &Result<_, Error>| matches!(result, Err(Error::Field(e)) if *e == expected);
+
+    let owned = RawUuidV7Asn1::try_from(raw_uuid).map(|_| ());
+    assert!(is_expected(&owned), "{owned:?}");
+    let array = raw_uuid.to_der_array().map(|_| ());
+    assert!(is_expected(&array), "{array:?}");
+    let mut buf = [0u8; RAW_UUID_V7_DER_MAX_LEN];
+    let slice = raw_uuid.encode_der(&mut buf).map(|_| ());
+    assert!(is_expected(&slice), "{slice:?}");
+}
+
+#[test]
+fn timestamps_beyond_48_bits_have_one_code() {
+    let wide = RawUuidV7 {
+        unix_ts_ms: 1 << 48,
+        ..mixed_1()
+    };
+    let der = hex2bytes("3020020701000000000000020107030304123003020680030902048d159e26af37bc");
+    let beyond = Duration::from_millis(1 << 48);
+    let results = [
+        RawUuidV7::decode_der(&der).map(|_| ()),
+        RawUuidV7Asn1::from_der_bytes(&der)
+            .and_then(UuidV7::try_from)
+            .map(|_| ()),
+        RawUuidV7Asn1::try_from(wide).map(|_| ()),
+        UnverifiedUuidV7::try_from(wide)
+            .map(|_| ())
+            .map_err(Error::from),
+        UuidV7Seeds::from_duration_since_epoch(beyond, 0)
+            .map(|_| ())
+            .map_err(Error::from),
+        validate::decode_strict(&der).map(|_| ()),
+        UuidV7::from_jer(r#"{"unix-ts-ms":18446744073709551616}"#).map(|_| ()),
+    ];
+    for (index, result) in results.into_iter().enumerate() {
+        let code = result.map_err(|e| e.code()).err();
+        assert_eq!(code, Some(ErrorCode::TimestampOverflow), "path {index}");
+    }
+
+    // Other fields keep their own code.
+    let wide = RawUuidV7 {
+        rand_a: 0x1000,
+        ..mixed_1()
+    };
+    let code = RawUuidV7Asn1::try_from(wide).map_err(|e| e.code()).err();
+    assert_eq!(code, Some(ErrorCode::FieldOutOfRange));
+}
+
+proptest! {
+    #[test]
+    fn encoded_raw_uuid_v7_decodes(value in any::<u128>(), version in any::<u8>()) {
+        let raw_uuid = RawUuidV7 {
+            version,
+            ..RawUuidV7::from(value)
+        };
+
+        let der = raw_uuid.to_der_array().expect("encodable");
+        prop_assert_eq!(RawUuidV7::decode_der(&der).ok(), Some(raw_uuid));
+        let der = RawUuidV7Asn1::try_from(raw_uuid)
+            .and_then(|asn1| asn1.to_der_bytes())
+            .expect("encodable");
+        prop_assert_eq!(RawUuidV7::decode_der(&der).ok(), Some(raw_uuid));
+    }
+}
@@ -0,0 +1,148 @@
+//! Checks the `serde` forms: strings and base64 in JSON, bytes in bincode.
+
+#![cfg(feature = "serde")]
+
+mod common;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::RAW_UUID_V7_DER_MAX_LEN;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::UuidV7Asn1;
+
+use serde_test::Compact;
+use serde_test::Configure;
+use serde_test::Token;
+
+use common::hex2bytes;
+use common::mixed_1;
+
+const MIXED_1_STR: &str = "01912345-6789-7123-8123-456789abcdef";
+
+/// The DER encoding of `mixed-1` in base64.
+const MIXED_1_BASE64: &str = "MB8CBgGRI0VniQIBBwMDBBIwAwIGgAMJAgSNFZ4mrze8";
+
+fn mixed_1_uuid_v7() -> UuidV7 {
+    UuidV7::parse_str(MIXED_1_STR).expect("valid UUIDv7")
+}
+
+#[test]
+fn uuid_v7_is_a_string_in_json() {
+    let json = serde_json::to_string(&mixed_1_uuid_v7()).expect("serializable");
+    assert_eq!(json, format!("\"{MIXED_1_STR}\""));
+    let decoded: UuidV7 = serde_json::from_str(&json).expect("deserializable");
+    assert_eq!(decoded, mixed_1_uuid_v7());
+}
+
+#[test]
+fn other_text_forms_are_accepted() {
+    let json = format!("\"{}\"", MIXED_1_STR.replace('-', "").to_uppercase());
+    let decoded: UuidV7 = serde_json::from_str(&json).expect("deserializable");
+    assert_eq!(decoded, mixed_1_uuid_v7());
+}
+
+#[test]
+fn uuid_v7_rejects_other_versions() {
+    let json = "\"01912345-6789-4123-8123-456789abcdef\"";
+    let result = serde_json::from_str::<UuidV7>(json);
+    assert!(result.is_err(), "{result:?}");
+
+    let unverified: UnverifiedUuidV7 = serde_json::from_str(json).expect("any UUID");
+    assert_eq!(
+        unverified.to_string(),
+        "01912345-6789-4123-8123-456789abcdef"
+    );
+}
+
+#[test]
+fn raw_uuid_v7_asn1_is_base64_in_json() {
+    let asn1 = RawUuidV7Asn1::try_from(mixed_1()).expect("encodable");
+    let json = serde_json::to_string(&asn1).expect("serializable");
+    assert_eq!(json, format!("\"{MIXED_1_BASE64}\""));
+    let decoded: RawUuidV7Asn1 = serde_json::from_str(&json).expect("deserializable");
+    assert_eq!(decoded, asn1);
+
+    let result = serde_json::from_str::<RawUuidV7Asn1>("\"MB8C!\"");
+    assert!(result.is_err(), "{result:?}");
+}
+
+#[test]
+fn uuid_v7_is_16_bytes_in_bincode() {
+    let bytes = bincode::serialize(&mixed_1_uuid_v7()).expect("serializable");
+    // bincode puts a 64-bit length before the bytes.
+    assert_eq!(bytes[..8], 16u64.to_le_bytes());
+    assert_eq!(bytes[8..], hex2bytes(&MIXED_1_STR.replace('-', "")));
+    let decoded: UuidV7 = bincode::deserialize(&bytes).expect("deserializable");
+    assert_eq!(decoded, mixed_1_uuid_v7());
+}
+
+#[test]
+fn short_or_long_bytes_are_rejected() {
+    let result = serde_json::from_str::<Compact<UuidV7>>("[1, 2, 3]");
+    assert!(result.is_err(), "{result:?}");
+
+    for len in [15, 17] {
+        let bytes = bincode::serialize(&vec![0x70u8; len]).expect("serializable");
+        let result = bincode::deserialize::<UuidV7>(&bytes);
+        assert!(result.is_err(), "{len}: {result:?}");
+    }
+}
+
+#[test]
+fn byte_sequences_are_accepted() {
+    let mut tokens = vec![Token::Seq { len: Some(16) }];
+    tokens.extend(
+        hex2bytes(&MIXED_1_STR.replace('-', ""))
+            .into_iter()
+            .map(Token::U8),
+    );
+    tokens.push(Token::SeqEnd);
+    serde_test::assert_de_tokens(&mixed_1_uuid_v7().compact(), &tokens);
+}
+
+#[test]
+fn der_sequence_length_is_bounded() {
+    // A size hint far beyond the longest encoding must not be trusted.
+    let mut tokens = vec![Token::Seq {
+        len: Some(usize::MAX),
+    }];
+    tokens.extend((0..=RAW_UUID_V7_DER_MAX_LEN).map(|_| Token::U8(0)));
+    serde_test::assert_de_tokens_error::<Compact<RawUuidV7Asn1>>(
+        &tokens,
+        "invalid length 38, expected base64-encoded DER or DER bytes",
+    );
+}
+
+proptest! {
+    #[test]
+    fn uuid_v7_round_trips(value in any::<u128>()) {
+        let value = (value & !(0xF << 76) & !(0x3 << 62)) | (7 << 76) | (2 << 62);
+        let uuid_v7 = UuidV7::try_from(value).expect("valid UUIDv7");
+
+        let json = serde_json::to_string(&uuid_v7).expect("serializable");
+        prop_assert_eq!(serde_json::from_str::<UuidV7>(&json).ok(), Some(uuid_v7));
+
+        let bytes = bincode::serialize(&uuid_v7).expect("serializable");
+        prop_assert_eq!(bincode::deserialize::<UuidV7>(&bytes).ok(), Some(uuid_v7));
+
+        let asn1 = UuidV7Asn1::try_from(uuid_v7).expect("encodable");
+        let json = serde_json::to_string(&asn1).expect("serializable");
+        prop_assert_eq!(serde_json::from_str::<UuidV7Asn1>(&json).ok(), Some(asn1.clone()));
+        let bytes = bincode::serialize(&asn1).expect("serializable");
+        prop_assert_eq!(bincode::deserialize::<UuidV7Asn1>(&bytes).ok(), Some(asn1));
+    }
+
+    #[test]
+    fn raw_uuid_v7_round_trips(value in any::<u128>()) {
+        let raw_uuid = RawUuidV7::from(value);
+        let json = serde_json::to_string(&raw_uuid).expect("serializable");
+        prop_assert_eq!(serde_json::from_str::<RawUuidV7>(&json).ok(), Some(raw_uuid));
+
+        let asn1 = RawUuidV7Asn1::try_from(raw_uuid).expect("encodable");
+        let bytes = bincode::serialize(&asn1).expect("serializable");
+        prop_assert_eq!(bincode::deserialize::<RawUuidV7Asn1>(&bytes).ok(), Some(asn1));
+    }
+}
@@ -0,0 +1,153 @@
+//! Checks the clocks, random sources and the generator built on them.
+
+use core::time::Duration;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Clock;
+use rs_asn1der2uuid7::MonotonicGenerator;
+use rs_asn1der2uuid7::RandomSource;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::UuidV7Generator;
+use rs_asn1der2uuid7::UuidV7Seeds;
+use rs_asn1der2uuid7::source::FixedClock;
+use rs_asn1der2uuid7::source::FixedRandom;
+use rs_asn1der2uuid7::source::SeededRandom;
+use rs_asn1der2uuid7::source::SteppingClock;
+
+/// 2024-08-05T16:00:36.745Z.
+const START: Duration = Duration::from_millis(1722873636745);
+
+fn run(seed: u64, count: usize) -> Vec<u128> {
+    let clock = SteppingClock::new(START, Duration::from_micros(250));
+    let mut generator = UuidV7Generator::new(clock, SeededRandom::new(seed));
+    (0..count).map(|_| generator.next_u128()).collect()
+}
+
+#[test]
+fn seeded_random_is_splitmix64() {
+    // Reference outputs of SplitMix64 seeded with 0.
+    let mut random = SeededRandom::new(0);
+    assert_eq!(random.next_u64(), 0xE220_A839_7B1D_CDAF);
+    assert_eq!(random.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+    assert_eq!(random.next_u64(), 0x06C4_5D18_8009_454F);
+
+    let mut random = SeededRandom::new(0);
+    assert_eq!(
+        random.next_u128(),
+        0xE220_A839_7B1D_CDAF_6E78_9E6A_A1B9_65F4
+    );
+}
+
+#[test]
+fn stepping_clock_advances_and_saturates() {
+    let mut clock = SteppingClock::new(START, Duration::from_millis(2));
+    assert_eq!(clock.now(), START);
+    assert_eq!(clock.now(), START + Duration::from_millis(2));
+
+    let mut clock = SteppingClock::new(Duration::MAX, Duration::from_millis(1));
+    assert_eq!(clock.now(), Duration::MAX);
+    assert_eq!(clock.now(), Duration::MAX);
+}
+
+#[test]
+fn runs_are_reproducible() {
+    assert_eq!(run(42, 1000), run(42, 1000));
+    assert_ne!(run(42, 10), run(43, 10));
+}
+
+#[test]
+fn generator_combines_sources_through_seeds() {
+    let mut generator = UuidV7Generator::new(FixedClock(START), FixedRandom(u128::MAX));
+    let seeds = UuidV7Seeds {
+        unix_ts_ms: START.as_millis() as u64,
+        random_bytes: u128::MAX,
+    };
+    assert_eq!(generator.next_seeds(), seeds);
+    assert_eq!(generator.next_u128(), seeds.to_u128());
+    assert_eq!(generator.next_uuid_v7().as_u128(), seeds.to_u128());
+
+    let (clock, random) = generator.into_parts();
+    assert_eq!(clock, FixedClock(START));
+    assert_eq!(random, FixedRandom(u128::MAX));
+}
+
+#[test]
+fn timestamp_is_clamped_to_its_maximum() {
+    for ms in [0xFFFF_FFFF_FFFF, 1 << 48, u64::MAX] {
+        let clock = FixedClock(Duration::from_millis(ms));
+        let mut generator = UuidV7Generator::new(clock, FixedRandom(0));
+        assert_eq!(generator.next_seeds().unix_ts_ms, 0xFFFF_FFFF_FFFF, "{ms}");
+        let value = UnverifiedUuidV7(generator.next_u128());
+        assert_eq!(value.unix_ts_ms(), 0xFFFF_FFFF_FFFF, "{ms}");
+    }
+}
+
+#[test]
+fn stepping_clock_drives_the_timestamp() {
+    let clock = SteppingClock::new(START, Duration::from_millis(1));
+    let mut generator = UuidV7Generator::new(clock, SeededRandom::new(7));
+    for i in 0..100 {
+        let value = UnverifiedUuidV7(generator.next_u128());
+        assert_eq!(value.unix_ts_ms(), START.as_millis() as u64 + i);
+    }
+}
+
+#[test]
+fn sources_can_be_borrowed() {
+    let mut clock = SteppingClock::new(START, Duration::from_millis(1));
+    let mut random = SeededRandom::new(1);
+    let first = UuidV7Generator::new(&mut clock, &mut random).next_u128();
+    let second = UuidV7Generator::new(&mut clock, &mut random).next_u128();
+    assert_ne!(first, second);
+    assert_eq!(clock.now(), START + Duration::from_millis(2));
+}
+
+#[test]
+fn monotonic_generator_is_reproducible() {
+    let values = |seed| {
+        let mut clock = FixedClock(START);
+        let mut random = SeededRandom::new(seed);
+        let mut generator = MonotonicGenerator::default();
+        (0..100)
+            .map(|_| generator.next_u128_with(&mut clock, &mut random))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("in range")
+    };
+    let first = values(9);
+    assert_eq!(first, values(9));
+    assert!(first.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn batches_are_reproducible() {
+    let batch = || {
+        let mut generator = MonotonicGenerator::default();
+        generator
+            .next_batch_u128_with(FixedClock(START), SeededRandom::new(3), 50)
+            .expect("in range")
+    };
+    assert_eq!(batch(), batch());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn system_sources_produce_valid_uuid_v7s() {
+    let mut generator = UuidV7Generator::default();
+    let value = generator.next_u128();
+    assert!(UuidV7::try_from(value).is_ok());
+    assert!(generator.next_raw_uuid_v7_asn1().is_ok());
+}
+
+proptest! {
+    #[test]
+    fn every_value_is_a_uuid_v7(seed in any::<u64>(), ms in 0u64..0xFFFF_FFFF_FFFF) {
+        let clock = FixedClock(Duration::from_millis(ms));
+        let mut generator = UuidV7Generator::new(clock, SeededRandom::new(seed));
+        let value = generator.next_u128();
+        prop_assert!(UuidV7::try_from(value).is_ok());
+        prop_assert_eq!(UnverifiedUuidV7(value).unix_ts_ms(), ms);
+    }
+}
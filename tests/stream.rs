@@ -0,0 +1,240 @@
+//! Checks both stream framings: round-trips, record boundaries and input
+//! that ends or grows where it should not.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::io::Cursor;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::ErrorCode;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::stream::DerReader;
+use rs_asn1der2uuid7::stream::DerWriter;
+use rs_asn1der2uuid7::stream::MAX_RECORD_LEN;
+use rs_asn1der2uuid7::stream::SequenceOfReader;
+use rs_asn1der2uuid7::stream::write_sequence_of;
+
+use der::ErrorKind;
+
+use common::hex2bytes;
+use common::mixed_1;
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.der.txt`.
+const DER_HEX: &str = "301f0206019123456789020107030304123003020680030902048d159e26af37bc";
+
+fn asn1_values(count: usize) -> Vec<RawUuidV7Asn1> {
+    (0..count as u64)
+        .map(|i| {
+            let raw_uuid = RawUuidV7 {
+                unix_ts_ms: mixed_1().unix_ts_ms + i,
+                ..mixed_1()
+            };
+            RawUuidV7Asn1::try_from(raw_uuid).expect("encodable")
+        })
+        .collect()
+}
+
+fn concatenated(values: &[RawUuidV7Asn1]) -> Vec<u8> {
+    let mut writer = DerWriter::new(Vec::new());
+    writer.write_all(values).expect("writable");
+    writer.flush().expect("flushable");
+    writer.into_inner()
+}
+
+fn sequence_of(values: &[RawUuidV7Asn1]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_sequence_of(&mut bytes, values).expect("writable");
+    bytes
+}
+
+#[test]
+fn concatenated_records_round_trip() {
+    let values = asn1_values(3);
+    let bytes = concatenated(&values);
+    assert_eq!(bytes[..33], hex2bytes(DER_HEX));
+    assert_eq!(bytes.len(), 3 * 33);
+
+    let decoded = DerReader::new(bytes.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("decodable");
+    assert_eq!(decoded, values);
+}
+
+#[test]
+fn sequence_of_round_trips() {
+    let values = asn1_values(2);
+    let bytes = sequence_of(&values);
+    assert_eq!(bytes[..2], [0x30, 66]);
+    assert_eq!(bytes[2..35], hex2bytes(DER_HEX));
+
+    let decoded = SequenceOfReader::new(bytes.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("decodable");
+    assert_eq!(decoded, values);
+}
+
+#[test]
+fn long_sequence_of_uses_a_long_form_length() {
+    let values = asn1_values(10);
+    let bytes = sequence_of(&values);
+    assert_eq!(bytes[..4], [0x30, 0x82, 0x01, 0x4a]);
+
+    let decoded = SequenceOfReader::new(bytes.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("decodable");
+    assert_eq!(decoded, values);
+}
+
+#[test]
+fn empty_inputs_yield_nothing() {
+    assert!(DerReader::new([].as_slice()).next().is_none());
+
+    let bytes = sequence_of(&[]);
+    assert_eq!(bytes, [0x30, 0x00]);
+    assert!(SequenceOfReader::new(bytes.as_slice()).next().is_none());
+}
+
+#[test]
+fn sequence_of_leaves_following_bytes_unread() {
+    let values = asn1_values(1);
+    let mut bytes = sequence_of(&values);
+    bytes.extend_from_slice(&[0xAA, 0xBB]);
+
+    let mut reader = SequenceOfReader::new(Cursor::new(bytes));
+    assert_eq!(
+        reader.next().transpose().expect("decodable"),
+        Some(values[0].clone())
+    );
+    assert!(reader.next().is_none());
+    assert_eq!(reader.into_inner().position(), 35);
+}
+
+#[test]
+fn position_counts_whole_records() {
+    let values = asn1_values(3);
+    let bytes = concatenated(&values);
+
+    let mut reader = DerReader::new(bytes.as_slice());
+    assert_eq!(reader.position(), 0);
+    let positions = (0..values.len())
+        .map(|_| {
+            reader.read_next().expect("decodable").expect("a record");
+            reader.position()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(positions, [33, 66, 99]);
+    assert!(reader.read_next().expect("clean end").is_none());
+    assert_eq!(reader.position(), 99);
+}
+
+#[test]
+fn truncated_final_record_is_reported() {
+    let bytes = concatenated(&asn1_values(2));
+    for cut in [34, 40, bytes.len() - 1] {
+        let mut reader = DerReader::new(&bytes[..cut]);
+        assert!(reader.next().expect("a record").is_ok());
+        let error = reader.next().expect("an error").expect_err("truncated");
+        assert_eq!(error.code(), ErrorCode::Truncated, "{cut}: {error}");
+        // The same variant as truncated input to the DER decoder.
+        assert!(
+            matches!(&error, Error::Der(e) if matches!(e.kind(), ErrorKind::Incomplete { .. })),
+            "{cut}: {error:?}"
+        );
+        assert!(reader.next().is_none(), "{cut}");
+        assert_eq!(reader.position(), 33, "{cut}");
+    }
+
+    let bytes = sequence_of(&asn1_values(2));
+    for cut in [1, 36, 40, bytes.len() - 1] {
+        let result = SequenceOfReader::new(&bytes[..cut]).collect::<Result<Vec<_>, _>>();
+        let error = result.expect_err("truncated");
+        assert_eq!(error.code(), ErrorCode::Truncated, "{cut}: {error}");
+    }
+}
+
+#[test]
+fn oversized_records_are_rejected_before_buffering() {
+    for header in [
+        // One byte over the limit.
+        [0x30, MAX_RECORD_LEN as u8 - 1].as_slice(),
+        &[0x30, 0x81, 0xFF],
+        &[0x30, 0x84, 0x7F, 0xFF, 0xFF, 0xFF],
+    ] {
+        // Only the header is present; reading the content would fail with
+        // a truncation instead.
+        let mut reader = DerReader::new(Cursor::new(header.to_vec()));
+        let error = reader.read_next().expect_err("oversized");
+        assert!(matches!(error, Error::Der(_)), "{header:02x?}: {error:?}");
+        assert_ne!(error.code(), ErrorCode::Truncated, "{header:02x?}");
+        assert_eq!(reader.into_inner().position(), header.len() as u64);
+    }
+}
+
+#[test]
+fn elements_longer_than_their_container_are_rejected() {
+    let mut bytes = sequence_of(&asn1_values(2));
+    // Claim 40 content bytes; the second element does not fit.
+    bytes[1] = 40;
+
+    let mut reader = SequenceOfReader::new(Cursor::new(bytes));
+    assert!(reader.next().expect("an element").is_ok());
+    let error = reader.next().expect("an error").expect_err("overlong");
+    assert!(matches!(error, Error::Der(_)), "{error:?}");
+    assert!(reader.next().is_none());
+    // Only the element header was read.
+    assert_eq!(reader.into_inner().position(), 37);
+}
+
+#[test]
+fn element_headers_are_bounded_by_their_container() {
+    let values = asn1_values(2);
+    let mut bytes = sequence_of(&values[..1]);
+    // Leave one content byte for a second element, which then follows the
+    // container in full.
+    bytes[1] = 34;
+    bytes.extend_from_slice(&concatenated(&values[1..]));
+
+    let mut reader = SequenceOfReader::new(Cursor::new(bytes));
+    assert!(reader.next().expect("an element").is_ok());
+    let error = reader.next().expect("an error").expect_err("overlong");
+    assert!(matches!(error, Error::Der(_)), "{error:?}");
+    assert_ne!(error.code(), ErrorCode::Truncated, "{error}");
+    // Nothing after the last content byte of the container was read.
+    assert_eq!(reader.into_inner().position(), 36);
+}
+
+#[test]
+fn other_tags_are_rejected() {
+    let mut bytes = hex2bytes(DER_HEX);
+    bytes[0] = 0x31;
+    let error = DerReader::new(bytes.as_slice())
+        .next()
+        .expect("an error")
+        .expect_err("wrong tag");
+    assert!(matches!(error, Error::Der(_)), "{error:?}");
+}
+
+proptest! {
+    #[test]
+    fn both_framings_round_trip(values in proptest::collection::vec(any::<u128>(), 0..20)) {
+        let values = values
+            .into_iter()
+            .map(|value| RawUuidV7Asn1::try_from(value).expect("encodable"))
+            .collect::<Vec<_>>();
+
+        let bytes = concatenated(&values);
+        let mut reader = DerReader::new(bytes.as_slice());
+        let decoded = reader.by_ref().collect::<Result<Vec<_>, _>>();
+        prop_assert_eq!(decoded.ok(), Some(values.clone()));
+        prop_assert_eq!(reader.position(), bytes.len() as u64);
+
+        let bytes = sequence_of(&values);
+        let decoded = SequenceOfReader::new(bytes.as_slice()).collect::<Result<Vec<_>, _>>();
+        prop_assert_eq!(decoded.ok(), Some(values));
+    }
+}
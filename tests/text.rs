@@ -0,0 +1,234 @@
+//! Checks the text forms against the examples in `src/text.rs` and the
+//! positions reported for bad input.
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::ParseError;
+use rs_asn1der2uuid7::TextFormat;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::UuidV7Error;
+
+const VALUE: u128 = 0x01898f2b_1c3d_7abc_9def_0123456789ab;
+
+const FORMS: [(TextFormat, &str); 6] = [
+    (
+        TextFormat::Hyphenated,
+        "01898f2b-1c3d-7abc-9def-0123456789ab",
+    ),
+    (
+        TextFormat::Urn,
+        "urn:uuid:01898f2b-1c3d-7abc-9def-0123456789ab",
+    ),
+    (TextFormat::Braced, "{01898f2b-1c3d-7abc-9def-0123456789ab}"),
+    (TextFormat::Simple, "01898f2b1c3d7abc9def0123456789ab"),
+    (TextFormat::CrockfordBase32, "01H67JP71XFAY9VVR14D2PF2DB"),
+    (TextFormat::Base64Url, "AYmPKxw9eryd7wEjRWeJqw"),
+];
+
+fn invalid(character: char, position: usize, expected: &'static str) -> ParseError {
+    ParseError::InvalidCharacter {
+        character,
+        position,
+        expected,
+    }
+}
+
+#[test]
+fn every_form_matches_its_example() {
+    for (format, text) in FORMS {
+        let unverified = UnverifiedUuidV7(VALUE);
+        assert_eq!(unverified.format(format).to_string(), text, "{format:?}");
+        assert_eq!(text.len(), format.char_len(), "{format:?}");
+        assert_eq!(UnverifiedUuidV7::parse_with(text, format), Ok(unverified));
+        assert_eq!(UnverifiedUuidV7::parse_str(text), Ok(unverified));
+
+        let uuid_v7 = UuidV7::parse_str(text).expect("valid UUIDv7");
+        assert_eq!(uuid_v7.as_u128(), VALUE, "{format:?}");
+        assert_eq!(uuid_v7.format(format).to_string(), text, "{format:?}");
+    }
+    assert_eq!(UnverifiedUuidV7(VALUE).to_string(), FORMS[0].1);
+}
+
+#[test]
+fn formatted_values_can_be_padded() {
+    let formatted = UnverifiedUuidV7(VALUE).format(TextFormat::Base64Url);
+    assert_eq!(format!("[{formatted:>24}]"), "[  AYmPKxw9eryd7wEjRWeJqw]");
+}
+
+#[test]
+fn case_is_ignored() {
+    let expected = Ok(UnverifiedUuidV7(VALUE));
+    for text in [
+        "01898F2B-1C3D-7ABC-9DEF-0123456789AB",
+        "URN:UUID:01898f2b-1c3d-7abc-9def-0123456789AB",
+        "{01898F2B-1c3d-7abc-9def-0123456789ab}",
+        "01898F2B1C3D7ABC9DEF0123456789Ab",
+        "01h67jp71xfay9vvr14d2pf2db",
+    ] {
+        assert_eq!(UnverifiedUuidV7::parse_str(text), expected, "{text}");
+    }
+    // base64url digits are case-sensitive.
+    assert_ne!(
+        UnverifiedUuidV7::parse_str("aYmPKxw9eryd7wEjRWeJqw"),
+        expected
+    );
+}
+
+#[test]
+fn crockford_aliases_are_accepted() {
+    // I and L read as 1, O reads as 0.
+    let expected = UnverifiedUuidV7::parse_str("01H67JP71XFAY9VVR14D2PF2DB");
+    for text in [
+        "O1H67JP71XFAY9VVR14D2PF2DB",
+        "0IH67JP71XFAY9VVR14D2PF2DB",
+        "0lH67JP71XFAY9VVR14D2PF2DB",
+        "oLH67JP71XFAY9VVR14D2PF2DB",
+    ] {
+        assert_eq!(UnverifiedUuidV7::parse_str(text), expected, "{text}");
+    }
+}
+
+#[test]
+fn bad_characters_are_reported_with_their_position() {
+    let cases = [
+        (
+            "01898f2b-1c3d-7abc-9def-0123456789ag",
+            invalid('g', 35, "a hexadecimal digit"),
+        ),
+        (
+            "01898f2b_1c3d-7abc-9def-0123456789ab",
+            invalid('_', 8, "'-'"),
+        ),
+        (
+            "urn:uuld:01898f2b-1c3d-7abc-9def-0123456789ab",
+            invalid('l', 6, "the prefix \"urn:uuid:\""),
+        ),
+        (
+            "(01898f2b-1c3d-7abc-9def-0123456789ab}",
+            invalid('(', 0, "'{'"),
+        ),
+        (
+            "{01898f2b-1c3d-7abc-9def-0123456789ab)",
+            invalid(')', 37, "'}'"),
+        ),
+        (
+            "01898f2b1c3d7abc9def0123456789a ",
+            invalid(' ', 31, "a hexadecimal digit"),
+        ),
+        (
+            "01H67JP71XFAY9VVR14D2PF2DU",
+            invalid('U', 25, "a Crockford Base32 digit"),
+        ),
+        (
+            "AYmPKxw9eryd7wEjRWeJq+",
+            invalid('+', 21, "a base64url digit"),
+        ),
+        (
+            "AYmPKxw9eryd7wEjRWeJqx",
+            invalid('x', 21, "a final base64url digit with zero padding bits"),
+        ),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(UnverifiedUuidV7::parse_str(text), Err(expected), "{text}");
+    }
+}
+
+#[test]
+fn multibyte_characters_are_reported_whole() {
+    let cases = [
+        // The length counts characters, so these match their forms.
+        (
+            "01898f2b-1c3d-7abc-9def-0123456789aé",
+            invalid('é', 35, "a hexadecimal digit"),
+        ),
+        (
+            "01898f2b–1c3d-7abc-9def-0123456789ab",
+            invalid('–', 8, "'-'"),
+        ),
+        (
+            "{01898f2b-1c3d-7abc-9def-0123456789ab」",
+            invalid('」', 37, "'}'"),
+        ),
+        (
+            "01H67JP71XFAY9VVR14D2PF2D€",
+            invalid('€', 25, "a Crockford Base32 digit"),
+        ),
+        (
+            "AYmPKxw9eryd7wEjRWeJ🦀w",
+            invalid('🦀', 20, "a base64url digit"),
+        ),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(UnverifiedUuidV7::parse_str(text), Err(expected), "{text}");
+    }
+    assert_eq!(
+        UnverifiedUuidV7::parse_str("01H67JP71XFAY9VVR14D2PF2D€").map_err(|e| e.to_string()),
+        Err("invalid character '€' at position 25, expected a Crockford Base32 digit".into())
+    );
+}
+
+#[test]
+fn wrong_lengths_are_rejected() {
+    for text in [
+        "",
+        "01898f2b-1c3d-7abc-9def-0123456789a",
+        "01898f2b-1c3d-7abc-9def-0123456789abc",
+        "01H67JP71XFAY9VVR14D2PF2D",
+    ] {
+        let len = text.chars().count();
+        assert_eq!(
+            UnverifiedUuidV7::parse_str(text),
+            Err(ParseError::InvalidLength { len }),
+            "{text}"
+        );
+    }
+
+    // A form picked explicitly must match, even if another one would.
+    assert_eq!(
+        UnverifiedUuidV7::parse_with(FORMS[3].1, TextFormat::Hyphenated),
+        Err(ParseError::InvalidLength { len: 32 })
+    );
+    // Characters are counted, not bytes.
+    assert_eq!(
+        UnverifiedUuidV7::parse_str("ééééééééééééééééééééééé"),
+        Err(ParseError::InvalidLength { len: 23 })
+    );
+}
+
+#[test]
+fn crockford_first_digit_is_at_most_7() {
+    assert_eq!(
+        UnverifiedUuidV7::parse_str("7ZZZZZZZZZZZZZZZZZZZZZZZZZ"),
+        Ok(UnverifiedUuidV7(u128::MAX))
+    );
+    assert_eq!(
+        UnverifiedUuidV7::parse_str("81H67JP71XFAY9VVR14D2PF2DB"),
+        Err(invalid('8', 0, "a Base32 digit from '0' to '7'"))
+    );
+}
+
+#[test]
+fn uuid_v7_checks_the_version() {
+    let result = UuidV7::parse_str("01898f2b-1c3d-4abc-9def-0123456789ab");
+    assert!(
+        matches!(result, Err(Error::Uuid(UuidV7Error::InvalidVersion(4)))),
+        "{result:?}"
+    );
+    let result = UuidV7::parse_str("01898f2b-1c3d-7abc-9def-0123456789ag");
+    assert!(matches!(result, Err(Error::Parse(_))), "{result:?}");
+}
+
+proptest! {
+    #[test]
+    fn every_form_round_trips(value in any::<u128>()) {
+        let unverified = UnverifiedUuidV7(value);
+        for (format, _) in FORMS {
+            let text = unverified.format(format).to_string();
+            prop_assert_eq!(text.len(), format.char_len());
+            prop_assert_eq!(UnverifiedUuidV7::parse_with(&text, format), Ok(unverified));
+            prop_assert_eq!(UnverifiedUuidV7::parse_str(&text), Ok(unverified));
+        }
+    }
+}
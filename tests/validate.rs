@@ -0,0 +1,268 @@
+//! Checks the validating decoder with one hand-edited copy of `mixed-1` per
+//! violation, under both policies.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::ErrorCode;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Asn1;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::UnverifiedUuidV7;
+use rs_asn1der2uuid7::ValidationPolicy;
+use rs_asn1der2uuid7::Violation;
+use rs_asn1der2uuid7::validate;
+
+use common::hex2bytes;
+use common::mixed_1;
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.der.txt`. Its elements start at
+/// offset 2 (`unix-ts-ms`), 10 (`version`), 13 (`rand-a`), 18 (`variant`)
+/// and 22 (`rand-b`).
+const DER_HEX: &str = "301f0206019123456789020107030304123003020680030902048d159e26af37bc";
+
+const MIXED_1: u128 = 0x01912345_6789_7123_8123_456789abcdef;
+
+/// Decodes `hex` under both policies and checks that strict decoding fails
+/// with the first of `expected`, and lenient decoding reports all of them
+/// along with `uuid`.
+fn assert_violations(name: &str, hex: &str, expected: &[Violation], uuid: u128) {
+    let der = hex2bytes(hex);
+
+    match validate::decode_strict(&der) {
+        Err(Error::Violation(violation)) => {
+            assert_eq!(violation, expected[0], "{name}");
+            assert_eq!(violation.offset(), expected[0].offset(), "{name}");
+        }
+        other => panic!("{name}: expected a violation, got {other:?}"),
+    }
+
+    let (lenient, violations) = validate::decode_lenient(&der).expect(name);
+    assert_eq!(violations, expected, "{name}");
+    assert_eq!(lenient, UnverifiedUuidV7(uuid), "{name}");
+
+    let validated = validate::decode(&der, ValidationPolicy::Lenient).expect(name);
+    assert!(!validated.is_valid(), "{name}");
+    assert_eq!(validated.violations, expected, "{name}");
+}
+
+#[test]
+fn mixed_1_matches_the_corpus() {
+    let der = RawUuidV7Asn1::try_from(mixed_1())
+        .and_then(|asn1| asn1.to_der_bytes())
+        .expect("encodable");
+    assert_eq!(der, hex2bytes(DER_HEX));
+}
+
+#[test]
+fn valid_input_has_no_violations() {
+    let der = hex2bytes(DER_HEX);
+    let uuid_v7 = validate::decode_strict(&der).expect("valid UUIDv7");
+    assert_eq!(uuid_v7.as_u128(), MIXED_1);
+
+    let (uuid, violations) = validate::decode_lenient(&der).expect("readable");
+    assert_eq!(uuid, UnverifiedUuidV7(MIXED_1));
+    assert!(violations.is_empty(), "{violations:?}");
+
+    let validated = validate::decode(&der, ValidationPolicy::Strict).expect("valid UUIDv7");
+    assert!(validated.is_valid());
+}
+
+#[test]
+fn non_minimal_lengths_are_violations() {
+    assert_violations(
+        "sequence length",
+        "30811f0206019123456789020107030304123003020680030902048d159e26af37bc",
+        &[Violation::NonMinimalLength { offset: 0 }],
+        MIXED_1,
+    );
+    assert_violations(
+        "version length",
+        "3020020601912345678902810107030304123003020680030902048d159e26af37bc",
+        &[Violation::NonMinimalLength { offset: 10 }],
+        MIXED_1,
+    );
+}
+
+#[test]
+fn non_minimal_integers_are_violations() {
+    assert_violations(
+        "version",
+        "3020020601912345678902020007030304123003020680030902048d159e26af37bc",
+        &[Violation::NonMinimalInteger {
+            field: RawUuidV7Field::Version,
+            offset: 10,
+        }],
+        MIXED_1,
+    );
+    assert_violations(
+        "unix-ts-ms",
+        "3020020700019123456789020107030304123003020680030902048d159e26af37bc",
+        &[Violation::NonMinimalInteger {
+            field: RawUuidV7Field::UnixTsMs,
+            offset: 2,
+        }],
+        MIXED_1,
+    );
+}
+
+#[test]
+fn negative_integers_are_violations() {
+    // -7 is both negative and not version 7.
+    assert_violations(
+        "version",
+        "301f02060191234567890201f9030304123003020680030902048d159e26af37bc",
+        &[
+            Violation::NegativeInteger {
+                field: RawUuidV7Field::Version,
+                offset: 10,
+            },
+            Violation::InvalidVersion {
+                value: -7,
+                offset: 10,
+            },
+        ],
+        0x01912345_6789_9123_8123_456789abcdef,
+    );
+}
+
+#[test]
+fn timestamps_beyond_48_bits_are_violations() {
+    assert_violations(
+        "unix-ts-ms",
+        "3020020701000000000000020107030304123003020680030902048d159e26af37bc",
+        &[Violation::TimestampOutOfRange {
+            value: 1 << 48,
+            offset: 2,
+        }],
+        0x00000000_0000_7123_8123_456789abcdef,
+    );
+}
+
+#[test]
+fn other_versions_are_violations() {
+    assert_violations(
+        "version",
+        "301f0206019123456789020104030304123003020680030902048d159e26af37bc",
+        &[Violation::InvalidVersion {
+            value: 4,
+            offset: 10,
+        }],
+        0x01912345_6789_4123_8123_456789abcdef,
+    );
+}
+
+#[test]
+fn other_variants_are_violations() {
+    assert_violations(
+        "variant",
+        "301f02060191234567890201070303041230030206c0030902048d159e26af37bc",
+        &[Violation::InvalidVariant {
+            value: 0b11,
+            offset: 18,
+        }],
+        0x01912345_6789_7123_c123_456789abcdef,
+    );
+}
+
+#[test]
+fn non_zero_unused_bits_are_violations() {
+    assert_violations(
+        "rand-a",
+        "301f0206019123456789020107030304123103020680030902048d159e26af37bc",
+        &[Violation::NonZeroUnusedBits {
+            field: RawUuidV7Field::RandA,
+            offset: 13,
+        }],
+        MIXED_1,
+    );
+    assert_violations(
+        "rand-b",
+        "301f0206019123456789020107030304123003020680030902048d159e26af37bf",
+        &[Violation::NonZeroUnusedBits {
+            field: RawUuidV7Field::RandB,
+            offset: 22,
+        }],
+        MIXED_1,
+    );
+}
+
+#[test]
+fn strict_errors_map_to_their_codes() {
+    let cases = [
+        (
+            "30811f0206019123456789020107030304123003020680030902048d159e26af37bc",
+            ErrorCode::NonMinimalEncoding,
+        ),
+        (
+            "301f02060191234567890201f9030304123003020680030902048d159e26af37bc",
+            ErrorCode::NegativeInteger,
+        ),
+        (
+            "3020020701000000000000020107030304123003020680030902048d159e26af37bc",
+            ErrorCode::TimestampOverflow,
+        ),
+        (
+            "301f0206019123456789020104030304123003020680030902048d159e26af37bc",
+            ErrorCode::InvalidVersion,
+        ),
+        (
+            "301f02060191234567890201070303041230030206c0030902048d159e26af37bc",
+            ErrorCode::InvalidVariant,
+        ),
+        (
+            "301f0206019123456789020107030304123103020680030902048d159e26af37bc",
+            ErrorCode::NonZeroUnusedBits,
+        ),
+    ];
+    for (hex, code) in cases {
+        let result = validate::decode_strict(&hex2bytes(hex));
+        assert_eq!(result.map_err(|e| e.code()).err(), Some(code), "{hex}");
+    }
+}
+
+#[test]
+fn unreadable_input_fails_under_both_policies() {
+    for hex in [
+        // Truncated `rand-b`.
+        "301f0206019123456789020107030304123003020680030902048d159e26af37",
+        // `rand-a` is one bit short.
+        "301f0206019123456789020107030305123003020680030902048d159e26af37bc",
+        // A trailing byte after the sequence.
+        "301f0206019123456789020107030304123003020680030902048d159e26af37bc00",
+    ] {
+        let der = hex2bytes(hex);
+        for policy in [ValidationPolicy::Strict, ValidationPolicy::Lenient] {
+            let result = validate::decode(&der, policy);
+            assert!(result.is_err(), "{hex} {policy:?}: {result:?}");
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn encoded_uuid_v7_is_strictly_valid(value in any::<u128>()) {
+        let value = (value & !(0xF << 76) & !(0x3 << 62)) | (7 << 76) | (2 << 62);
+        let der = RawUuidV7Asn1::try_from(RawUuidV7::from(value))
+            .and_then(|asn1| asn1.to_der_bytes())
+            .expect("encodable");
+        let uuid_v7 = validate::decode_strict(&der).expect("valid UUIDv7");
+        prop_assert_eq!(uuid_v7.as_u128(), value);
+    }
+
+    #[test]
+    fn lenient_decoding_keeps_every_in_range_field(value in any::<u128>()) {
+        let raw_uuid = RawUuidV7::from(value);
+        let der = RawUuidV7Asn1::try_from(raw_uuid)
+            .and_then(|asn1| asn1.to_der_bytes())
+            .expect("encodable");
+        let (uuid, violations) = validate::decode_lenient(&der).expect("readable");
+        prop_assert_eq!(uuid, UnverifiedUuidV7(value));
+        let is_v7 = raw_uuid.version == 7 && raw_uuid.variant == 0b10;
+        prop_assert_eq!(violations.is_empty(), is_v7);
+    }
+}
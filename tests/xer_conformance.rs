@@ -0,0 +1,255 @@
+//! Checks the XER codec against the golden vectors in `tests/vectors`.
+//!
+//! The vectors are written offline by the encoders in `gen-vectors.py`; the
+//! header of each file says whether asn1tools cross-checked them against
+//! `uuid-v7.asn1`.
+
+mod common;
+
+use proptest::prelude::*;
+
+use rs_asn1der2uuid7::Error;
+use rs_asn1der2uuid7::FieldError;
+use rs_asn1der2uuid7::RawUuidV7;
+use rs_asn1der2uuid7::RawUuidV7Field;
+use rs_asn1der2uuid7::UuidV7;
+use rs_asn1der2uuid7::XerError;
+#[cfg(feature = "alloc")]
+use rs_asn1der2uuid7::XerStyle;
+
+use uuid::Uuid;
+
+#[cfg(feature = "alloc")]
+use common::any_raw_uuid_v7;
+use common::assert_rejected;
+use common::mixed_1;
+use common::raw_uuid_of;
+use common::records;
+
+const RAW_UUID_V7_VECTORS: &str = include_str!("vectors/raw-uuid-v7.xer.txt");
+const UUID_V7_VECTORS: &str = include_str!("vectors/uuid-v7.xer.txt");
+
+/// `mixed-1` from `tests/vectors/raw-uuid-v7.xer.txt`, as basic XER.
+const MIXED_1_BASIC: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<RawUuidV7>
+    <unix-ts-ms> 1722873636745 </unix-ts-ms>
+    <version>7</version>
+    <rand-a>0001 0010 0011</rand-a>
+    <variant>10</variant>
+    <rand-b>
+        000001 00100011 01000101 01100111 10001001 10101011 11001101 11101111
+    </rand-b >
+</RawUuidV7>
+"#;
+
+#[test]
+fn raw_uuid_v7_codec_matches_corpus() {
+    for record in records(RAW_UUID_V7_VECTORS) {
+        let raw_uuid = raw_uuid_of(&record);
+        #[cfg(feature = "alloc")]
+        {
+            let xer = raw_uuid.to_xer_string(XerStyle::Canonical);
+            assert_eq!(xer.ok().as_deref(), Some(record[6]), "{}", record[0]);
+        }
+
+        let decoded = RawUuidV7::from_xer(record[6]).expect("decodable");
+        assert_eq!(decoded, raw_uuid, "{}", record[0]);
+    }
+}
+
+#[test]
+fn uuid_v7_codec_matches_corpus() {
+    for record in records(UUID_V7_VECTORS) {
+        let uuid = Uuid::parse_str(record[1]).expect("uuid");
+        let uuid_v7 = UuidV7::try_from(uuid).expect("valid UUIDv7");
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            uuid_v7.to_xer_string(XerStyle::Canonical),
+            record[2],
+            "{}",
+            record[0]
+        );
+
+        let decoded = UuidV7::from_xer(record[2]).expect("decodable");
+        assert_eq!(decoded, uuid_v7, "{}", record[0]);
+    }
+}
+
+#[test]
+fn basic_xer_is_accepted() {
+    assert_eq!(RawUuidV7::from_xer(MIXED_1_BASIC).ok(), Some(mixed_1()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn indented_style_has_a_line_per_field() {
+    let xer = mixed_1()
+        .to_xer_string(XerStyle::Indented)
+        .expect("encodable");
+    assert_eq!(xer.lines().count(), 7);
+    assert!(
+        xer.lines()
+            .skip(1)
+            .take(5)
+            .all(|line| line.starts_with("  <"))
+    );
+    assert_eq!(RawUuidV7::from_xer(&xer).ok(), Some(mixed_1()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn wide_bit_string_field_is_rejected() {
+    let raw_uuid = RawUuidV7 {
+        rand_a: 0x1000,
+        ..RawUuidV7::from(0u128)
+    };
+    let result = raw_uuid.to_xer_string(XerStyle::Canonical);
+    assert!(matches!(result, Err(Error::Field(_))));
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+    let cases = [
+        ("", XerError::UnexpectedEnd),
+        (
+            "<UuidV7>",
+            XerError::UnexpectedElement {
+                expected: "RawUuidV7",
+                position: 0,
+            },
+        ),
+        (
+            "<RawUuidV7><version>7</version>",
+            XerError::UnexpectedElement {
+                expected: "unix-ts-ms",
+                position: 11,
+            },
+        ),
+        (
+            "<RawUuidV7><unix-ts-ms>x</unix-ts-ms>",
+            XerError::UnexpectedCharacter {
+                character: 'x',
+                position: 23,
+                expected: "a decimal digit",
+            },
+        ),
+        (
+            "<RawUuidV7><unix-ts-ms>01</unix-ts-ms>",
+            XerError::NonMinimalInteger {
+                field: RawUuidV7Field::UnixTsMs,
+                position: 23,
+            },
+        ),
+        (
+            "<RawUuidV7><unix-ts-ms>-1</unix-ts-ms>",
+            XerError::NegativeInteger {
+                field: RawUuidV7Field::UnixTsMs,
+                position: 23,
+            },
+        ),
+        (
+            "<RawUuidV7><unix-ts-ms>18446744073709551616</unix-ts-ms>",
+            XerError::IntegerOverflow {
+                field: RawUuidV7Field::UnixTsMs,
+                position: 23,
+            },
+        ),
+        (
+            "<RawUuidV7><unix-ts-ms>1</unix-ts-ms><version>7</version><rand-a>0000",
+            XerError::UnexpectedEnd,
+        ),
+    ];
+    assert_rejected(
+        cases,
+        |xer| RawUuidV7::from_xer(xer),
+        |e| match e {
+            Error::Xer(e) => Some(e),
+            _ => None,
+        },
+    );
+}
+
+#[test]
+fn trailing_content_is_rejected() {
+    let record = records(RAW_UUID_V7_VECTORS).next().expect("vectors");
+    let xer = format!("{} <RawUuidV7/>", record[6]);
+    let result = RawUuidV7::from_xer(&xer);
+    let position = record[6].len() + 1;
+    assert!(
+        matches!(result, Err(Error::Xer(XerError::TrailingData { position: p })) if p == position),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn wrong_bit_count_is_rejected() {
+    let xer = MIXED_1_BASIC.replace("0001 0010 0011", "0001 0010 001");
+    let expected = FieldError::InvalidBitLength {
+        field: RawUuidV7Field::RandA,
+        expected: 12,
+        actual: 11,
+    };
+    let result = RawUuidV7::from_xer(&xer);
+    assert!(matches!(result, Err(Error::Field(e)) if e == expected));
+}
+
+#[test]
+fn wide_timestamps_are_rejected() {
+    fn is_expected<T>(result: &Result<T, Error>) -> bool {
+        let expected = FieldError::OutOfRange {
+            field: RawUuidV7Field::UnixTsMs,
+            value: 1 << 48,
+        };
+        matches!(result, Err(Error::Field(e)) if *e == expected)
+    }
+
+    #[cfg(feature = "alloc")]
+    {
+        let raw_uuid = RawUuidV7 {
+            unix_ts_ms: 1 << 48,
+            ..mixed_1()
+        };
+        let result = raw_uuid.to_xer_string(XerStyle::Canonical);
+        assert!(is_expected(&result), "{result:?}");
+    }
+
+    // mixed-1 with unix-ts-ms = 2^48.
+    let xer = MIXED_1_BASIC.replacen("1722873636745", "281474976710656", 1);
+    let result = RawUuidV7::from_xer(&xer);
+    assert!(is_expected(&result), "{result:?}");
+
+    let xer = format!(
+        "<UuidV7><unix-ts-ms>{}</unix-ts-ms><rand-ab>{}</rand-ab></UuidV7>",
+        1u64 << 48,
+        "0".repeat(74)
+    );
+    let result = UuidV7::from_xer(&xer);
+    assert!(is_expected(&result), "{result:?}");
+}
+
+#[cfg(feature = "alloc")]
+proptest! {
+    #[test]
+    fn raw_uuid_v7_round_trips(raw_uuid in any_raw_uuid_v7(), indented in any::<bool>()) {
+        let style = if indented { XerStyle::Indented } else { XerStyle::Canonical };
+        let xer = raw_uuid.to_xer_string(style).expect("encodable");
+        prop_assert_eq!(RawUuidV7::from_xer(&xer).ok(), Some(raw_uuid));
+    }
+
+    #[test]
+    fn uuid_v7_round_trips(value in any::<u128>(), indented in any::<bool>()) {
+        let style = if indented { XerStyle::Indented } else { XerStyle::Canonical };
+        let value = (value & !(0xF << 76) & !(0x3 << 62)) | (7 << 76) | (2 << 62);
+        let uuid_v7 = UuidV7::try_from(value).expect("valid UUIDv7");
+        let xer = uuid_v7.to_xer_string(style);
+        prop_assert_eq!(UuidV7::from_xer(&xer).ok(), Some(uuid_v7));
+    }
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_text_does_not_panic(text in "\\PC{0,64}") {
+        let _ = RawUuidV7::from_xer(&text);
+        let _ = UuidV7::from_xer(&text);
+    }
+}